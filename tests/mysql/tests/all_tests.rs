@@ -372,3 +372,24 @@ fn should_be_able_to_write_a_custom_set() {
         q.run(&conn).await.unwrap();
     })
 }
+
+#[test]
+fn should_cancel_a_statement_that_times_out() {
+    async_std::task::block_on(async {
+        use std::time::Duration;
+        use welds::connections::timeout::TimeoutClient;
+        use welds::connections::{Client, Error};
+        let conn = get_conn().await;
+        let client = TimeoutClient::new(&conn, Duration::from_millis(200));
+        let result = client.execute("SELECT SLEEP(30)", &[]).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        // the sleep was killed on the server, it isn't still running
+        let sql =
+            "SELECT count(*) FROM information_schema.processlist WHERE info = 'SELECT SLEEP(30)'";
+        let running: i64 = sqlx::query_scalar(sql)
+            .fetch_one(conn.as_sqlx_pool())
+            .await
+            .unwrap();
+        assert_eq!(running, 0);
+    })
+}
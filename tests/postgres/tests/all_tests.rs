@@ -716,3 +716,45 @@ fn should_be_able_to_write_a_custom_set() {
         q.run(&conn).await.unwrap();
     })
 }
+
+#[test]
+fn should_cancel_a_statement_that_times_out() {
+    async_std::task::block_on(async {
+        use std::time::Duration;
+        use welds::connections::timeout::TimeoutClient;
+        use welds::connections::{Client, Error};
+        let conn = get_conn().await;
+        let client = TimeoutClient::new(&conn, Duration::from_millis(200));
+        let result = client.execute("SELECT pg_sleep(30)", &[]).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        // the sleep was canceled on the server, it isn't still running
+        let sql = "SELECT count(*) FROM pg_stat_activity WHERE state = 'active' AND query = 'SELECT pg_sleep(30)'";
+        let running: i64 = sqlx::query_scalar(sql)
+            .fetch_one(conn.as_sqlx_pool())
+            .await
+            .unwrap();
+        assert_eq!(running, 0);
+    })
+}
+
+#[test]
+fn should_have_the_server_cancel_a_statement_past_the_statement_timeout() {
+    async_std::task::block_on(async {
+        use std::time::Duration;
+        use welds::connections::{Client, Error, PoolConfig};
+        let url = testlib::postgres::conn_string();
+        let config = PoolConfig::new().statement_timeout(Duration::from_millis(200));
+        let conn = welds::connections::postgres::connect_with(&url, &config)
+            .await
+            .unwrap();
+        let result = conn.execute("SELECT pg_sleep(31)", &[]).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        // the sleep was canceled on the server, it isn't still running
+        let sql = "SELECT count(*) FROM pg_stat_activity WHERE state = 'active' AND query = 'SELECT pg_sleep(31)'";
+        let running: i64 = sqlx::query_scalar(sql)
+            .fetch_one(conn.as_sqlx_pool())
+            .await
+            .unwrap();
+        assert_eq!(running, 0);
+    })
+}
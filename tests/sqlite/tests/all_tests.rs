@@ -178,6 +178,32 @@ fn should_be_able_to_crud_with_small_int() {
     })
 }
 
+#[test]
+fn should_be_able_to_use_a_transaction_concurrently() {
+    async_std::task::block_on(async {
+        let client = get_conn().await;
+        let trans = client.begin().await.unwrap();
+
+        let mut p1 = Product::new();
+        let mut p2 = Product::new();
+        let (r1, r2) = futures::join!(p1.save(&trans), p2.save(&trans));
+        r1.unwrap();
+        r2.unwrap();
+        assert!(p1.id != 0);
+        assert!(p2.id != 0);
+        assert!(p1.id != p2.id);
+
+        let (found1, found2) = futures::join!(
+            Product::find_by_id(&trans, p1.id),
+            Product::find_by_id(&trans, p2.id)
+        );
+        assert!(found1.unwrap().is_some());
+        assert!(found2.unwrap().is_some());
+
+        trans.rollback().await.unwrap();
+    })
+}
+
 #[test]
 fn should_be_able_to_order_by_id() {
     async_std::task::block_on(async {
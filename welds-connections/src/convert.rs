@@ -0,0 +1,225 @@
+use std::marker::PhantomData;
+
+/// The error returned when a value read from the database can't be converted
+pub type ConvertError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Converts a field to and from a type the database drivers understand.
+///
+/// Used by models with `#[welds(with = "Converter")]`, so types that don't implement
+/// the driver traits (sqlx/tiberius) can be fields.
+///
+/// ```rust,ignore
+/// pub struct Money(pub i64);
+///
+/// pub struct MoneyCents;
+/// impl DbConverter<Money> for MoneyCents {
+///     type Db = i64;
+///     fn to_db(value: &Money) -> i64 { value.0 }
+///     fn from_db(value: i64) -> Result<Money, ConvertError> { Ok(Money(value)) }
+/// }
+///
+/// #[derive(WeldsModel)]
+/// struct Order {
+///     #[welds(with = "MoneyCents")]
+///     pub total: Money,
+/// }
+/// ```
+///
+/// A converter for `T` also converts `Option<T>`, storing NULL for None.
+pub trait DbConverter<T>: 'static {
+    /// The type stored in the database
+    type Db;
+
+    /// The value to store in the database for this field
+    fn to_db(value: &T) -> Self::Db;
+
+    /// The field for a value read from the database
+    fn from_db(value: Self::Db) -> Result<T, ConvertError>;
+}
+
+impl<C, T> DbConverter<Option<T>> for C
+where
+    C: DbConverter<T>,
+{
+    type Db = Option<C::Db>;
+
+    fn to_db(value: &Option<T>) -> Self::Db {
+        value.as_ref().map(C::to_db)
+    }
+
+    fn from_db(value: Self::Db) -> Result<Option<T>, ConvertError> {
+        value.map(C::from_db).transpose()
+    }
+}
+
+/// A field that is sent to (and read from) the database using the converter `C`.
+///
+/// This is what the generated model code binds and reads for fields with `#[welds(with = ...)]`.
+/// Use `Converted::new(value)` to build one when writing a query by hand.
+#[repr(transparent)]
+pub struct Converted<C, T> {
+    value: T,
+    _converter: PhantomData<fn() -> C>,
+}
+
+impl<C, T> Converted<C, T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            _converter: PhantomData,
+        }
+    }
+
+    /// Views a field as converted, without copying it
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: Converted is repr(transparent) over T
+        unsafe { &*(value as *const T as *const Self) }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<C, T> From<T> for Converted<C, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<C, T: Clone> Clone for Converted<C, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<C, T: std::fmt::Debug> std::fmt::Debug for Converted<C, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Text that is known at compile time, bound as a param.
+///
+/// sqlx only encodes a `&str` for the lifetime of the query, so a `&'static str` isn't a `Param`.
+/// Used to write the discriminator of a model in a single-table inheritance enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticText(pub &'static str);
+
+#[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
+mod sqlx_impls {
+    use super::*;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::{Database, Decode, Encode, Type};
+
+    impl<DB, C, T> Type<DB> for Converted<C, T>
+    where
+        DB: Database,
+        C: DbConverter<T>,
+        C::Db: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <C::Db as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <C::Db as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB, C, T> Encode<'q, DB> for Converted<C, T>
+    where
+        DB: Database,
+        C: DbConverter<T>,
+        C::Db: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            C::to_db(&self.value).encode(buf)
+        }
+    }
+
+    impl<DB> Type<DB> for StaticText
+    where
+        DB: Database,
+        String: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <String as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <String as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB> Encode<'q, DB> for StaticText
+    where
+        DB: Database,
+        String: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            self.0.to_owned().encode(buf)
+        }
+    }
+
+    impl<'r, DB, C, T> Decode<'r, DB> for Converted<C, T>
+    where
+        DB: Database,
+        C: DbConverter<T>,
+        C::Db: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            let value = <C::Db as Decode<'r, DB>>::decode(value)?;
+            Ok(Self::new(C::from_db(value)?))
+        }
+    }
+}
+
+#[cfg(feature = "mssql")]
+mod tiberius_impls {
+    use super::*;
+    use crate::errors::{Error, Result};
+    use crate::row::TiberiusDecode;
+    use tiberius::{Column, ColumnData, IntoSql, ToSql};
+
+    impl<C, T> ToSql for Converted<C, T>
+    where
+        C: DbConverter<T>,
+        C::Db: IntoSql<'static>,
+        T: Send + Sync,
+    {
+        fn to_sql(&self) -> ColumnData<'_> {
+            C::to_db(&self.value).into_sql()
+        }
+    }
+
+    impl ToSql for StaticText {
+        fn to_sql(&self) -> ColumnData<'_> {
+            self.0.into_sql()
+        }
+    }
+
+    impl<C, T> TiberiusDecode for Converted<C, T>
+    where
+        C: DbConverter<T>,
+        C::Db: TiberiusDecode,
+    {
+        fn read(col: &Column, value: ColumnData<'static>) -> Result<Self> {
+            let value = <C::Db as TiberiusDecode>::read(col, value)?;
+            let value = C::from_db(value)
+                .map_err(|err| Error::Convert(col.name().to_owned(), err.to_string()))?;
+            Ok(Self::new(value))
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use crate::errors::{Error, Result};
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+/// Implemented by `#[derive(WeldsEnum)]`.
+///
+/// Maps a fieldless rust enum to the value stored for it in a column.
+/// The encode/decode for each database is generated by the `__welds_enum_*` macros.
+pub trait DbEnum: Sized + Send + Sync + 'static {
+    /// The type stored in the database. `String` for text columns, `i32` for integer columns
+    type Repr: Display;
+
+    /// How the enum is stored in the database
+    const STORAGE: EnumStorage;
+
+    /// The text stored for each variant, in order. Empty for integer enums
+    const TEXT_VALUES: &'static [&'static str];
+
+    /// The value to store in the database for this variant
+    fn to_db(&self) -> Self::Repr;
+
+    /// The variant for a value read from the database
+    fn from_db(value: &Self::Repr) -> Option<Self>;
+}
+
+/// How an enum is stored in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumStorage {
+    /// The name of the variant (or its rename) in a text column
+    Text,
+    /// The discriminant of the variant in an integer column
+    Integer,
+    /// A native Postgres ENUM type with this name.
+    /// Other databases store the text in a text column
+    PostgresEnum(&'static str),
+}
+
+/// Reads a variant from the value stored in the database
+pub fn decode_db_value<T: DbEnum>(value: T::Repr) -> Result<T> {
+    T::from_db(&value).ok_or_else(|| {
+        Error::InvalidEnumValue(std::any::type_name::<T>().to_owned(), value.to_string())
+    })
+}
+
+/// A NULL for an `Option` of a `DbEnum`, bound as a param.
+///
+/// tiberius only implements `ToSql` for `Option` of its own types,
+/// so the generated model code binds a `#[welds(db_enum)]` field that is None as this.
+/// It is bound as a NULL of the type the enum is stored as.
+pub struct EnumNull<T>(PhantomData<fn() -> T>);
+
+impl<T> EnumNull<T> {
+    pub const NULL: Self = EnumNull(PhantomData);
+}
+
+#[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
+mod sqlx_impls {
+    use super::EnumNull;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::{Database, Encode, Type};
+
+    impl<DB, T> Type<DB> for EnumNull<T>
+    where
+        DB: Database,
+        T: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <T as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <T as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB, T> Encode<'q, DB> for EnumNull<T>
+    where
+        DB: Database,
+    {
+        fn encode_by_ref(
+            &self,
+            _buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            Ok(IsNull::Yes)
+        }
+    }
+}
+
+#[cfg(feature = "mssql")]
+impl<T: DbEnum> tiberius::ToSql for EnumNull<T> {
+    fn to_sql(&self) -> tiberius::ColumnData<'_> {
+        match T::STORAGE {
+            EnumStorage::Integer => tiberius::ColumnData::I32(None),
+            _ => tiberius::ColumnData::String(None),
+        }
+    }
+}
+
+/// sqlx Type/Encode/Decode for a `DbEnum`, by way of its `Repr`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_sqlx {
+    ($ty:ty, $db:ty) => {
+        impl $crate::__private::sqlx::Type<$db> for $ty {
+            fn type_info() -> <$db as $crate::__private::sqlx::Database>::TypeInfo {
+                <<$ty as $crate::enums::DbEnum>::Repr as $crate::__private::sqlx::Type<$db>>::type_info()
+            }
+            fn compatible(ty: &<$db as $crate::__private::sqlx::Database>::TypeInfo) -> bool {
+                <<$ty as $crate::enums::DbEnum>::Repr as $crate::__private::sqlx::Type<$db>>::compatible(ty)
+            }
+        }
+
+        impl<'q> $crate::__private::sqlx::Encode<'q, $db> for $ty {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <$db as $crate::__private::sqlx::Database>::ArgumentBuffer<'q>,
+            ) -> ::std::result::Result<
+                $crate::__private::sqlx::encode::IsNull,
+                $crate::__private::sqlx::error::BoxDynError,
+            > {
+                let value = $crate::enums::DbEnum::to_db(self);
+                $crate::__private::sqlx::Encode::<'q, $db>::encode(value, buf)
+            }
+        }
+
+        impl<'r> $crate::__private::sqlx::Decode<'r, $db> for $ty {
+            fn decode(
+                value: <$db as $crate::__private::sqlx::Database>::ValueRef<'r>,
+            ) -> ::std::result::Result<Self, $crate::__private::sqlx::error::BoxDynError> {
+                let value = <<$ty as $crate::enums::DbEnum>::Repr as $crate::__private::sqlx::Decode<'r, $db>>::decode(value)?;
+                Ok($crate::enums::decode_db_value::<$ty>(value)?)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_sqlite {
+    ($ty:ty) => {
+        $crate::__welds_enum_sqlx!($ty, $crate::__private::sqlx::Sqlite);
+    };
+}
+
+#[cfg(not(feature = "sqlite"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_sqlite {
+    ($ty:ty) => {};
+}
+
+#[cfg(feature = "mysql")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_mysql {
+    ($ty:ty) => {
+        $crate::__welds_enum_sqlx!($ty, $crate::__private::sqlx::MySql);
+    };
+}
+
+#[cfg(not(feature = "mysql"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_mysql {
+    ($ty:ty) => {};
+}
+
+// Postgres has its own Type impl so the enum can be a native ENUM type
+#[cfg(feature = "postgres")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_postgres {
+    ($ty:ty) => {
+        impl $crate::__private::sqlx::Type<$crate::__private::sqlx::Postgres> for $ty {
+            fn type_info() -> $crate::__private::sqlx::postgres::PgTypeInfo {
+                match <$ty as $crate::enums::DbEnum>::STORAGE {
+                    $crate::enums::EnumStorage::PostgresEnum(name) => {
+                        $crate::__private::sqlx::postgres::PgTypeInfo::with_name(name)
+                    }
+                    _ => <<$ty as $crate::enums::DbEnum>::Repr as $crate::__private::sqlx::Type<
+                        $crate::__private::sqlx::Postgres,
+                    >>::type_info(),
+                }
+            }
+            fn compatible(ty: &$crate::__private::sqlx::postgres::PgTypeInfo) -> bool {
+                *ty == <Self as $crate::__private::sqlx::Type<$crate::__private::sqlx::Postgres>>::type_info()
+                    || <<$ty as $crate::enums::DbEnum>::Repr as $crate::__private::sqlx::Type<
+                        $crate::__private::sqlx::Postgres,
+                    >>::compatible(ty)
+            }
+        }
+
+        impl<'q> $crate::__private::sqlx::Encode<'q, $crate::__private::sqlx::Postgres> for $ty {
+            fn encode_by_ref(
+                &self,
+                buf: &mut $crate::__private::sqlx::postgres::PgArgumentBuffer,
+            ) -> ::std::result::Result<
+                $crate::__private::sqlx::encode::IsNull,
+                $crate::__private::sqlx::error::BoxDynError,
+            > {
+                let value = $crate::enums::DbEnum::to_db(self);
+                $crate::__private::sqlx::Encode::<'q, $crate::__private::sqlx::Postgres>::encode(
+                    value, buf,
+                )
+            }
+        }
+
+        impl<'r> $crate::__private::sqlx::Decode<'r, $crate::__private::sqlx::Postgres> for $ty {
+            fn decode(
+                value: $crate::__private::sqlx::postgres::PgValueRef<'r>,
+            ) -> ::std::result::Result<Self, $crate::__private::sqlx::error::BoxDynError> {
+                let value = <<$ty as $crate::enums::DbEnum>::Repr as $crate::__private::sqlx::Decode<
+                    'r,
+                    $crate::__private::sqlx::Postgres,
+                >>::decode(value)?;
+                Ok($crate::enums::decode_db_value::<$ty>(value)?)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "postgres"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_postgres {
+    ($ty:ty) => {};
+}
+
+#[cfg(feature = "mssql")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_mssql {
+    ($ty:ty) => {
+        impl $crate::__private::tiberius::ToSql for $ty {
+            fn to_sql(&self) -> $crate::__private::tiberius::ColumnData<'_> {
+                let value = $crate::enums::DbEnum::to_db(self);
+                $crate::__private::tiberius::IntoSql::into_sql(value)
+            }
+        }
+
+        impl $crate::__private::tiberius::FromSqlOwned for $ty {
+            fn from_sql_owned(
+                value: $crate::__private::tiberius::ColumnData<'static>,
+            ) -> $crate::__private::tiberius::Result<Option<Self>> {
+                let value: Option<<$ty as $crate::enums::DbEnum>::Repr> =
+                    $crate::__private::tiberius::FromSqlOwned::from_sql_owned(value)?;
+                value
+                    .map(|v| {
+                        $crate::enums::decode_db_value::<$ty>(v).map_err(|err| {
+                            $crate::__private::tiberius::error::Error::Conversion(
+                                err.to_string().into(),
+                            )
+                        })
+                    })
+                    .transpose()
+            }
+        }
+
+        impl $crate::row::TiberiusDecode for $ty {
+            fn read(
+                col: &$crate::__private::tiberius::Column,
+                value: $crate::__private::tiberius::ColumnData<'static>,
+            ) -> $crate::errors::Result<Self> {
+                let value = $crate::__private::tiberius::FromSqlOwned::from_sql_owned(value)?;
+                value.ok_or_else(|| $crate::Error::UnexpectedNoneInColumn(col.name().to_owned()))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "mssql"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum_mssql {
+    ($ty:ty) => {};
+}
+
+/// Implements encode/decode of a `DbEnum` for all the enabled databases
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __welds_enum {
+    ($ty:ty) => {
+        $crate::__welds_enum_sqlite!($ty);
+        $crate::__welds_enum_postgres!($ty);
+        $crate::__welds_enum_mysql!($ty);
+        $crate::__welds_enum_mssql!($ty);
+    };
+}
@@ -14,10 +14,19 @@ pub enum Error {
     InvalidDatabaseUrl,
     RowNowFound,
     PoolError,
+    PoolTimedOut,
+    Timeout,
     ClosedTransaction,
     ColumnNotFound(String),
     UnexpectedNoneInColumn(String),
+    UnsupportedType(String),
+    Deserialize(String),
+    InvalidEnumValue(String, String),
+    Convert(String, String),
     JsonParseError(String, String),
+    Middleware(String),
+    Mock(String),
+    Replay(String),
 }
 
 impl std::error::Error for Error {}
@@ -34,15 +43,30 @@ impl Display for Error {
             Error::Bb8(err) => err.to_string(),
             Error::InvalidDatabaseUrl => "Invalid database URL. If your connection string is valid, make sure the feature for your database type is enabled".to_string(),
             Error::PoolError => "the MSSQL connection pool has a locked mutex".to_string(),
+            Error::PoolTimedOut => {
+                "timed out waiting for a connection from the connection pool".to_string()
+            }
+            Error::Timeout => "the statement timed out and was canceled".to_string(),
             Error::RowNowFound => "Row not found".to_string(),
             Error::ClosedTransaction => {
                 "SQL can not be executed on a closed transaction".to_string()
             }
             Error::ColumnNotFound(name) => format!("Column not found: {name}"),
             Error::UnexpectedNoneInColumn(name) => format!("Unexpected None in column: {name}"),
+            Error::UnsupportedType(name) => {
+                format!("The type of column {name} can not be read as a dynamic value")
+            }
+            Error::Deserialize(message) => format!("unable to deserialize row: {message}"),
+            Error::InvalidEnumValue(ty, value) => {
+                format!("{value} is not a valid value for the enum {ty}")
+            }
+            Error::Convert(col, message) => format!("unable to convert column {col}: {message}"),
             Error::JsonParseError(col, json) => {
                 format!("unable to parse json in column: {col}. json: {json}")
             }
+            Error::Middleware(message) => message.to_string(),
+            Error::Mock(message) => format!("mock: {message}"),
+            Error::Replay(message) => format!("replay: {message}"),
         };
 
         f.write_str(&message)?;
@@ -50,13 +74,42 @@ impl Display for Error {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Deserialize(msg.to_string())
+    }
+}
+
 #[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
 impl From<sqlx::error::Error> for Error {
     fn from(inner: sqlx::error::Error) -> Self {
+        if is_sqlx_timeout(&inner) {
+            return Error::Timeout;
+        }
         Error::Sqlx(inner)
     }
 }
 
+/// Returns true if the database canceled the statement because it ran too long
+#[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
+fn is_sqlx_timeout(err: &sqlx::error::Error) -> bool {
+    let db_err = match err.as_database_error() {
+        Some(db_err) => db_err,
+        None => return false,
+    };
+    // postgres: query_canceled (statement_timeout)
+    if db_err.code().as_deref() == Some("57014") {
+        return true;
+    }
+    // mysql: ER_QUERY_TIMEOUT (max_execution_time)
+    #[cfg(feature = "mysql")]
+    if let Some(my_err) = db_err.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+        return my_err.number() == 3024;
+    }
+    false
+}
+
 #[cfg(feature = "mssql")]
 impl From<bb8_tiberius::Error> for Error {
     fn from(inner: bb8_tiberius::Error) -> Self {
@@ -1,10 +1,17 @@
 pub use crate::errors::Error;
 use crate::errors::Result;
 use async_trait::async_trait;
+pub use pool_config::PoolConfig;
 pub use row::Row;
 pub use transaction::Transaction;
+pub mod convert;
+pub mod enums;
 pub mod errors;
+pub mod middleware;
+pub mod pool_config;
 pub mod row;
+pub mod timeout;
+mod trace;
 pub mod transaction;
 
 pub mod any;
@@ -16,9 +23,21 @@ pub mod mysql;
 pub mod noop;
 #[cfg(feature = "postgres")]
 pub mod postgres;
+#[cfg(feature = "replay")]
+pub mod replay;
+pub mod replica;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
+// Used by the code generated in welds-macros
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
+    pub use sqlx;
+    #[cfg(feature = "mssql")]
+    pub use tiberius;
+}
+
 pub struct Fetch<'s, 'args, 't> {
     pub sql: &'s str,
     pub params: &'args [&'t (dyn Param + Sync)],
@@ -46,8 +65,22 @@ pub trait Client: Sync + Send {
 /// Used the ENV DATABASE_URL
 /// builds a connection with whatever is in it.
 pub async fn connect_from_env() -> Result<any::AnyClient> {
-    let url = std::env::var("DATABASE_URL").or(Err(Error::InvalidDatabaseUrl))?;
-    connect(&url).await
+    connect_from_env_with(&PoolConfig::default()).await
+}
+
+/// Used the ENV DATABASE_URL
+/// builds a connection with whatever is in it, using custom connection pool settings
+pub async fn connect_from_env_with(config: &PoolConfig) -> Result<any::AnyClient> {
+    connect_from_url(std::env::var("DATABASE_URL").ok(), config).await
+}
+
+/// Connects to the DATABASE_URL read from the ENV, erroring if there wasn't one
+pub(crate) async fn connect_from_url(
+    url: Option<String>,
+    config: &PoolConfig,
+) -> Result<any::AnyClient> {
+    let url = url.ok_or(Error::InvalidDatabaseUrl)?;
+    connect_with(url, config).await
 }
 
 /// Returns a connection pool (Client/TransactStart) for the given connection string.
@@ -58,35 +91,43 @@ pub async fn connect_from_env() -> Result<any::AnyClient> {
 /// SQLX Connection String (postgres, mysql, sqlite)
 /// ADO Connection String (mssql)
 pub async fn connect(cs: impl Into<String>) -> Result<any::AnyClient> {
+    connect_with(cs, &PoolConfig::default()).await
+}
+
+/// Returns a connection pool (Client/TransactStart) for the given connection string,
+/// using custom connection pool settings.
+///
+/// See `connect` for the connection string formats
+pub async fn connect_with(cs: impl Into<String>, config: &PoolConfig) -> Result<any::AnyClient> {
     let cs: String = cs.into();
     #[cfg(feature = "postgres")]
     if cs.starts_with("postgresql:") {
         log::debug!("Welds connecting to Postgres");
-        let client = postgres::connect(&cs).await?;
+        let client = postgres::connect_with(&cs, config).await?;
         return Ok(any::AnyClient::Postgres(client));
     }
     #[cfg(feature = "postgres")]
     if cs.starts_with("postgres:") {
         log::debug!("Welds connecting to Postgres");
-        let client = postgres::connect(&cs).await?;
+        let client = postgres::connect_with(&cs, config).await?;
         return Ok(any::AnyClient::Postgres(client));
     }
     #[cfg(feature = "mysql")]
     if cs.starts_with("mysql:") {
         log::debug!("Welds connecting to MySql");
-        let client = mysql::connect(&cs).await?;
+        let client = mysql::connect_with(&cs, config).await?;
         return Ok(any::AnyClient::Mysql(client));
     }
     #[cfg(feature = "sqlite")]
     if cs.starts_with("sqlite:") {
         log::debug!("Welds connecting to Sqlite");
-        let client = sqlite::connect(&cs).await?;
+        let client = sqlite::connect_with(&cs, config).await?;
         return Ok(any::AnyClient::Sqlite(client));
     }
     #[cfg(feature = "mssql")]
     if !cs.is_empty() {
         log::debug!("Welds connecting to MSSQL");
-        let client = mssql::connect(&cs).await?;
+        let client = mssql::connect_with(&cs, config).await?;
         return Ok(any::AnyClient::Mssql(client));
     }
     Err(errors::Error::InvalidDatabaseUrl)
@@ -119,6 +160,7 @@ impl ExecuteResult {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub enum Syntax {
     Mysql,
     Postgres,
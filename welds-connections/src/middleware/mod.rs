@@ -0,0 +1,523 @@
+use super::Row;
+use super::{Client, Param};
+use crate::errors::{Error, Result};
+use crate::{ExecuteResult, Fetch, Syntax, TransactStart, Transaction};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A hook that is called before and after each statement run through a `MiddlewareClient`.
+///
+/// Both methods have a default that does nothing, implement the ones you need.
+///
+/// ```rust,ignore
+/// struct SlowQueryLog(Duration);
+///
+/// #[async_trait]
+/// impl Middleware for SlowQueryLog {
+///     async fn after(&self, request: &Request<'_>, _response: &mut Result<Response>) {
+///         if request.elapsed() > self.0 {
+///             log::warn!("SLOW QUERY: {:?}", request.statements());
+///         }
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Called before the statements are sent to the database.
+    ///
+    /// The SQL of the request can be rewritten here.
+    /// Return `Ok(Some(response))` to skip the database and use this response instead.
+    /// Return `Err(..)` to fail the request without running it.
+    async fn before(&self, _request: &mut Request<'_>) -> Result<Option<Response>> {
+        Ok(None)
+    }
+
+    /// Called after the statements have run (or were skipped).
+    /// The response can be inspected or replaced here.
+    async fn after(&self, _request: &Request<'_>, _response: &mut Result<Response>) {}
+}
+
+/// What kind of call was made on the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    Execute,
+    FetchRows,
+    FetchMany,
+}
+
+/// A single SQL statement and its params
+pub struct Statement<'a> {
+    pub sql: String,
+    pub params: &'a [&'a (dyn Param + Sync)],
+}
+
+impl std::fmt::Debug for Statement<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Statement")
+            .field("sql", &self.sql)
+            .field("params", &self.params.len())
+            .finish()
+    }
+}
+
+/// The statements passed through the middleware chain.
+/// `Execute` and `FetchRows` always have exactly one statement
+pub struct Request<'a> {
+    kind: RequestKind,
+    syntax: Syntax,
+    in_transaction: bool,
+    started: Instant,
+    statements: Vec<Statement<'a>>,
+}
+
+impl<'a> Request<'a> {
+    fn new(
+        inner: &dyn Client,
+        in_transaction: bool,
+        kind: RequestKind,
+        statements: Vec<Statement<'a>>,
+    ) -> Self {
+        Request {
+            kind,
+            syntax: inner.syntax(),
+            in_transaction,
+            started: Instant::now(),
+            statements,
+        }
+    }
+
+    pub fn kind(&self) -> RequestKind {
+        self.kind
+    }
+
+    /// The syntax of the database the request will be sent to
+    pub fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    /// True if the statements are being run inside a transaction
+    pub fn in_transaction(&self) -> bool {
+        self.in_transaction
+    }
+
+    /// How long it has been since the request was started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn statements(&self) -> &[Statement<'a>] {
+        &self.statements
+    }
+
+    /// Use to rewrite the SQL of the statements
+    pub fn statements_mut(&mut self) -> &mut [Statement<'a>] {
+        &mut self.statements
+    }
+}
+
+/// The result of a request. The variant will match the `RequestKind`
+pub enum Response {
+    Execute(ExecuteResult),
+    FetchRows(Vec<Row>),
+    FetchMany(Vec<Vec<Row>>),
+}
+
+/// Wraps a client, running each statement through a chain of `Middleware`.
+///
+/// The `before` of each middleware is called in the order they were added,
+/// the `after` is called in the reverse order.
+/// If a middleware returns a response from `before`, the rest of the chain and the database are skipped,
+/// but the `after` of every middleware that already ran is still called.
+///
+/// Transactions started from this client send their statements through the same chain.
+///
+/// ```rust,ignore
+/// let client = MiddlewareClient::new(client)
+///     .with(SlowQueryLog(Duration::from_millis(500)))
+///     .with(QueryTagger("billing-service"));
+/// ```
+pub struct MiddlewareClient<C> {
+    inner: C,
+    chain: Chain,
+}
+
+impl<C> MiddlewareClient<C>
+where
+    C: Client,
+{
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            chain: Chain::default(),
+        }
+    }
+
+    /// Adds a middleware to the end of the chain
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.chain.0.push(Arc::new(middleware));
+        self
+    }
+
+    /// Adds a middleware that is shared with other clients to the end of the chain
+    pub fn with_shared(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.chain.0.push(middleware);
+        self
+    }
+
+    /// Returns the client being wrapped
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+/// A list of middleware that statements are run through.
+/// Shared by a `MiddlewareClient` and the transactions it starts
+#[derive(Clone, Default)]
+pub(crate) struct Chain(Vec<Arc<dyn Middleware>>);
+
+impl Chain {
+    /// A chain of a single middleware
+    #[cfg(feature = "replay")]
+    pub(crate) fn single(middleware: Arc<dyn Middleware>) -> Chain {
+        Chain(vec![middleware])
+    }
+
+    /// Puts this chain in front of another chain
+    pub(crate) fn then(mut self, other: Chain) -> Chain {
+        self.0.extend(other.0);
+        self
+    }
+
+    async fn run(&self, inner: &dyn Client, mut request: Request<'_>) -> Result<Response> {
+        // walk the chain, stopping early if a middleware answers the request
+        let mut ran = 0;
+        let mut answered = None;
+        for middleware in &self.0 {
+            ran += 1;
+            match middleware.before(&mut request).await {
+                Ok(None) => {}
+                Ok(Some(response)) => {
+                    answered = Some(Ok(response));
+                    break;
+                }
+                Err(err) => {
+                    answered = Some(Err(err));
+                    break;
+                }
+            }
+        }
+
+        let mut response = match answered {
+            Some(response) => response,
+            None => send(inner, &request).await,
+        };
+
+        for middleware in self.0[..ran].iter().rev() {
+            middleware.after(&request, &mut response).await;
+        }
+        response
+    }
+
+    pub(crate) async fn execute(
+        &self,
+        inner: &dyn Client,
+        in_transaction: bool,
+        sql: &str,
+        params: &[&(dyn Param + Sync)],
+    ) -> Result<ExecuteResult> {
+        let statement = Statement {
+            sql: sql.to_string(),
+            params,
+        };
+        let request = Request::new(inner, in_transaction, RequestKind::Execute, vec![statement]);
+        match self.run(inner, request).await? {
+            Response::Execute(result) => Ok(result),
+            _ => Err(wrong_response(RequestKind::Execute)),
+        }
+    }
+
+    pub(crate) async fn fetch_rows(
+        &self,
+        inner: &dyn Client,
+        in_transaction: bool,
+        sql: &str,
+        params: &[&(dyn Param + Sync)],
+    ) -> Result<Vec<Row>> {
+        let statement = Statement {
+            sql: sql.to_string(),
+            params,
+        };
+        let request = Request::new(
+            inner,
+            in_transaction,
+            RequestKind::FetchRows,
+            vec![statement],
+        );
+        match self.run(inner, request).await? {
+            Response::FetchRows(rows) => Ok(rows),
+            _ => Err(wrong_response(RequestKind::FetchRows)),
+        }
+    }
+
+    pub(crate) async fn fetch_many(
+        &self,
+        inner: &dyn Client,
+        in_transaction: bool,
+        args: &[Fetch<'_, '_, '_>],
+    ) -> Result<Vec<Vec<Row>>> {
+        let statements = args
+            .iter()
+            .map(|f| Statement {
+                sql: f.sql.to_string(),
+                params: f.params,
+            })
+            .collect();
+        let request = Request::new(inner, in_transaction, RequestKind::FetchMany, statements);
+        match self.run(inner, request).await? {
+            Response::FetchMany(sets) => Ok(sets),
+            _ => Err(wrong_response(RequestKind::FetchMany)),
+        }
+    }
+}
+
+/// sends the request to the inner client
+async fn send(inner: &dyn Client, request: &Request<'_>) -> Result<Response> {
+    let statements = &request.statements;
+    match request.kind {
+        RequestKind::Execute => {
+            let s = &statements[0];
+            Ok(Response::Execute(inner.execute(&s.sql, s.params).await?))
+        }
+        RequestKind::FetchRows => {
+            let s = &statements[0];
+            Ok(Response::FetchRows(
+                inner.fetch_rows(&s.sql, s.params).await?,
+            ))
+        }
+        RequestKind::FetchMany => {
+            let fetches: Vec<Fetch> = statements
+                .iter()
+                .map(|s| Fetch {
+                    sql: &s.sql,
+                    params: s.params,
+                })
+                .collect();
+            Ok(Response::FetchMany(inner.fetch_many(&fetches).await?))
+        }
+    }
+}
+
+fn wrong_response(expected: RequestKind) -> Error {
+    Error::Middleware(format!(
+        "a middleware returned a response that does not match the request kind {expected:?}"
+    ))
+}
+
+#[async_trait]
+impl<C> Client for MiddlewareClient<C>
+where
+    C: Client,
+{
+    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+        self.chain.execute(&self.inner, false, sql, params).await
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        self.chain.fetch_rows(&self.inner, false, sql, params).await
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        args: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        self.chain.fetch_many(&self.inner, false, args).await
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.inner.syntax()
+    }
+}
+
+#[async_trait]
+impl<C> TransactStart for MiddlewareClient<C>
+where
+    C: Client + TransactStart,
+{
+    async fn begin<'t>(&'t self) -> Result<Transaction<'t>> {
+        let trans = self.inner.begin().await?;
+        Ok(trans.with_middleware(self.chain.clone()))
+    }
+}
+
+#[cfg(all(test, feature = "noop", feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::noop::MockClient;
+    use std::sync::Mutex;
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    /// records when its hooks are called
+    struct Named {
+        name: &'static str,
+        log: Log,
+        answer: bool,
+        fail_after: bool,
+    }
+
+    fn named(name: &'static str, log: &Log) -> Named {
+        Named {
+            name,
+            log: log.clone(),
+            answer: false,
+            fail_after: false,
+        }
+    }
+
+    #[async_trait]
+    impl Middleware for Named {
+        async fn before(&self, request: &mut Request<'_>) -> Result<Option<Response>> {
+            let sql = &request.statements()[0].sql;
+            let trans = request.in_transaction();
+            let line = format!("{} before {sql} trans={trans}", self.name);
+            self.log.lock().unwrap().push(line);
+            if self.answer {
+                return Ok(Some(Response::Execute(ExecuteResult { rows_affected: 9 })));
+            }
+            Ok(None)
+        }
+
+        async fn after(&self, _request: &Request<'_>, response: &mut Result<Response>) {
+            let line = format!("{} after ok={}", self.name, response.is_ok());
+            self.log.lock().unwrap().push(line);
+            if self.fail_after {
+                *response = Err(Error::Middleware(format!("{} said no", self.name)));
+            }
+        }
+    }
+
+    fn lines(log: &Log) -> Vec<String> {
+        log.lock().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn hooks_should_run_before_in_order_and_after_in_reverse() {
+        let log = Log::default();
+        let mock = MockClient::new(Syntax::Postgres);
+        mock.expect_execute("DELETE", 1);
+        let client = MiddlewareClient::new(mock)
+            .with(named("a", &log))
+            .with(named("b", &log));
+        client.execute("DELETE FROM cars", &[]).await.unwrap();
+        assert_eq!(
+            lines(&log),
+            vec![
+                "a before DELETE FROM cars trans=false",
+                "b before DELETE FROM cars trans=false",
+                "b after ok=true",
+                "a after ok=true",
+            ]
+        );
+        client.inner().assert_all_consumed();
+    }
+
+    #[tokio::test]
+    async fn answering_in_before_should_skip_the_database_and_the_rest_of_the_chain() {
+        let log = Log::default();
+        let mut answers = named("b", &log);
+        answers.answer = true;
+        // the mock has no expectations, it errors if the statement reaches it
+        let client = MiddlewareClient::new(MockClient::new(Syntax::Postgres))
+            .with(named("a", &log))
+            .with(answers)
+            .with(named("c", &log));
+        let result = client.execute("DELETE FROM cars", &[]).await.unwrap();
+        assert_eq!(result.rows_affected(), 9);
+        assert_eq!(
+            lines(&log),
+            vec![
+                "a before DELETE FROM cars trans=false",
+                "b before DELETE FROM cars trans=false",
+                "b after ok=true",
+                "a after ok=true",
+            ]
+        );
+        assert!(client.inner().history().is_empty());
+    }
+
+    #[tokio::test]
+    async fn errors_from_after_should_reach_the_caller() {
+        let log = Log::default();
+        let mut fails = named("b", &log);
+        fails.fail_after = true;
+        let mock = MockClient::new(Syntax::Postgres);
+        mock.expect_execute("DELETE", 1);
+        let client = MiddlewareClient::new(mock)
+            .with(named("a", &log))
+            .with(fails);
+        let result = client.execute("DELETE FROM cars", &[]).await;
+        assert!(matches!(result, Err(Error::Middleware(msg)) if msg == "b said no"));
+        // the statement still ran, and the outer middleware sees the error
+        assert_eq!(client.inner().history().len(), 1);
+        assert_eq!(lines(&log).last().unwrap(), "a after ok=false");
+    }
+
+    #[tokio::test]
+    async fn a_response_of_the_wrong_kind_should_be_an_error() {
+        let log = Log::default();
+        let mut answers = named("a", &log);
+        answers.answer = true;
+        let client = MiddlewareClient::new(MockClient::new(Syntax::Postgres)).with(answers);
+        let result = client.fetch_rows("SELECT 1", &[]).await;
+        assert!(matches!(result, Err(Error::Middleware(_))));
+    }
+
+    #[tokio::test]
+    async fn transactions_should_run_through_the_middleware() {
+        let log = Log::default();
+        let sqlite = crate::sqlite::connect("sqlite::memory:").await.unwrap();
+        let client = MiddlewareClient::new(sqlite).with(named("a", &log));
+        client.execute("CREATE TABLE t (x INT)", &[]).await.unwrap();
+
+        let trans = client.begin().await.unwrap();
+        trans
+            .execute("INSERT INTO t VALUES (1)", &[])
+            .await
+            .unwrap();
+        let rows = trans.fetch_rows("SELECT x FROM t", &[]).await.unwrap();
+        trans.commit().await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            lines(&log),
+            vec![
+                "a before CREATE TABLE t (x INT) trans=false",
+                "a after ok=true",
+                "a before INSERT INTO t VALUES (1) trans=true",
+                "a after ok=true",
+                "a before SELECT x FROM t trans=true",
+                "a after ok=true",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn nested_clients_should_run_the_outer_middleware_first_in_transactions() {
+        let log = Log::default();
+        let sqlite = crate::sqlite::connect("sqlite::memory:").await.unwrap();
+        let inner = MiddlewareClient::new(sqlite).with(named("inner", &log));
+        let client = MiddlewareClient::new(inner).with(named("outer", &log));
+        let trans = client.begin().await.unwrap();
+        trans.execute("SELECT 1", &[]).await.unwrap();
+        trans.rollback().await.unwrap();
+        assert_eq!(
+            lines(&log),
+            vec![
+                "outer before SELECT 1 trans=true",
+                "inner before SELECT 1 trans=true",
+                "inner after ok=true",
+                "outer after ok=true",
+            ]
+        );
+    }
+}
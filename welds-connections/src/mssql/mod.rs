@@ -3,10 +3,16 @@ use super::Row;
 use super::TransactStart;
 use super::{Client, Param};
 use crate::errors::Result;
+use crate::timeout::deadline;
+use crate::trace;
 use crate::ExecuteResult;
+use crate::PoolConfig;
 use async_trait::async_trait;
 use std::sync::Arc;
+use std::time::Duration;
 
+#[cfg(feature = "noop")]
+use crate::noop::ParamValue;
 use tiberius::ToSql;
 mod pool;
 use pool::Pool;
@@ -15,6 +21,7 @@ pub(crate) mod transaction;
 
 pub struct MssqlClient {
     pool: Arc<Pool>,
+    timeout: Option<Duration>,
 }
 
 #[async_trait]
@@ -23,15 +30,33 @@ impl TransactStart for MssqlClient {
         let conn = self.pool.get().await?;
         log::debug!("TransactStart: building transaction");
         let trans = transaction::MssqlTransaction::new(conn).await?;
-        let inner = TransT::Mssql(trans);
+        let inner = TransT::Mssql(Box::new(trans));
         Ok(Transaction::new(inner))
     }
 }
 
 pub async fn connect(cs: &str) -> Result<MssqlClient> {
+    connect_with(cs, &PoolConfig::default()).await
+}
+
+/// Connect using custom connection pool settings
+pub async fn connect_with(cs: &str, config: &PoolConfig) -> Result<MssqlClient> {
     let mgr = bb8_tiberius::ConnectionManager::build(cs)?;
-    let pool = Pool::new(mgr);
-    Ok(MssqlClient { pool })
+    let pool = Pool::new(mgr, config);
+
+    // open the min number of connections up front.
+    // They are returned to the pool when dropped
+    let min = pool.min_connections();
+    let mut warm = Vec::default();
+    for _ in 0..min {
+        warm.push(pool.get().await?);
+    }
+    drop(warm);
+
+    Ok(MssqlClient {
+        pool,
+        timeout: config.statement_timeout,
+    })
 }
 
 impl MssqlClient {
@@ -45,21 +70,30 @@ impl MssqlClient {
 #[async_trait]
 impl Client for MssqlClient {
     async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
-        let conn = self.pool.get().await?;
-        conn.execute(sql, params).await
+        let running = async {
+            let conn = self.pool.get().await?;
+            deadline(self.timeout, conn.execute(sql, params)).await
+        };
+        trace::execute(self.syntax(), sql, params.len(), running).await
     }
 
     async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
-        let conn = self.pool.get().await?;
-        conn.fetch_rows(sql, params).await
+        let running = async {
+            let conn = self.pool.get().await?;
+            deadline(self.timeout, conn.fetch_rows(sql, params)).await
+        };
+        trace::fetch_rows(self.syntax(), sql, params.len(), running).await
     }
 
     async fn fetch_many<'s, 'args, 't>(
         &self,
         args: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
-        let conn = self.pool.get().await?;
-        conn.fetch_many(args).await
+        let running = async {
+            let conn = self.pool.get().await?;
+            deadline(self.timeout, conn.fetch_many(args)).await
+        };
+        trace::fetch_many(self.syntax(), args, running).await
     }
 
     fn syntax(&self) -> crate::Syntax {
@@ -69,6 +103,10 @@ impl Client for MssqlClient {
 
 pub trait MssqlParam {
     fn add_param<'a>(&'a self, args: Vec<&'a dyn ToSql>) -> Vec<&'a dyn ToSql>;
+
+    /// Reads the value out of the param so it can be recorded by the `MockClient`
+    #[cfg(feature = "noop")]
+    fn param_value(&self) -> ParamValue;
 }
 
 impl<T> MssqlParam for T
@@ -79,4 +117,34 @@ where
         args.push(self);
         args
     }
+
+    #[cfg(feature = "noop")]
+    fn param_value(&self) -> ParamValue {
+        ParamValue::from_column_data(&self.to_sql())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noop::ParamValue;
+    use tiberius::ColumnData;
+
+    // a type from outside welds, that only implements ToSql
+    struct Cents(i64);
+
+    impl ToSql for Cents {
+        fn to_sql(&self) -> ColumnData<'_> {
+            ColumnData::I64(Some(self.0))
+        }
+    }
+
+    #[test]
+    fn should_bind_a_user_type_that_implements_to_sql() {
+        let cents = Cents(250);
+        let args = MssqlParam::add_param(&cents, Vec::default());
+        assert_eq!(args.len(), 1);
+        assert!(matches!(args[0].to_sql(), ColumnData::I64(Some(250))));
+        assert_eq!(MssqlParam::param_value(&cents), ParamValue::Int(250));
+    }
 }
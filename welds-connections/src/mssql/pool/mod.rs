@@ -1,13 +1,16 @@
 use super::{Client, Param};
-use crate::errors::Error::PoolError;
+use crate::errors::Error::{PoolError, PoolTimedOut};
 use crate::errors::Result;
+use crate::PoolConfig;
 use async_mutex::Mutex as AsyncMutex;
 use bb8::ManageConnection;
 use bb8_tiberius::ConnectionManager;
 use std::ops::DerefMut;
+use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tokio::task::yield_now;
 
 // ******************************************************************************
@@ -26,6 +29,9 @@ use tokio::task::yield_now;
 
 pub(crate) type TiberiusConn = tiberius::Client<tokio_util::compat::Compat<tokio::net::TcpStream>>;
 
+/// What is sent back to the pool when a PooledConnection is dropped
+pub(crate) type ConnReturn = (TiberiusConn, ConnectionStatus, Instant);
+
 mod pooledconnection;
 pub(crate) use pooledconnection::PooledConnection;
 
@@ -33,15 +39,53 @@ pub struct Pool {
     mgr: ConnectionManager,
     slots: Vec<Mutex<Slot>>,
     round_robin_next: Mutex<usize>,
-    tx: Sender<(TiberiusConn, ConnectionStatus)>,
+    tx: Sender<ConnReturn>,
+    settings: Settings,
+}
+
+/// The parts of the PoolConfig that the MSSQL pool uses
+struct Settings {
+    size: usize,
+    min_connections: usize,
+    acquire_timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+    test_before_acquire: bool,
+}
+
+impl Settings {
+    fn new(config: &PoolConfig) -> Self {
+        let size = config.max_connections.unwrap_or(10).max(1) as usize;
+        // more than the pool can hold would wait forever for a free slot
+        let min_connections = (config.min_connections.unwrap_or_default() as usize).min(size);
+        Self {
+            size,
+            min_connections,
+            acquire_timeout: config.acquire_timeout,
+            idle_timeout: config.idle_timeout,
+            max_lifetime: config.max_lifetime,
+            test_before_acquire: config.test_before_acquire.unwrap_or(true),
+        }
+    }
+
+    /// True if a connection has been idle or alive for too long and should be closed
+    fn is_expired(&self, created: Instant, idle_since: Instant) -> bool {
+        let idle_too_long = self
+            .idle_timeout
+            .is_some_and(|timeout| idle_since.elapsed() >= timeout);
+        let alive_too_long = self
+            .max_lifetime
+            .is_some_and(|lifetime| created.elapsed() >= lifetime);
+        idle_too_long || alive_too_long
+    }
 }
 
 impl Pool {
     /// Create a new connection pool
-    pub fn new(mgr: ConnectionManager) -> Arc<Self> {
-        let size = 10;
-        let mut slots = Vec::with_capacity(size);
-        for _ in 0..size {
+    pub fn new(mgr: ConnectionManager, config: &PoolConfig) -> Arc<Self> {
+        let settings = Settings::new(config);
+        let mut slots = Vec::with_capacity(settings.size);
+        for _ in 0..settings.size {
             slots.push(Mutex::new(Slot::Empty));
         }
 
@@ -52,12 +96,18 @@ impl Pool {
             slots,
             round_robin_next: Mutex::new(0),
             tx,
+            settings,
         });
         let return_ref = me.clone();
         tokio::spawn(async move { pool_return(return_ref, rx).await });
         me
     }
 
+    /// The number of connections to open up front, never more than the pool can hold
+    pub(crate) fn min_connections(&self) -> usize {
+        self.settings.min_connections
+    }
+
     /// Returns a connection from the connection pool.
     /// useful if you want to do several operations in the same connection
     /// The connection is automatically returned to the pool when dropped
@@ -68,11 +118,13 @@ impl Pool {
             let guard = self.round_robin_next.lock().map_err(|_| PoolError)?;
             *guard
         };
+        let started = Instant::now();
 
         loop {
-            let checked_out = try_checkout(slot_index, &self.slots, &self.mgr).await?;
+            let checked_out =
+                try_checkout(slot_index, &self.slots, &self.mgr, &self.settings).await?;
 
-            if let Some(tiberius_conn) = checked_out {
+            if let Some((tiberius_conn, created)) = checked_out {
                 // save off the next slot to use
                 slot_index += 1;
                 slot_index %= self.slots.len();
@@ -84,10 +136,18 @@ impl Pool {
                 return Ok(PooledConnection {
                     status: ConnectionStatus::Clean,
                     tiberius_conn,
+                    created,
+                    in_flight: AtomicBool::new(false),
                     conn_return: self.tx.clone(),
                 });
             }
 
+            if let Some(timeout) = self.settings.acquire_timeout {
+                if started.elapsed() >= timeout {
+                    return Err(PoolTimedOut);
+                }
+            }
+
             slot_index += 1;
             slot_index %= self.slots.len();
             yield_now().await;
@@ -114,11 +174,13 @@ impl Pool {
 
 /// tries to checkout the connection at a given slot.
 /// returns None if the connection in that slot is not available
+/// On success, returns the connection and when it was created
 async fn try_checkout(
     index: usize,
     slots: &[Mutex<Slot>],
     mgr: &ConnectionManager,
-) -> Result<Option<TiberiusConn>> {
+    settings: &Settings,
+) -> Result<Option<(TiberiusConn, Instant)>> {
     // lock the mutex and do the checking out part.
     let slot: Slot = {
         let mut slot_guard = slots[index].lock().unwrap();
@@ -136,46 +198,66 @@ async fn try_checkout(
     };
 
     // get the connection out of the slot
-    let conn: Option<TiberiusConn> = match slot {
-        Slot::Avalable(c) => Some(c),
+    let conn: Option<IdleConn> = match slot {
+        Slot::Avalable(c) => Some(*c),
         Slot::Empty => None,
         // this should never happen
         Slot::Checkedout => panic!("double checkout"),
     };
 
+    // drop connections that have been idle or alive for too long
+    let conn = conn.filter(|c| !c.is_expired(settings));
+
     match conn {
         None => {
             // build a new connection
             log::debug!("MSSQL POOL adding Connection");
             let new_conn = mgr.connect().await?;
-            Ok(Some(new_conn))
+            Ok(Some((new_conn, Instant::now())))
         }
-        Some(mut conn) => {
+        Some(IdleConn {
+            mut conn, created, ..
+        }) => {
             // make sure the connection isn't dead
-            if conn.execute("SELECT 1", &[]).await.is_err() {
+            if settings.test_before_acquire && conn.execute("SELECT 1", &[]).await.is_err() {
                 log::debug!("MSSQL POOL rebuild Connection");
                 let new_conn = mgr.connect().await?;
-                Ok(Some(new_conn))
+                Ok(Some((new_conn, Instant::now())))
             } else {
-                Ok(Some(conn))
+                Ok(Some((conn, created)))
             }
         }
     }
 }
 
 pub(crate) enum Slot {
-    Avalable(TiberiusConn),
+    Avalable(Box<IdleConn>),
     Checkedout,
     Empty,
 }
 
+/// A connection sitting in the pool waiting to be used
+pub(crate) struct IdleConn {
+    conn: TiberiusConn,
+    created: Instant,
+    idle_since: Instant,
+}
+
+impl IdleConn {
+    fn is_expired(&self, settings: &Settings) -> bool {
+        settings.is_expired(self.created, self.idle_since)
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub(crate) enum ConnectionStatus {
     Clean,
     NeedsRollback(String),
+    // The connection was dropped while running a statement. It needs to be closed
+    Broken,
 }
 
-async fn pool_return(pool: Arc<Pool>, mut rx: Receiver<(TiberiusConn, ConnectionStatus)>) {
+async fn pool_return(pool: Arc<Pool>, mut rx: Receiver<ConnReturn>) {
     loop {
         let _ = pool_return_inner(pool.clone(), &mut rx).await;
         if Arc::strong_count(&pool) == 1 {
@@ -185,10 +267,7 @@ async fn pool_return(pool: Arc<Pool>, mut rx: Receiver<(TiberiusConn, Connection
     }
 }
 
-async fn pool_return_inner(
-    pool: Arc<Pool>,
-    rx: &mut Receiver<(TiberiusConn, ConnectionStatus)>,
-) -> Result<()> {
+async fn pool_return_inner(pool: Arc<Pool>, rx: &mut Receiver<ConnReturn>) -> Result<()> {
     // wait for a connection to be returned
     let tuple = match rx.try_recv().ok() {
         None => return Ok(()),
@@ -196,7 +275,22 @@ async fn pool_return_inner(
     };
 
     tokio::spawn(async move {
-        let (mut conn, status) = tuple;
+        let (mut conn, status, created) = tuple;
+
+        // A broken connection is closed instead of returned. Closing it cancels whatever it was running.
+        // Free up a slot so a new connection can take its place
+        if status == ConnectionStatus::Broken {
+            log::debug!("MSSQL POOL closing canceled Connection");
+            drop(conn);
+            for slot in &pool.slots {
+                let mut slot_guard = slot.lock().unwrap();
+                if let Slot::Checkedout = slot_guard.deref_mut() {
+                    *slot_guard = Slot::Empty;
+                    return;
+                }
+            }
+            return;
+        }
 
         // before putting the connection back in the pool, rollback the transaction if needed
         if let ConnectionStatus::NeedsRollback(_trans_name) = status {
@@ -230,7 +324,11 @@ async fn pool_return_inner(
 
             if is_checkout {
                 // return the connection to the pool
-                let mut returning = Slot::Avalable(conn);
+                let mut returning = Slot::Avalable(Box::new(IdleConn {
+                    conn,
+                    created,
+                    idle_since: Instant::now(),
+                }));
                 std::mem::swap(&mut returning, slot_guard);
                 {
                     // update the next slot to use to point to this slot.
@@ -248,3 +346,73 @@ async fn pool_return_inner(
     });
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ago(secs: u64) -> Instant {
+        Instant::now() - Duration::from_secs(secs)
+    }
+
+    fn build_pool(config: &PoolConfig) -> Arc<Pool> {
+        // building the manager doesn't connect to anything
+        let cs = "server=tcp:localhost,1433;user=sa;password=welds!123;TrustServerCertificate=true";
+        let mgr = ConnectionManager::build(cs).unwrap();
+        Pool::new(mgr, config)
+    }
+
+    #[test]
+    fn settings_should_default_to_testing_connections() {
+        let settings = Settings::new(&PoolConfig::default());
+        assert!(settings.test_before_acquire);
+        assert_eq!(settings.acquire_timeout, None);
+        // connections never expire without an idle_timeout or max_lifetime
+        assert!(!settings.is_expired(ago(86_400), ago(86_400)));
+    }
+
+    #[test]
+    fn connections_should_expire_when_idle_or_old() {
+        let config = PoolConfig::new()
+            .idle_timeout(Duration::from_secs(30))
+            .max_lifetime(Duration::from_secs(300));
+        let settings = Settings::new(&config);
+        assert!(!settings.is_expired(ago(60), ago(10)));
+        assert!(settings.is_expired(ago(60), ago(31)));
+        assert!(settings.is_expired(ago(301), ago(0)));
+    }
+
+    #[tokio::test]
+    async fn pool_should_be_sized_by_max_connections() {
+        assert_eq!(build_pool(&PoolConfig::new()).slots.len(), 10);
+        let config = PoolConfig::new().max_connections(3);
+        assert_eq!(build_pool(&config).slots.len(), 3);
+        let config = PoolConfig::new().max_connections(0);
+        assert_eq!(build_pool(&config).slots.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn min_connections_should_not_be_more_than_the_pool_holds() {
+        assert_eq!(build_pool(&PoolConfig::new()).min_connections(), 0);
+        let config = PoolConfig::new().min_connections(2);
+        assert_eq!(build_pool(&config).min_connections(), 2);
+        let config = PoolConfig::new().min_connections(20);
+        assert_eq!(build_pool(&config).min_connections(), 10);
+        let config = PoolConfig::new().max_connections(3).min_connections(20);
+        assert_eq!(build_pool(&config).min_connections(), 3);
+    }
+
+    #[tokio::test]
+    async fn get_should_time_out_when_every_connection_is_checked_out() {
+        let config = PoolConfig::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_millis(50));
+        let pool = build_pool(&config);
+        *pool.slots[0].lock().unwrap() = Slot::Checkedout;
+        let started = Instant::now();
+        let result = pool.get().await;
+        assert!(matches!(result, Err(PoolTimedOut)));
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert_eq!(pool.status().await, "[C]");
+    }
+}
@@ -1,4 +1,5 @@
 use super::super::MssqlParam;
+use super::ConnReturn;
 use super::ConnectionStatus;
 use super::TiberiusConn;
 use super::{Client, Param};
@@ -7,14 +8,20 @@ use crate::row::Row;
 use crate::ExecuteResult;
 use async_mutex::Mutex as AsyncMutex;
 use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
+use std::time::Instant;
 use tiberius::ToSql;
 
 pub struct PooledConnection {
     pub(crate) status: ConnectionStatus,
     // NOTE: this is an option so it can be taken when dropped
     pub(super) tiberius_conn: AsyncMutex<Option<TiberiusConn>>,
-    pub(super) conn_return: Sender<(TiberiusConn, ConnectionStatus)>,
+    pub(super) created: Instant,
+    // true while a statement is running. If still true when dropped,
+    // the statement was canceled part way through and the connection can't be reused
+    pub(super) in_flight: AtomicBool,
+    pub(super) conn_return: Sender<ConnReturn>,
 }
 
 impl PooledConnection {
@@ -43,15 +50,22 @@ impl Drop for PooledConnection {
         let mut conn: Option<TiberiusConn> = None;
         std::mem::swap(&mut conn, guard);
         let conn = conn.unwrap();
+        let status = match self.in_flight.load(Ordering::SeqCst) {
+            true => ConnectionStatus::Broken,
+            false => self.status.clone(),
+        };
         self.conn_return
-            .send((conn, self.status.clone()))
+            .send((conn, status, self.created))
             .expect("Unable to return connection to pool");
     }
 }
 
-#[async_trait]
-impl Client for PooledConnection {
-    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+impl PooledConnection {
+    async fn execute_inner(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync)],
+    ) -> Result<ExecuteResult> {
         let mut guard = self.tiberius_conn.lock().await;
         let conn: &mut TiberiusConn = guard.as_mut().unwrap();
 
@@ -66,7 +80,11 @@ impl Client for PooledConnection {
         })
     }
 
-    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+    async fn fetch_rows_inner(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync)],
+    ) -> Result<Vec<Row>> {
         let mut guard = self.tiberius_conn.lock().await;
         let conn: &mut TiberiusConn = guard.as_mut().unwrap();
 
@@ -87,7 +105,7 @@ impl Client for PooledConnection {
         Ok(all)
     }
 
-    async fn fetch_many<'s, 'args, 't>(
+    async fn fetch_many_inner<'s, 'args, 't>(
         &self,
         args: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
@@ -114,6 +132,33 @@ impl Client for PooledConnection {
         }
         Ok(resultset)
     }
+}
+
+#[async_trait]
+impl Client for PooledConnection {
+    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+        self.in_flight.store(true, Ordering::SeqCst);
+        let result = self.execute_inner(sql, params).await;
+        self.in_flight.store(false, Ordering::SeqCst);
+        result
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        self.in_flight.store(true, Ordering::SeqCst);
+        let result = self.fetch_rows_inner(sql, params).await;
+        self.in_flight.store(false, Ordering::SeqCst);
+        result
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        args: &[crate::Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        self.in_flight.store(true, Ordering::SeqCst);
+        let result = self.fetch_many_inner(args).await;
+        self.in_flight.store(false, Ordering::SeqCst);
+        result
+    }
 
     fn syntax(&self) -> crate::Syntax {
         crate::Syntax::Mssql
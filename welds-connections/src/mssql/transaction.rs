@@ -42,7 +42,9 @@ impl MssqlTransaction<'_> {
 
     pub async fn commit(mut self) -> Result<()> {
         log::debug!("MSSQL COMMIT: {}", self.trans_name);
-        assert_eq!(self.state, State::Open);
+        if self.state != State::Open {
+            return Err(ClosedTransaction);
+        }
         self.state = State::Commited;
         let sql = format!("COMMIT TRANSACTION {}", self.trans_name);
         self.conn.simple_query(&sql).await?;
@@ -55,7 +57,9 @@ impl MssqlTransaction<'_> {
         if self.state == State::Rolledback {
             return Ok(());
         }
-        assert_eq!(self.state, State::Open);
+        if self.state != State::Open {
+            return Err(ClosedTransaction);
+        }
         self.state = State::Rolledback;
         let sql = format!("ROLLBACK TRANSACTION {}", self.trans_name);
         self.conn.simple_query(&sql).await?;
@@ -3,15 +3,22 @@ use super::Row;
 use super::TransactStart;
 use super::{Client, Param};
 use crate::errors::Result;
+use crate::timeout::{self, deadline, Cancelable};
+use crate::trace;
 use crate::ExecuteResult;
+use crate::PoolConfig;
 use async_trait::async_trait;
 use sqlx::mysql::MySqlArguments;
+use sqlx::mysql::MySqlConnectOptions;
 use sqlx::query::Query;
-use sqlx::{MySql, MySqlPool};
+use sqlx::{Connection, Executor, MySql, MySqlConnection, MySqlPool};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct MysqlClient {
     pool: Arc<MySqlPool>,
+    timeout: Option<Duration>,
 }
 
 #[async_trait]
@@ -24,9 +31,31 @@ impl TransactStart for MysqlClient {
 }
 
 pub async fn connect(url: &str) -> Result<MysqlClient> {
-    let pool = MySqlPool::connect(url).await?;
+    connect_with(url, &PoolConfig::default()).await
+}
+
+/// Connect using custom connection pool settings
+pub async fn connect_with(url: &str, config: &PoolConfig) -> Result<MysqlClient> {
+    let mut options = MySqlConnectOptions::from_str(url)?;
+    if let Some(size) = config.statement_cache_size {
+        options = options.statement_cache_capacity(size);
+    }
+    let mut pool_options = config.sqlx_pool_options::<MySql>();
+    if let Some(timeout) = config.statement_timeout {
+        // have the server cancel any SELECT that runs too long
+        let sql = format!("SET SESSION max_execution_time = {}", timeout.as_millis());
+        pool_options = pool_options.after_connect(move |conn, _meta| {
+            let sql = sql.clone();
+            Box::pin(async move {
+                conn.execute(sql.as_str()).await?;
+                Ok(())
+            })
+        });
+    }
+    let pool = pool_options.connect_with(options).await?;
     Ok(MysqlClient {
         pool: Arc::new(pool),
+        timeout: config.statement_timeout,
     })
 }
 
@@ -34,6 +63,7 @@ impl From<sqlx::MySqlPool> for MysqlClient {
     fn from(pool: sqlx::MySqlPool) -> MysqlClient {
         MysqlClient {
             pool: Arc::new(pool),
+            timeout: None,
         }
     }
 }
@@ -42,52 +72,84 @@ impl MysqlClient {
     pub fn as_sqlx_pool(&self) -> &MySqlPool {
         &self.pool
     }
+
+    /// Checks out a connection to run a statement on. When the statement is running under a
+    /// deadline, it can be canceled on the server with `KILL QUERY` if the deadline passes
+    async fn acquire(&self) -> Result<Cancelable<MySql>> {
+        let mut conn = self.pool.acquire().await?;
+        if !timeout::limited() {
+            return Ok(Cancelable::new(conn));
+        }
+        let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+            .fetch_one(&mut *conn)
+            .await?;
+        let options = self.pool.connect_options();
+        Ok(Cancelable::new(conn).on_timeout(async move {
+            // the statement's connection is busy, cancel it from a connection outside the pool
+            let mut conn = MySqlConnection::connect_with(&options).await?;
+            conn.execute(format!("KILL QUERY {id}").as_str()).await?;
+            conn.close().await
+        }))
+    }
 }
 
+#[cfg(feature = "noop")]
+use crate::noop::ParamValue;
 use sqlx::encode::Encode;
 use sqlx::types::Type;
 
 #[async_trait]
 impl Client for MysqlClient {
     async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
-        let mut query = sqlx::query::<MySql>(sql);
-        for param in params {
-            query = MysqlParam::add_param(*param, query);
-        }
-        let r = query.execute(&*self.pool).await?;
-        Ok(ExecuteResult {
-            rows_affected: r.rows_affected(),
-        })
+        let running = deadline(self.timeout, async {
+            let mut query = sqlx::query::<MySql>(sql);
+            for param in params {
+                query = MysqlParam::add_param(*param, query);
+            }
+            let mut conn = self.acquire().await?;
+            let r = query.execute(&mut **conn).await?;
+            Ok(ExecuteResult {
+                rows_affected: r.rows_affected(),
+            })
+        });
+        trace::execute(self.syntax(), sql, params.len(), running).await
     }
 
     async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
-        let mut query = sqlx::query::<MySql>(sql);
-        for param in params {
-            query = MysqlParam::add_param(*param, query);
-        }
-        let mut raw_rows = query.fetch_all(&*self.pool).await?;
-        let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-        Ok(rows)
+        let running = deadline(self.timeout, async {
+            let mut query = sqlx::query::<MySql>(sql);
+            for param in params {
+                query = MysqlParam::add_param(*param, query);
+            }
+            let mut conn = self.acquire().await?;
+            let mut raw_rows = query.fetch_all(&mut **conn).await?;
+            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+            Ok(rows)
+        });
+        trace::fetch_rows(self.syntax(), sql, params.len(), running).await
     }
 
     async fn fetch_many<'s, 'args, 't>(
         &self,
         fetches: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
-        let mut datasets = Vec::default();
-        let mut conn = self.pool.acquire().await?;
-        for fetch in fetches {
-            let sql = fetch.sql;
-            let params = fetch.params;
-            let mut query = sqlx::query::<MySql>(sql);
-            for param in params {
-                query = MysqlParam::add_param(*param, query);
+        let running = deadline(self.timeout, async {
+            let mut datasets = Vec::default();
+            let mut conn = self.acquire().await?;
+            for fetch in fetches {
+                let sql = fetch.sql;
+                let params = fetch.params;
+                let mut query = sqlx::query::<MySql>(sql);
+                for param in params {
+                    query = MysqlParam::add_param(*param, query);
+                }
+                let mut raw_rows = query.fetch_all(&mut **conn).await?;
+                let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                datasets.push(rows);
             }
-            let mut raw_rows = query.fetch_all(&mut *conn).await?;
-            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-            datasets.push(rows);
-        }
-        Ok(datasets)
+            Ok(datasets)
+        });
+        trace::fetch_many(self.syntax(), fetches, running).await
     }
 
     fn syntax(&self) -> crate::Syntax {
@@ -100,6 +162,10 @@ pub trait MysqlParam {
         &'q self,
         query: Query<'q, MySql, MySqlArguments>,
     ) -> Query<'q, MySql, MySqlArguments>;
+
+    /// Reads the value out of the param so it can be recorded by the `MockClient`
+    #[cfg(feature = "noop")]
+    fn param_value(&self) -> ParamValue;
 }
 
 impl<T> MysqlParam for T
@@ -113,4 +179,52 @@ where
     ) -> Query<'q, MySql, MySqlArguments> {
         query.bind(self)
     }
+
+    #[cfg(feature = "noop")]
+    fn param_value(&self) -> ParamValue {
+        use sqlx::encode::IsNull;
+        use sqlx::TypeInfo;
+        let mut buf = Vec::default();
+        match Encode::<MySql>::encode_by_ref(self, &mut buf) {
+            Ok(IsNull::Yes) => return ParamValue::Null,
+            Ok(IsNull::No) => {}
+            Err(_) => return ParamValue::Unknown,
+        }
+        let type_info = Encode::<MySql>::produces(self).unwrap_or_else(T::type_info);
+        ParamValue::from_mysql(type_info.name(), &buf)
+    }
+}
+
+#[cfg(all(test, feature = "noop"))]
+mod tests {
+    use super::*;
+
+    fn value<T: MysqlParam>(param: T) -> ParamValue {
+        param.param_value()
+    }
+
+    #[test]
+    fn should_read_the_value_of_params() {
+        assert_eq!(value(true), ParamValue::Bool(true));
+        assert_eq!(value(-7_i8), ParamValue::Int(-7));
+        assert_eq!(value(7_i16), ParamValue::Int(7));
+        assert_eq!(value(-7_i32), ParamValue::Int(-7));
+        assert_eq!(value(i64::MAX), ParamValue::Int(i64::MAX));
+        assert_eq!(value(7_u32), ParamValue::Int(7));
+        assert_eq!(value(1.5_f32), ParamValue::Float(1.5));
+        assert_eq!(value(2.25_f64), ParamValue::Float(2.25));
+        assert_eq!(value("civic".to_string()), ParamValue::from("civic"));
+        assert_eq!(value("x".repeat(300)), ParamValue::Text("x".repeat(300)));
+        assert_eq!(value(vec![1_u8, 2]), ParamValue::Bytes(vec![1, 2]));
+        assert_eq!(value(None::<i32>), ParamValue::Null);
+        assert_eq!(value(Some(3_i64)), ParamValue::Int(3));
+    }
+
+    #[test]
+    fn should_read_dates_as_text() {
+        let made = chrono::NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        assert_eq!(value(made), ParamValue::from("2024-01-31"));
+        let sold = made.and_hms_milli_opt(13, 45, 0, 250).unwrap();
+        assert_eq!(value(sold), ParamValue::from("2024-01-31T13:45:00.25"));
+    }
 }
@@ -0,0 +1,403 @@
+use super::super::Row;
+use super::super::{Client, Param};
+use crate::errors::{Error, Result};
+use crate::row::{MockRow, Value};
+use crate::transaction::TransT;
+use crate::{ExecuteResult, Fetch, Syntax, TransactStart, Transaction};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// A client for testing. No database is needed.
+///
+/// Queue up the statements you expect to be run, along with the rows (or error) each should return.
+/// Each statement that is run must contain the SQL pattern of the next expectation in the queue,
+/// otherwise `Error::Mock` is returned.
+///
+/// ```rust,ignore
+/// let client = MockClient::new(Syntax::Postgres);
+/// client.expect_rows("FROM cars", vec![MockRow::new().column("id", 1_i32)]);
+/// client.expect_execute("DELETE FROM cars", 1);
+///
+/// // ... run the code under test with the client ...
+///
+/// client.assert_all_consumed();
+/// assert_eq!(client.history()[0].params, vec![ParamValue::Int(1)]);
+/// ```
+///
+/// A transaction started from a MockClient runs its statements on the client.
+/// Committing or rolling it back does nothing.
+pub struct MockClient {
+    syntax: Syntax,
+    expectations: Mutex<VecDeque<Expectation>>,
+    history: Mutex<Vec<MockStatement>>,
+}
+
+struct Expectation {
+    pattern: String,
+    result: Canned,
+}
+
+enum Canned {
+    Rows(Vec<MockRow>),
+    RowsAffected(u64),
+    Error(String),
+}
+
+/// A statement that was run on a `MockClient`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockStatement {
+    pub sql: String,
+    pub params: Vec<ParamValue>,
+}
+
+/// The value of a param that was bound to a statement.
+///
+/// Values are read using the encoding of the param for one of the enabled databases
+/// (Sqlite, then Postgres, MySql, MSSQL). Dates, times, uuids, decimals and json are read as text.
+/// If no database feature is enabled the values are `Unknown`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParamValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    /// A value that doesn't fit one of the other kinds, in its debug format
+    Other(String),
+    /// The value could not be read
+    Unknown,
+}
+
+impl From<i32> for ParamValue {
+    fn from(x: i32) -> Self {
+        ParamValue::Int(x as i64)
+    }
+}
+
+impl From<i64> for ParamValue {
+    fn from(x: i64) -> Self {
+        ParamValue::Int(x)
+    }
+}
+
+impl From<f64> for ParamValue {
+    fn from(x: f64) -> Self {
+        ParamValue::Float(x)
+    }
+}
+
+impl From<&str> for ParamValue {
+    fn from(x: &str) -> Self {
+        ParamValue::Text(x.to_string())
+    }
+}
+
+impl From<String> for ParamValue {
+    fn from(x: String) -> Self {
+        ParamValue::Text(x)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ParamValue {
+    /// Reads a param from the binary format it is sent to postgres as
+    pub(crate) fn from_postgres(type_name: &str, bytes: &[u8]) -> ParamValue {
+        let int = || match bytes.len() {
+            2 => Some(i16::from_be_bytes(bytes.try_into().ok()?) as i64),
+            4 => Some(i32::from_be_bytes(bytes.try_into().ok()?) as i64),
+            8 => Some(i64::from_be_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        };
+        let float = || match bytes.len() {
+            4 => Some(f32::from_be_bytes(bytes.try_into().ok()?) as f64),
+            8 => Some(f64::from_be_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        };
+        let text = || String::from_utf8(bytes.to_vec()).ok();
+        let value = match type_name {
+            "BOOL" => bytes.first().map(|b| ParamValue::Bool(*b != 0)),
+            "INT2" | "INT4" | "INT8" => int().map(ParamValue::Int),
+            "FLOAT4" | "FLOAT8" => float().map(ParamValue::Float),
+            "TEXT" | "VARCHAR" | "BPCHAR" | "NAME" | "CITEXT" => text().map(ParamValue::Text),
+            "BYTEA" => Some(ParamValue::Bytes(bytes.to_vec())),
+            // dates, times, uuids, decimals and json are recorded as text
+            _ => crate::row::text_value::postgres(type_name, bytes).map(ParamValue::Text),
+        };
+        value.unwrap_or_else(|| ParamValue::Other(format!("{type_name}: {bytes:?}")))
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl ParamValue {
+    /// Reads a param from the binary format it is sent to mysql as
+    pub(crate) fn from_mysql(type_name: &str, bytes: &[u8]) -> ParamValue {
+        let signed = || match bytes.len() {
+            1 => Some(i8::from_le_bytes(bytes.try_into().ok()?) as i64),
+            2 => Some(i16::from_le_bytes(bytes.try_into().ok()?) as i64),
+            4 => Some(i32::from_le_bytes(bytes.try_into().ok()?) as i64),
+            8 => Some(i64::from_le_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        };
+        let unsigned = || match bytes.len() {
+            1 => Some(bytes[0] as u64),
+            2 => Some(u16::from_le_bytes(bytes.try_into().ok()?) as u64),
+            4 => Some(u32::from_le_bytes(bytes.try_into().ok()?) as u64),
+            8 => Some(u64::from_le_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        };
+        let float = || match bytes.len() {
+            4 => Some(f32::from_le_bytes(bytes.try_into().ok()?) as f64),
+            8 => Some(f64::from_le_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        };
+        let value = match type_name {
+            "BOOLEAN" => bytes.first().map(|b| ParamValue::Bool(*b != 0)),
+            "TINYINT" | "SMALLINT" | "INT" | "MEDIUMINT" | "BIGINT" => {
+                signed().map(ParamValue::Int)
+            }
+            name if name.ends_with(" UNSIGNED") => unsigned()
+                .and_then(|x| i64::try_from(x).ok())
+                .map(ParamValue::Int),
+            "FLOAT" | "DOUBLE" => float().map(ParamValue::Float),
+            "CHAR" | "VARCHAR" | "TINYTEXT" | "TEXT" | "MEDIUMTEXT" | "LONGTEXT" | "ENUM"
+            | "JSON" | "DECIMAL" => mysql_lenenc(bytes)
+                .and_then(|x| String::from_utf8(x.to_vec()).ok())
+                .map(ParamValue::Text),
+            "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" => {
+                mysql_lenenc(bytes).map(|x| ParamValue::Bytes(x.to_vec()))
+            }
+            // dates and times are recorded as text
+            _ => crate::row::text_value::mysql(type_name, bytes).map(ParamValue::Text),
+        };
+        value.unwrap_or_else(|| ParamValue::Other(format!("{type_name}: {bytes:?}")))
+    }
+}
+
+/// The bytes of a length-encoded string or blob
+#[cfg(feature = "mysql")]
+fn mysql_lenenc(bytes: &[u8]) -> Option<&[u8]> {
+    let (len, start) = match *bytes.first()? {
+        0xfc => (
+            u16::from_le_bytes(bytes.get(1..3)?.try_into().ok()?) as usize,
+            3,
+        ),
+        0xfd => {
+            let b = bytes.get(1..4)?;
+            (u32::from_le_bytes([b[0], b[1], b[2], 0]) as usize, 4)
+        }
+        0xfe => (
+            u64::from_le_bytes(bytes.get(1..9)?.try_into().ok()?) as usize,
+            9,
+        ),
+        len => (len as usize, 1),
+    };
+    bytes.get(start..start + len)
+}
+
+#[cfg(feature = "mssql")]
+impl ParamValue {
+    pub(crate) fn from_column_data(data: &tiberius::ColumnData<'_>) -> ParamValue {
+        match Value::from_column_data(data) {
+            Some(value) => value.into(),
+            None => ParamValue::Other(format!("{:?}", data)),
+        }
+    }
+}
+
+impl ParamValue {
+    /// The name of the kind of value this is
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            ParamValue::Null => "Null",
+            ParamValue::Bool(_) => "Bool",
+            ParamValue::Int(_) => "Int",
+            ParamValue::Float(_) => "Float",
+            ParamValue::Text(_) => "Text",
+            ParamValue::Bytes(_) => "Bytes",
+            ParamValue::Other(_) => "Other",
+            ParamValue::Unknown => "Unknown",
+        }
+    }
+}
+
+impl From<Value> for ParamValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => ParamValue::Null,
+            Value::Bool(x) => ParamValue::Bool(x),
+            Value::Int(x) => ParamValue::Int(x),
+            Value::UInt(x) => ParamValue::Int(x as i64),
+            Value::Float(x) => ParamValue::Float(x),
+            Value::Text(x) => ParamValue::Text(x),
+            Value::Bytes(x) => ParamValue::Bytes(x),
+        }
+    }
+}
+
+impl MockClient {
+    pub fn new(syntax: Syntax) -> Self {
+        Self {
+            syntax,
+            expectations: Mutex::new(VecDeque::default()),
+            history: Mutex::new(Vec::default()),
+        }
+    }
+
+    /// Expect a statement containing `pattern`, returning these rows
+    pub fn expect_rows(&self, pattern: impl Into<String>, rows: Vec<MockRow>) {
+        self.push(pattern, Canned::Rows(rows));
+    }
+
+    /// Expect a statement containing `pattern`, reporting this many rows affected
+    pub fn expect_execute(&self, pattern: impl Into<String>, rows_affected: u64) {
+        self.push(pattern, Canned::RowsAffected(rows_affected));
+    }
+
+    /// Expect a statement containing `pattern`, failing with `Error::Mock(message)`
+    pub fn expect_error(&self, pattern: impl Into<String>, message: impl Into<String>) {
+        self.push(pattern, Canned::Error(message.into()));
+    }
+
+    /// The number of expected statements that have not been run yet
+    pub fn remaining(&self) -> usize {
+        self.expectations.lock().unwrap().len()
+    }
+
+    /// Panics if any of the expected statements have not been run
+    pub fn assert_all_consumed(&self) {
+        let expectations = self.expectations.lock().unwrap();
+        if !expectations.is_empty() {
+            let patterns: Vec<&str> = expectations.iter().map(|e| e.pattern.as_str()).collect();
+            panic!("MockClient has expected statements that were not run: {patterns:?}");
+        }
+    }
+
+    /// Every statement that was run on this client, in order
+    pub fn history(&self) -> Vec<MockStatement> {
+        self.history.lock().unwrap().clone()
+    }
+
+    /// Removes all the expectations and history
+    pub fn reset(&self) {
+        self.expectations.lock().unwrap().clear();
+        self.history.lock().unwrap().clear();
+    }
+
+    fn push(&self, pattern: impl Into<String>, result: Canned) {
+        let pattern = pattern.into();
+        let mut expectations = self.expectations.lock().unwrap();
+        expectations.push_back(Expectation { pattern, result });
+    }
+
+    /// Records the statement and takes the canned result for it
+    fn run(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Canned> {
+        let params = params.iter().map(|p| param_value(*p)).collect();
+        let mut history = self.history.lock().unwrap();
+        history.push(MockStatement {
+            sql: sql.to_string(),
+            params,
+        });
+
+        let mut expectations = self.expectations.lock().unwrap();
+        let expected = match expectations.front() {
+            Some(expected) => expected,
+            None => return Err(Error::Mock(format!("unexpected statement: {sql}"))),
+        };
+        if !sql.contains(&expected.pattern) {
+            return Err(Error::Mock(format!(
+                "expected a statement containing `{}`, got: {sql}",
+                expected.pattern
+            )));
+        }
+        match expectations.pop_front().map(|e| e.result) {
+            Some(Canned::Error(message)) => Err(Error::Mock(message)),
+            Some(result) => Ok(result),
+            None => Ok(Canned::Rows(Vec::default())),
+        }
+    }
+
+    fn fetch(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        match self.run(sql, params)? {
+            Canned::Rows(rows) => Ok(rows.into_iter().map(Row::from).collect()),
+            _ => Ok(Vec::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl Client for MockClient {
+    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+        let rows_affected = match self.run(sql, params)? {
+            Canned::Rows(rows) => rows.len() as u64,
+            Canned::RowsAffected(count) => count,
+            Canned::Error(_) => 0,
+        };
+        Ok(ExecuteResult { rows_affected })
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        self.fetch(sql, params)
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        fetches: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        let mut datasets = Vec::default();
+        for fetch in fetches {
+            datasets.push(self.fetch(fetch.sql, fetch.params)?);
+        }
+        Ok(datasets)
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+}
+
+#[async_trait]
+impl TransactStart for MockClient {
+    async fn begin<'t>(&'t self) -> Result<Transaction<'t>> {
+        Ok(Transaction::new(TransT::Mock(self)))
+    }
+}
+
+/// Reads the value of a param, using whichever encoding is available
+#[cfg(feature = "sqlite")]
+pub(crate) fn param_value(param: &(dyn Param + Sync)) -> ParamValue {
+    crate::sqlite::SqliteParam::param_value(param)
+}
+
+#[cfg(all(feature = "postgres", not(feature = "sqlite")))]
+pub(crate) fn param_value(param: &(dyn Param + Sync)) -> ParamValue {
+    crate::postgres::PostgresParam::param_value(param)
+}
+
+#[cfg(all(feature = "mysql", not(feature = "sqlite"), not(feature = "postgres")))]
+pub(crate) fn param_value(param: &(dyn Param + Sync)) -> ParamValue {
+    crate::mysql::MysqlParam::param_value(param)
+}
+
+#[cfg(all(
+    feature = "mssql",
+    not(feature = "sqlite"),
+    not(feature = "postgres"),
+    not(feature = "mysql")
+))]
+pub(crate) fn param_value(param: &(dyn Param + Sync)) -> ParamValue {
+    crate::mssql::MssqlParam::param_value(param)
+}
+
+// without a database feature there is no encoding to read the param with
+#[cfg(not(any(
+    feature = "sqlite",
+    feature = "postgres",
+    feature = "mysql",
+    feature = "mssql"
+)))]
+pub(crate) fn param_value(_param: &(dyn Param + Sync)) -> ParamValue {
+    ParamValue::Unknown
+}
@@ -5,6 +5,12 @@ use crate::{ExecuteResult, Syntax};
 use async_trait::async_trait;
 use std::sync::{Arc, Mutex};
 
+mod mock;
+pub use crate::row::MockRow;
+#[cfg(feature = "replay")]
+pub(crate) use mock::param_value;
+pub use mock::{MockClient, MockStatement, ParamValue};
+
 // This is a version of a client that does nothing.
 // It is used for testing
 //
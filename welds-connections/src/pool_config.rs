@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+/// Settings for the connection pool that is built when connecting to a database.
+///
+/// Any setting that is left unset will use the default for that backend.
+///
+/// ```rust,ignore
+/// let config = PoolConfig::new()
+///     .max_connections(20)
+///     .acquire_timeout(Duration::from_secs(5));
+/// let client = welds_connections::connect_with(url, &config).await?;
+/// ```
+///
+/// NOTE: MSSQL has no statement cache, `statement_cache_size` is ignored for MSSQL connections.
+/// NOTE: For Sqlite and MSSQL `statement_timeout` is not applied to transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolConfig {
+    pub(crate) max_connections: Option<u32>,
+    pub(crate) min_connections: Option<u32>,
+    pub(crate) acquire_timeout: Option<Duration>,
+    pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) max_lifetime: Option<Duration>,
+    pub(crate) test_before_acquire: Option<bool>,
+    pub(crate) statement_cache_size: Option<usize>,
+    pub(crate) statement_timeout: Option<Duration>,
+}
+
+impl PoolConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most connections the pool will open at one time
+    pub fn max_connections(mut self, max: u32) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
+    /// The number of connections the pool will try to keep open.
+    /// Never more than `max_connections`
+    pub fn min_connections(mut self, min: u32) -> Self {
+        self.min_connections = Some(min);
+        self
+    }
+
+    /// How long to wait for a connection from the pool before giving up
+    pub fn acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = Some(timeout);
+        self
+    }
+
+    /// How long a connection can sit unused in the pool before it is closed
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// How long a connection can live before it is closed and replaced
+    pub fn max_lifetime(mut self, lifetime: Duration) -> Self {
+        self.max_lifetime = Some(lifetime);
+        self
+    }
+
+    /// If true, a connection is pinged to make sure it is alive before it is handed out
+    pub fn test_before_acquire(mut self, test: bool) -> Self {
+        self.test_before_acquire = Some(test);
+        self
+    }
+
+    /// The number of prepared statements each connection will cache
+    pub fn statement_cache_size(mut self, size: usize) -> Self {
+        self.statement_cache_size = Some(size);
+        self
+    }
+
+    /// The longest a single statement is allowed to run before it is canceled.
+    /// A statement that times out returns `Error::Timeout`
+    ///
+    /// Postgres and MySql also have the database itself enforce this limit
+    /// (`statement_timeout` / `max_execution_time`), including inside transactions.
+    pub fn statement_timeout(mut self, timeout: Duration) -> Self {
+        self.statement_timeout = Some(timeout);
+        self
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "sqlite", feature = "postgres"))]
+impl PoolConfig {
+    /// Builds the sqlx PoolOptions for these settings
+    pub(crate) fn sqlx_pool_options<DB: sqlx::Database>(&self) -> sqlx::pool::PoolOptions<DB> {
+        let mut options = sqlx::pool::PoolOptions::<DB>::new();
+        if let Some(max) = self.max_connections {
+            options = options.max_connections(max);
+        }
+        if let Some(min) = self.min_connections {
+            options = options.min_connections(min);
+        }
+        if let Some(timeout) = self.acquire_timeout {
+            options = options.acquire_timeout(timeout);
+        }
+        if let Some(timeout) = self.idle_timeout {
+            options = options.idle_timeout(timeout);
+        }
+        if let Some(lifetime) = self.max_lifetime {
+            options = options.max_lifetime(lifetime);
+        }
+        if let Some(test) = self.test_before_acquire {
+            options = options.test_before_acquire(test);
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_default_to_the_backend_settings() {
+        let config = PoolConfig::new();
+        assert_eq!(config, PoolConfig::default());
+        assert_eq!(config.max_connections, None);
+        assert_eq!(config.min_connections, None);
+        assert_eq!(config.acquire_timeout, None);
+        assert_eq!(config.idle_timeout, None);
+        assert_eq!(config.max_lifetime, None);
+        assert_eq!(config.test_before_acquire, None);
+        assert_eq!(config.statement_cache_size, None);
+        assert_eq!(config.statement_timeout, None);
+    }
+
+    #[test]
+    fn builder_should_set_each_setting() {
+        let config = PoolConfig::new()
+            .max_connections(20)
+            .min_connections(2)
+            .acquire_timeout(Duration::from_secs(5))
+            .idle_timeout(Duration::from_secs(60))
+            .max_lifetime(Duration::from_secs(600))
+            .test_before_acquire(false)
+            .statement_cache_size(50)
+            .statement_timeout(Duration::from_secs(3));
+        assert_eq!(config.max_connections, Some(20));
+        assert_eq!(config.min_connections, Some(2));
+        assert_eq!(config.acquire_timeout, Some(Duration::from_secs(5)));
+        assert_eq!(config.idle_timeout, Some(Duration::from_secs(60)));
+        assert_eq!(config.max_lifetime, Some(Duration::from_secs(600)));
+        assert_eq!(config.test_before_acquire, Some(false));
+        assert_eq!(config.statement_cache_size, Some(50));
+        assert_eq!(config.statement_timeout, Some(Duration::from_secs(3)));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlx_pool_options_should_only_change_what_was_set() {
+        let defaults = sqlx::pool::PoolOptions::<sqlx::Sqlite>::new();
+        let options = PoolConfig::new()
+            .max_connections(7)
+            .idle_timeout(Duration::from_secs(9))
+            .test_before_acquire(false)
+            .sqlx_pool_options::<sqlx::Sqlite>();
+        assert_eq!(options.get_max_connections(), 7);
+        assert_eq!(options.get_idle_timeout(), Some(Duration::from_secs(9)));
+        assert!(!options.get_test_before_acquire());
+        assert_eq!(
+            options.get_min_connections(),
+            defaults.get_min_connections()
+        );
+        assert_eq!(
+            options.get_acquire_timeout(),
+            defaults.get_acquire_timeout()
+        );
+        assert_eq!(options.get_max_lifetime(), defaults.get_max_lifetime());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn connect_from_url_should_connect_or_error_without_a_url() {
+        use crate::{connect_from_url, Client, Error};
+        let config = PoolConfig::new().max_connections(1);
+
+        let missing = connect_from_url(None, &config).await;
+        assert!(matches!(missing, Err(Error::InvalidDatabaseUrl)));
+
+        let url = Some("sqlite::memory:".to_string());
+        let client = connect_from_url(url, &config).await.unwrap();
+        assert_eq!(client.syntax(), crate::Syntax::Sqlite);
+        client.execute("SELECT 1", &[]).await.unwrap();
+    }
+}
@@ -3,16 +3,23 @@ use super::Row;
 use super::TransactStart;
 use super::{Client, Param};
 use crate::errors::Result;
+use crate::timeout::{self, deadline, Cancelable};
+use crate::trace;
 use crate::ExecuteResult;
+use crate::PoolConfig;
 use async_trait::async_trait;
 use sqlx::postgres::PgArguments;
+use sqlx::postgres::PgConnectOptions;
 use sqlx::query::Query;
-use sqlx::{PgPool, Postgres};
+use sqlx::{Connection, PgConnection, PgPool, Postgres};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct PostgresClient {
     pool: Arc<PgPool>,
+    timeout: Option<Duration>,
 }
 
 #[async_trait]
@@ -25,9 +32,26 @@ impl TransactStart for PostgresClient {
 }
 
 pub async fn connect(url: &str) -> Result<PostgresClient> {
-    let pool = PgPool::connect(url).await?;
+    connect_with(url, &PoolConfig::default()).await
+}
+
+/// Connect using custom connection pool settings
+pub async fn connect_with(url: &str, config: &PoolConfig) -> Result<PostgresClient> {
+    let mut options = PgConnectOptions::from_str(url)?;
+    if let Some(size) = config.statement_cache_size {
+        options = options.statement_cache_capacity(size);
+    }
+    if let Some(timeout) = config.statement_timeout {
+        // have the server cancel any statement that runs too long
+        options = options.options([("statement_timeout", timeout.as_millis())]);
+    }
+    let pool = config
+        .sqlx_pool_options::<Postgres>()
+        .connect_with(options)
+        .await?;
     Ok(PostgresClient {
         pool: Arc::new(pool),
+        timeout: config.statement_timeout,
     })
 }
 
@@ -35,6 +59,7 @@ impl From<sqlx::PgPool> for PostgresClient {
     fn from(pool: sqlx::PgPool) -> PostgresClient {
         PostgresClient {
             pool: Arc::new(pool),
+            timeout: None,
         }
     }
 }
@@ -43,52 +68,90 @@ impl PostgresClient {
     pub fn as_sqlx_pool(&self) -> &PgPool {
         &self.pool
     }
+
+    /// Checks out a connection to run a statement on. When the statement is running under a
+    /// deadline, it can be canceled on the server with `pg_cancel_backend` if the deadline passes
+    async fn acquire(&self) -> Result<Cancelable<Postgres>> {
+        let mut conn = self.pool.acquire().await?;
+        // With a statement_timeout the server cancels the statement itself when the deadline passes.
+        // The backend only needs looking up when a TimeoutClient gives it less time than that
+        let server_cancels = self.timeout.is_some() && !timeout::limited_by_outer();
+        if !timeout::limited() || server_cancels {
+            return Ok(Cancelable::new(conn));
+        }
+        let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+            .fetch_one(&mut *conn)
+            .await?;
+        let options = self.pool.connect_options();
+        Ok(Cancelable::new(conn).on_timeout(async move {
+            // the statement's connection is busy, cancel it from a connection outside the pool
+            let mut conn = PgConnection::connect_with(&options).await?;
+            sqlx::query("SELECT pg_cancel_backend($1)")
+                .bind(pid)
+                .execute(&mut conn)
+                .await?;
+            conn.close().await
+        }))
+    }
 }
 
+#[cfg(feature = "noop")]
+use crate::noop::ParamValue;
 use sqlx::encode::Encode;
 use sqlx::types::Type;
 
 #[async_trait]
 impl Client for PostgresClient {
     async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
-        let mut query = sqlx::query::<Postgres>(sql);
-        for param in params {
-            query = PostgresParam::add_param(*param, query);
-        }
-        let r = query.execute(&*self.pool).await?;
-        Ok(ExecuteResult {
-            rows_affected: r.rows_affected(),
-        })
+        let running = deadline(self.timeout, async {
+            let mut query = sqlx::query::<Postgres>(sql);
+            for param in params {
+                query = PostgresParam::add_param(*param, query);
+            }
+            let mut conn = self.acquire().await?;
+            let r = query.execute(&mut **conn).await?;
+            Ok(ExecuteResult {
+                rows_affected: r.rows_affected(),
+            })
+        });
+        trace::execute(self.syntax(), sql, params.len(), running).await
     }
 
     async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
-        let mut query = sqlx::query::<Postgres>(sql);
-        for param in params {
-            query = PostgresParam::add_param(*param, query);
-        }
-        let mut raw_rows = query.fetch_all(&*self.pool).await?;
-        let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-        Ok(rows)
+        let running = deadline(self.timeout, async {
+            let mut query = sqlx::query::<Postgres>(sql);
+            for param in params {
+                query = PostgresParam::add_param(*param, query);
+            }
+            let mut conn = self.acquire().await?;
+            let mut raw_rows = query.fetch_all(&mut **conn).await?;
+            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+            Ok(rows)
+        });
+        trace::fetch_rows(self.syntax(), sql, params.len(), running).await
     }
 
     async fn fetch_many<'s, 'args, 't>(
         &self,
         fetches: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
-        let mut datasets = Vec::default();
-        let mut conn = self.pool.acquire().await?;
-        for fetch in fetches {
-            let sql = fetch.sql;
-            let params = fetch.params;
-            let mut query = sqlx::query::<Postgres>(sql);
-            for param in params {
-                query = PostgresParam::add_param(*param, query);
+        let running = deadline(self.timeout, async {
+            let mut datasets = Vec::default();
+            let mut conn = self.acquire().await?;
+            for fetch in fetches {
+                let sql = fetch.sql;
+                let params = fetch.params;
+                let mut query = sqlx::query::<Postgres>(sql);
+                for param in params {
+                    query = PostgresParam::add_param(*param, query);
+                }
+                let mut raw_rows = query.fetch_all(&mut **conn).await?;
+                let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                datasets.push(rows);
             }
-            let mut raw_rows = query.fetch_all(&mut *conn).await?;
-            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-            datasets.push(rows);
-        }
-        Ok(datasets)
+            Ok(datasets)
+        });
+        trace::fetch_many(self.syntax(), fetches, running).await
     }
 
     fn syntax(&self) -> crate::Syntax {
@@ -101,6 +164,10 @@ pub trait PostgresParam {
         &'q self,
         query: Query<'q, Postgres, PgArguments>,
     ) -> Query<'q, Postgres, PgArguments>;
+
+    /// Reads the value out of the param so it can be recorded by the `MockClient`
+    #[cfg(feature = "noop")]
+    fn param_value(&self) -> ParamValue;
 }
 
 impl<T> PostgresParam for T
@@ -114,4 +181,51 @@ where
     ) -> Query<'q, Postgres, PgArguments> {
         query.bind(self)
     }
+
+    #[cfg(feature = "noop")]
+    fn param_value(&self) -> ParamValue {
+        use sqlx::encode::IsNull;
+        use sqlx::postgres::PgArgumentBuffer;
+        use sqlx::TypeInfo;
+        let mut buf = PgArgumentBuffer::default();
+        match Encode::<Postgres>::encode_by_ref(self, &mut buf) {
+            Ok(IsNull::Yes) => return ParamValue::Null,
+            Ok(IsNull::No) => {}
+            Err(_) => return ParamValue::Unknown,
+        }
+        let type_info = Encode::<Postgres>::produces(self).unwrap_or_else(T::type_info);
+        ParamValue::from_postgres(type_info.name(), &buf)
+    }
+}
+
+#[cfg(all(test, feature = "noop"))]
+mod tests {
+    use super::*;
+
+    fn value<T: PostgresParam>(param: T) -> ParamValue {
+        param.param_value()
+    }
+
+    #[test]
+    fn should_read_the_value_of_params() {
+        assert_eq!(value(true), ParamValue::Bool(true));
+        assert_eq!(value(7_i16), ParamValue::Int(7));
+        assert_eq!(value(-7_i32), ParamValue::Int(-7));
+        assert_eq!(value(i64::MAX), ParamValue::Int(i64::MAX));
+        assert_eq!(value(1.5_f32), ParamValue::Float(1.5));
+        assert_eq!(value(2.25_f64), ParamValue::Float(2.25));
+        assert_eq!(value("civic".to_string()), ParamValue::from("civic"));
+        assert_eq!(value(vec![1_u8, 2]), ParamValue::Bytes(vec![1, 2]));
+        assert_eq!(value(None::<i32>), ParamValue::Null);
+        assert_eq!(value(Some(3_i64)), ParamValue::Int(3));
+    }
+
+    #[test]
+    fn should_read_dates_and_uuids_as_text() {
+        let id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        let uuid = uuid::Uuid::parse_str(id).unwrap();
+        assert_eq!(value(uuid), ParamValue::from(id));
+        let made = chrono::NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        assert_eq!(value(made), ParamValue::from("2024-01-31"));
+    }
 }
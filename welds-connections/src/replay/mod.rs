@@ -0,0 +1,608 @@
+use super::Row;
+use super::{Client, Param};
+use crate::errors::{Error, Result};
+use crate::middleware::{Chain, Middleware, Request, Response, Statement};
+use crate::noop::{param_value, ParamValue};
+use crate::row::MockRow;
+use crate::transaction::TransT;
+use crate::{ExecuteResult, Fetch, Syntax, TransactStart, Transaction};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Every statement that was run through a `RecordingClient`, along with what the database returned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    pub syntax: Syntax,
+    pub statements: Vec<RecordedStatement>,
+}
+
+/// A single statement in a `Recording`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedStatement {
+    pub sql: String,
+    pub params: Vec<ParamValue>,
+    pub result: RecordedResult,
+}
+
+/// What the database returned for a `RecordedStatement`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedResult {
+    RowsAffected(u64),
+    Rows(Vec<RecordedRow>),
+    Error(String),
+}
+
+/// The columns of a row, in order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRow {
+    pub columns: Vec<RecordedColumn>,
+}
+
+/// A column of a `RecordedRow`, with the database type it was read from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedColumn {
+    pub name: String,
+    pub type_name: String,
+    pub value: ParamValue,
+}
+
+impl Recording {
+    pub fn new(syntax: Syntax) -> Self {
+        Self {
+            syntax,
+            statements: Vec::default(),
+        }
+    }
+
+    /// Reads a recording from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let json = std::fs::read_to_string(path).map_err(|err| Error::Replay(err.to_string()))?;
+        serde_json::from_str(&json).map_err(|err| Error::Replay(err.to_string()))
+    }
+
+    /// Writes the recording to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|err| Error::Replay(err.to_string()))?;
+        std::fs::write(path, json).map_err(|err| Error::Replay(err.to_string()))
+    }
+}
+
+/// Wraps a client, recording every statement, its params, and the rows that were returned.
+///
+/// ```rust,ignore
+/// let client = RecordingClient::new(welds_connections::sqlite::connect(url).await?);
+/// // ... run your tests with the client ...
+/// client.save("tests/recordings/cars.json")?;
+/// ```
+///
+/// Columns are recorded as loosely typed values (numbers, bool, text, bytes), along with their database type.
+/// Dates, times, uuids, decimals and json are recorded as text.
+/// Use `ReplayClient::parse_text` to read them back as their own type.
+///
+/// Statements run inside a transaction started from this client are recorded too.
+/// The BEGIN / COMMIT / ROLLBACK of the transaction are not.
+pub struct RecordingClient<C> {
+    inner: C,
+    recorder: Recorder,
+    chain: Chain,
+}
+
+impl<C> RecordingClient<C>
+where
+    C: Client,
+{
+    pub fn new(inner: C) -> Self {
+        let recording = Recording::new(inner.syntax());
+        let recorder = Recorder(Arc::new(Mutex::new(recording)));
+        let chain = Chain::single(Arc::new(recorder.clone()));
+        Self {
+            inner,
+            recorder,
+            chain,
+        }
+    }
+
+    /// Returns the client being recorded
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Returns a copy of everything recorded so far
+    pub fn recording(&self) -> Recording {
+        self.recorder.0.lock().unwrap().clone()
+    }
+
+    /// Writes everything recorded so far to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.recording().save(path)
+    }
+}
+
+/// Records the statements sent through it.
+/// Shared by a `RecordingClient` and the transactions it starts
+#[derive(Clone)]
+struct Recorder(Arc<Mutex<Recording>>);
+
+impl Recorder {
+    fn record(&self, statement: &Statement<'_>, result: RecordedResult) {
+        let params = statement.params.iter().map(|p| param_value(*p)).collect();
+        let mut recording = self.0.lock().unwrap();
+        recording.statements.push(RecordedStatement {
+            sql: statement.sql.clone(),
+            params,
+            result,
+        });
+    }
+
+    /// The rows can't be recorded. Returns the error in place of the rows, and records it
+    fn fail(&self, statement: &Statement<'_>, response: &mut Result<Response>, err: Error) {
+        self.record(statement, RecordedResult::Error(err.to_string()));
+        *response = Err(err);
+    }
+}
+
+#[async_trait]
+impl Middleware for Recorder {
+    async fn after(&self, request: &Request<'_>, response: &mut Result<Response>) {
+        let statements = request.statements();
+        match response {
+            Ok(Response::Execute(r)) => {
+                let recorded = RecordedResult::RowsAffected(r.rows_affected());
+                self.record(&statements[0], recorded);
+            }
+            Ok(Response::FetchRows(rows)) => match record_rows(rows) {
+                Ok(recorded) => self.record(&statements[0], recorded),
+                Err(err) => self.fail(&statements[0], response, err),
+            },
+            Ok(Response::FetchMany(datasets)) => {
+                let recorded: Result<Vec<_>> = datasets.iter().map(|r| record_rows(r)).collect();
+                match recorded {
+                    Ok(recorded) => {
+                        for (statement, rows) in statements.iter().zip(recorded) {
+                            self.record(statement, rows);
+                        }
+                    }
+                    Err(err) => self.fail(&statements[0], response, err),
+                }
+            }
+            Err(err) => {
+                // a batch fails as a whole, the error is recorded on the first statement
+                if let Some(statement) = statements.first() {
+                    self.record(statement, RecordedResult::Error(err.to_string()));
+                }
+            }
+        }
+    }
+}
+
+fn record_rows(rows: &[Row]) -> Result<RecordedResult> {
+    let rows = rows
+        .iter()
+        .map(|row| {
+            let columns = row.recorded_values()?;
+            Ok(RecordedRow { columns })
+        })
+        .collect::<Result<_>>()?;
+    Ok(RecordedResult::Rows(rows))
+}
+
+#[async_trait]
+impl<C> Client for RecordingClient<C>
+where
+    C: Client,
+{
+    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+        self.chain.execute(&self.inner, false, sql, params).await
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        self.chain.fetch_rows(&self.inner, false, sql, params).await
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        fetches: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        self.chain.fetch_many(&self.inner, false, fetches).await
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.inner.syntax()
+    }
+}
+
+#[async_trait]
+impl<C> TransactStart for RecordingClient<C>
+where
+    C: Client + TransactStart,
+{
+    async fn begin<'t>(&'t self) -> Result<Transaction<'t>> {
+        let trans = self.inner.begin().await?;
+        Ok(trans.with_middleware(self.chain.clone()))
+    }
+}
+
+/// A client that serves the results from a `Recording`. No database is needed.
+///
+/// Statements must be run in the same order, with the same SQL and params, as when they were recorded.
+/// If the SQL or params are different, `Error::Replay` is returned.
+/// A param that could not be read (`ParamValue::Unknown`), when recording or replaying, matches any value.
+///
+/// ```rust,ignore
+/// let client = ReplayClient::load("tests/recordings/cars.json")?;
+/// // ... run your tests with the client ...
+/// client.assert_all_consumed();
+/// ```
+///
+/// NOTE: recorded errors are replayed as `Error::Replay` with the message of the original error.
+///
+/// Dates, times, uuids, decimals and json were recorded as text.
+/// Register the types you read them as, and the text is parsed back into them:
+///
+/// ```rust,ignore
+/// let client = ReplayClient::load("tests/recordings/cars.json")?
+///     .parse_text::<chrono::NaiveDateTime>()
+///     .parse_text::<uuid::Uuid>();
+/// ```
+///
+/// A transaction started from a ReplayClient replays its statements like any other statement.
+/// Committing or rolling it back does nothing, the same as a transaction of a `MockClient`.
+pub struct ReplayClient {
+    syntax: Syntax,
+    statements: Mutex<VecDeque<RecordedStatement>>,
+    // an empty row holding the text parsers, every replayed row starts from it
+    empty_row: MockRow,
+}
+
+impl ReplayClient {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            syntax: recording.syntax,
+            statements: Mutex::new(recording.statements.into()),
+            empty_row: MockRow::new(),
+        }
+    }
+
+    /// Builds a ReplayClient from a recording saved to a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(Recording::load(path)?))
+    }
+
+    /// Text columns of the replayed rows can be read as a `T` (or `Option<T>`), by parsing them.
+    /// See `MockRow::parse_text`
+    pub fn parse_text<T>(mut self) -> Self
+    where
+        T: FromStr + 'static,
+    {
+        self.empty_row = self.empty_row.parse_text::<T>();
+        self
+    }
+
+    /// The number of recorded statements that have not been replayed yet
+    pub fn remaining(&self) -> usize {
+        self.statements.lock().unwrap().len()
+    }
+
+    /// Panics if any of the recorded statements have not been replayed
+    pub fn assert_all_consumed(&self) {
+        let statements = self.statements.lock().unwrap();
+        if let Some(next) = statements.front() {
+            panic!(
+                "ReplayClient has {} recorded statements that were not run. next: {}",
+                statements.len(),
+                next.sql
+            );
+        }
+    }
+
+    /// Takes the next recorded result, making sure the SQL and params match what was recorded
+    fn next(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<RecordedResult> {
+        let mut statements = self.statements.lock().unwrap();
+        let recorded = match statements.front() {
+            Some(recorded) => recorded,
+            None => return Err(Error::Replay(format!("no recorded statement for: {sql}"))),
+        };
+        if recorded.sql != sql {
+            return Err(Error::Replay(format!(
+                "SQL does not match the recording.\nexpected: {}\n     got: {sql}",
+                recorded.sql
+            )));
+        }
+        let params: Vec<ParamValue> = params.iter().map(|p| param_value(*p)).collect();
+        if !params_match(&recorded.params, &params) {
+            return Err(Error::Replay(format!(
+                "params do not match the recording for: {sql}\nexpected: {:?}\n     got: {params:?}",
+                recorded.params
+            )));
+        }
+        match statements.pop_front().map(|s| s.result) {
+            Some(RecordedResult::Error(message)) => Err(Error::Replay(message)),
+            Some(result) => Ok(result),
+            None => Ok(RecordedResult::Rows(Vec::default())),
+        }
+    }
+
+    fn fetch(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        let rows = match self.next(sql, params)? {
+            RecordedResult::Rows(rows) => rows,
+            _ => Vec::default(),
+        };
+        let rows = rows.into_iter().map(|recorded| {
+            let row = recorded
+                .columns
+                .into_iter()
+                .fold(self.empty_row.clone(), |row, c| {
+                    row.value_of_type(c.name, c.type_name, c.value)
+                });
+            Row::from(row)
+        });
+        Ok(rows.collect())
+    }
+}
+
+#[async_trait]
+impl Client for ReplayClient {
+    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+        let rows_affected = match self.next(sql, params)? {
+            RecordedResult::RowsAffected(count) => count,
+            RecordedResult::Rows(rows) => rows.len() as u64,
+            RecordedResult::Error(_) => 0,
+        };
+        Ok(ExecuteResult { rows_affected })
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        self.fetch(sql, params)
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        fetches: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        let mut datasets = Vec::default();
+        for fetch in fetches {
+            datasets.push(self.fetch(fetch.sql, fetch.params)?);
+        }
+        Ok(datasets)
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+}
+
+#[async_trait]
+impl TransactStart for ReplayClient {
+    async fn begin<'t>(&'t self) -> Result<Transaction<'t>> {
+        Ok(Transaction::new(TransT::Mock(self)))
+    }
+}
+
+/// Params that could not be read (Unknown) match anything
+fn params_match(recorded: &[ParamValue], params: &[ParamValue]) -> bool {
+    let unknown = |p: &ParamValue| *p == ParamValue::Unknown;
+    recorded.len() == params.len()
+        && recorded
+            .iter()
+            .zip(params)
+            .all(|(a, b)| a == b || unknown(a) || unknown(b))
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::noop::MockClient;
+
+    fn civic() -> String {
+        "civic".to_string()
+    }
+
+    fn recorded_client() -> RecordingClient<MockClient> {
+        let mock = MockClient::new(Syntax::Postgres);
+        let car = MockRow::new()
+            .value("id", ParamValue::Int(1))
+            .value("name", ParamValue::Text("civic".to_string()));
+        mock.expect_rows("FROM cars", vec![car]);
+        mock.expect_execute("DELETE FROM cars", 3);
+        mock.expect_error("FROM missing", "no such table");
+        RecordingClient::new(mock)
+    }
+
+    async fn run(client: &dyn Client) -> (Vec<Row>, u64, Result<Vec<Row>>) {
+        let id: i64 = 1;
+        let rows = client
+            .fetch_rows("SELECT * FROM cars WHERE id = $1", &[&id])
+            .await
+            .unwrap();
+        let deleted = client
+            .execute("DELETE FROM cars WHERE name = $1", &[&civic()])
+            .await
+            .unwrap();
+        let failed = client.fetch_rows("SELECT * FROM missing", &[]).await;
+        (rows, deleted.rows_affected(), failed)
+    }
+
+    #[tokio::test]
+    async fn should_record_the_sql_params_and_results() {
+        let client = recorded_client();
+        let _ = run(&client).await;
+        client.inner().assert_all_consumed();
+
+        let recording = client.recording();
+        assert_eq!(recording.syntax, Syntax::Postgres);
+        let statements = &recording.statements;
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].sql, "SELECT * FROM cars WHERE id = $1");
+        assert_eq!(statements[0].params, vec![ParamValue::Int(1)]);
+        let column = |name: &str, type_name: &str, value| RecordedColumn {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            value,
+        };
+        let columns = vec![
+            column("id", "Int", ParamValue::Int(1)),
+            column("name", "Text", ParamValue::Text("civic".to_string())),
+        ];
+        let rows = vec![RecordedRow { columns }];
+        assert_eq!(statements[0].result, RecordedResult::Rows(rows));
+        assert_eq!(statements[1].params, vec![ParamValue::from("civic")]);
+        assert_eq!(statements[1].result, RecordedResult::RowsAffected(3));
+        let error = RecordedResult::Error("mock: no such table".to_string());
+        assert_eq!(statements[2].result, error);
+    }
+
+    #[tokio::test]
+    async fn should_replay_a_saved_recording() {
+        let client = recorded_client();
+        let _ = run(&client).await;
+        let path = std::env::temp_dir().join(format!("welds_replay_{}.json", std::process::id()));
+        client.save(&path).unwrap();
+
+        let replay = ReplayClient::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(replay.syntax(), Syntax::Postgres);
+        let (rows, deleted, failed) = run(&replay).await;
+        replay.assert_all_consumed();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<i64>("id").unwrap(), 1);
+        assert_eq!(rows[0].get::<String>("name").unwrap(), "civic");
+        assert_eq!(deleted, 3);
+        match failed {
+            Err(Error::Replay(message)) => assert!(message.contains("no such table")),
+            _ => panic!("expected the recorded error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_should_fail_when_the_sql_is_different() {
+        let client = recorded_client();
+        let _ = run(&client).await;
+        let replay = ReplayClient::new(client.recording());
+        let id: i64 = 1;
+        let result = replay.fetch_rows("SELECT * FROM cars", &[&id]).await;
+        assert!(matches!(result, Err(Error::Replay(_))));
+        assert_eq!(replay.remaining(), 3);
+    }
+
+    #[tokio::test]
+    async fn replay_should_fail_when_the_params_are_different() {
+        let client = recorded_client();
+        let _ = run(&client).await;
+        let replay = ReplayClient::new(client.recording());
+        let sql = "SELECT * FROM cars WHERE id = $1";
+        let id: i64 = 2;
+        let result = replay.fetch_rows(sql, &[&id]).await;
+        assert!(matches!(result, Err(Error::Replay(_))));
+        let result = replay.fetch_rows(sql, &[]).await;
+        assert!(matches!(result, Err(Error::Replay(_))));
+        assert_eq!(replay.remaining(), 3);
+    }
+
+    #[test]
+    fn unknown_params_should_match_anything() {
+        let recorded = vec![ParamValue::Unknown, ParamValue::Int(1)];
+        assert!(params_match(
+            &recorded,
+            &[ParamValue::Int(7), ParamValue::Int(1)]
+        ));
+        assert!(params_match(
+            &recorded,
+            &[ParamValue::Null, ParamValue::Unknown]
+        ));
+        assert!(!params_match(
+            &recorded,
+            &[ParamValue::Null, ParamValue::Int(2)]
+        ));
+        assert!(!params_match(&recorded, &[ParamValue::Null]));
+    }
+
+    #[tokio::test]
+    async fn should_record_statements_run_in_a_transaction() {
+        let sqlite = crate::sqlite::connect("sqlite::memory:").await.unwrap();
+        let client = RecordingClient::new(sqlite);
+        client
+            .execute("CREATE TABLE cars (name TEXT)", &[])
+            .await
+            .unwrap();
+        let trans = client.begin().await.unwrap();
+        let insert = "INSERT INTO cars (name) VALUES (?)";
+        trans.execute(insert, &[&civic()]).await.unwrap();
+        let rows = trans.fetch_rows("SELECT name FROM cars", &[]).await;
+        assert_eq!(rows.unwrap().len(), 1);
+        trans.commit().await.unwrap();
+
+        let statements = client.recording().statements;
+        let sql: Vec<&str> = statements.iter().map(|s| s.sql.as_str()).collect();
+        let expected = vec![
+            "CREATE TABLE cars (name TEXT)",
+            insert,
+            "SELECT name FROM cars",
+        ];
+        assert_eq!(sql, expected);
+        assert_eq!(statements[1].params, vec![ParamValue::from("civic")]);
+        assert_eq!(statements[1].result, RecordedResult::RowsAffected(1));
+
+        // and they replay in a transaction of the ReplayClient
+        let replay = ReplayClient::new(client.recording());
+        replay
+            .execute("CREATE TABLE cars (name TEXT)", &[])
+            .await
+            .unwrap();
+        let trans = replay.begin().await.unwrap();
+        assert_eq!(trans.syntax(), Syntax::Sqlite);
+        trans.execute(insert, &[&civic()]).await.unwrap();
+        let rows = trans.fetch_rows("SELECT name FROM cars", &[]).await;
+        assert_eq!(rows.unwrap()[0].get::<String>("name").unwrap(), "civic");
+        trans.commit().await.unwrap();
+        replay.assert_all_consumed();
+    }
+
+    #[tokio::test]
+    async fn should_replay_timestamps_and_uuids() {
+        use chrono::NaiveDateTime;
+        use uuid::Uuid;
+
+        let sqlite = crate::sqlite::connect("sqlite::memory:").await.unwrap();
+        let client = RecordingClient::new(sqlite);
+        let create = "CREATE TABLE cars (id UUID, made TIMESTAMP, sold TIMESTAMP)";
+        client.execute(create, &[]).await.unwrap();
+        let id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        let insert = format!("INSERT INTO cars VALUES ('{id}', '2024-01-31T13:45:00.25', NULL)");
+        client.execute(&insert, &[]).await.unwrap();
+        let select = "SELECT id, made, sold FROM cars";
+        client.fetch_rows(select, &[]).await.unwrap();
+
+        let statements = client.recording().statements;
+        let columns = match &statements[2].result {
+            RecordedResult::Rows(rows) => rows[0].columns.clone(),
+            _ => panic!("expected the rows to be recorded"),
+        };
+        assert_eq!(columns[0].value, ParamValue::from(id));
+        assert_eq!(columns[1].value, ParamValue::from("2024-01-31T13:45:00.25"));
+
+        let replay = ReplayClient::new(client.recording())
+            .parse_text::<NaiveDateTime>()
+            .parse_text::<Uuid>();
+        replay.execute(create, &[]).await.unwrap();
+        replay.execute(&insert, &[]).await.unwrap();
+        let rows = replay.fetch_rows(select, &[]).await.unwrap();
+        replay.assert_all_consumed();
+
+        let row = &rows[0];
+        let types: Vec<String> = columns.into_iter().map(|c| c.type_name).collect();
+        let replayed_types: Vec<String> = row.columns().into_iter().map(|c| c.type_name).collect();
+        assert_eq!(replayed_types, types);
+        assert_eq!(row.get::<Uuid>("id").unwrap(), Uuid::parse_str(id).unwrap());
+        let made: NaiveDateTime = "2024-01-31T13:45:00.25".parse().unwrap();
+        assert_eq!(row.get::<NaiveDateTime>("made").unwrap(), made);
+        assert_eq!(row.get::<Option<NaiveDateTime>>("sold").unwrap(), None);
+        // still readable as the text it was recorded as
+        assert_eq!(row.get::<String>("id").unwrap(), id);
+    }
+}
@@ -0,0 +1,237 @@
+use super::Row;
+use super::{Client, Param};
+use crate::errors::Result;
+use crate::{ExecuteResult, Fetch, Syntax, TransactStart, Transaction};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How a `ReplicaClient` picks which replica to send a read to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplicaStrategy {
+    /// Take turns sending reads to each replica
+    #[default]
+    RoundRobin,
+    /// Send reads to the replica with the fewest reads currently running
+    LeastBusy,
+}
+
+/// A client that routes SQL between a primary database and its read replicas.
+///
+/// - `fetch_rows` and `fetch_many` are sent to one of the replicas
+/// - `execute` is always sent to the primary
+/// - transactions are always started on the primary
+///
+/// If you need to read your own writes, use `primary()` to get the primary client
+/// and run those queries on it directly.
+///
+/// ```rust,ignore
+/// let primary = welds_connections::postgres::connect(primary_url).await?;
+/// let replica = welds_connections::postgres::connect(replica_url).await?;
+/// let client = ReplicaClient::new(primary, vec![replica])
+///     .with_strategy(ReplicaStrategy::LeastBusy);
+/// ```
+pub struct ReplicaClient<C> {
+    primary: C,
+    replicas: Vec<Replica<C>>,
+    strategy: ReplicaStrategy,
+    round_robin_next: AtomicUsize,
+}
+
+struct Replica<C> {
+    client: C,
+    in_flight: AtomicUsize,
+}
+
+impl<C> ReplicaClient<C>
+where
+    C: Client + TransactStart,
+{
+    /// Create a new client from a primary and a list of its replicas.
+    /// If no replicas are given, all reads are sent to the primary
+    pub fn new(primary: C, replicas: Vec<C>) -> Self {
+        let replicas = replicas
+            .into_iter()
+            .map(|client| Replica {
+                client,
+                in_flight: AtomicUsize::new(0),
+            })
+            .collect();
+        Self {
+            primary,
+            replicas,
+            strategy: ReplicaStrategy::default(),
+            round_robin_next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Change how reads are balanced across the replicas
+    pub fn with_strategy(mut self, strategy: ReplicaStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns the client for the primary database.
+    /// Use this to force reads to the primary (read-your-writes)
+    pub fn primary(&self) -> &C {
+        &self.primary
+    }
+
+    /// Returns the clients for each of the read replicas
+    pub fn replicas(&self) -> impl Iterator<Item = &C> {
+        self.replicas.iter().map(|r| &r.client)
+    }
+
+    /// Picks the replica the next read should be sent to
+    fn next_replica(&self) -> Option<&Replica<C>> {
+        if self.replicas.is_empty() {
+            return None;
+        }
+        let index = match self.strategy {
+            ReplicaStrategy::RoundRobin => {
+                self.round_robin_next.fetch_add(1, Ordering::Relaxed) % self.replicas.len()
+            }
+            ReplicaStrategy::LeastBusy => {
+                // start looking at a different replica each time so ties are spread out
+                let start =
+                    self.round_robin_next.fetch_add(1, Ordering::Relaxed) % self.replicas.len();
+                (0..self.replicas.len())
+                    .map(|offset| (start + offset) % self.replicas.len())
+                    .min_by_key(|i| self.replicas[*i].in_flight.load(Ordering::Relaxed))
+                    .unwrap_or(start)
+            }
+        };
+        self.replicas.get(index)
+    }
+}
+
+/// Tracks a read that is running on a replica.
+/// The replica is marked as less busy when this is dropped
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl<'a> InFlight<'a> {
+    fn start(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl<C> Client for ReplicaClient<C>
+where
+    C: Client + TransactStart,
+{
+    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+        self.primary.execute(sql, params).await
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        match self.next_replica() {
+            None => self.primary.fetch_rows(sql, params).await,
+            Some(replica) => {
+                let _in_flight = InFlight::start(&replica.in_flight);
+                replica.client.fetch_rows(sql, params).await
+            }
+        }
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        args: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        match self.next_replica() {
+            None => self.primary.fetch_many(args).await,
+            Some(replica) => {
+                let _in_flight = InFlight::start(&replica.in_flight);
+                replica.client.fetch_many(args).await
+            }
+        }
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.primary.syntax()
+    }
+}
+
+#[async_trait]
+impl<C> TransactStart for ReplicaClient<C>
+where
+    C: Client + TransactStart,
+{
+    async fn begin<'t>(&'t self) -> Result<Transaction<'t>> {
+        self.primary.begin().await
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::sqlite::{connect, SqliteClient};
+
+    // each database knows its own name, so we can tell where a statement was sent
+    async fn named(name: &str) -> SqliteClient {
+        let client = connect("sqlite::memory:").await.unwrap();
+        let create = "CREATE TABLE whoami (name TEXT NOT NULL)";
+        client.execute(create, &[]).await.unwrap();
+        let insert = format!("INSERT INTO whoami (name) VALUES ('{name}')");
+        client.execute(&insert, &[]).await.unwrap();
+        client
+    }
+
+    async fn replicated(strategy: ReplicaStrategy) -> ReplicaClient<SqliteClient> {
+        let replicas = vec![named("r0").await, named("r1").await];
+        ReplicaClient::new(named("primary").await, replicas).with_strategy(strategy)
+    }
+
+    async fn whoami(client: &dyn Client) -> String {
+        let rows = client
+            .fetch_rows("SELECT name FROM whoami", &[])
+            .await
+            .unwrap();
+        rows[0].get("name").unwrap()
+    }
+
+    #[tokio::test]
+    async fn round_robin_should_take_turns() {
+        let client = replicated(ReplicaStrategy::RoundRobin).await;
+        assert_eq!(whoami(&client).await, "r0");
+        assert_eq!(whoami(&client).await, "r1");
+        assert_eq!(whoami(&client).await, "r0");
+    }
+
+    #[tokio::test]
+    async fn least_busy_should_skip_busy_replicas() {
+        let client = replicated(ReplicaStrategy::LeastBusy).await;
+        let busy = InFlight::start(&client.replicas[0].in_flight);
+        assert_eq!(whoami(&client).await, "r1");
+        assert_eq!(whoami(&client).await, "r1");
+        drop(busy);
+        assert_eq!(client.replicas[0].in_flight.load(Ordering::Relaxed), 0);
+        assert_eq!(client.replicas[1].in_flight.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn writes_and_primary_should_go_to_the_primary() {
+        let client = replicated(ReplicaStrategy::RoundRobin).await;
+        let sql = "UPDATE whoami SET name = name || '!'";
+        client.execute(sql, &[]).await.unwrap();
+        assert_eq!(whoami(client.primary()).await, "primary!");
+        let replicas: Vec<&SqliteClient> = client.replicas().collect();
+        assert_eq!(whoami(replicas[0]).await, "r0");
+
+        let trans = client.begin().await.unwrap();
+        assert_eq!(whoami(&trans).await, "primary!");
+        trans.rollback().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn no_replicas_should_read_from_the_primary() {
+        let client = ReplicaClient::new(named("primary").await, vec![]);
+        assert_eq!(whoami(&client).await, "primary");
+    }
+}
@@ -0,0 +1,391 @@
+use super::value::Value;
+use super::Row;
+use crate::errors::{Error, Result};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+impl Row {
+    /// Builds any type that implements `serde::Deserialize` from this row.
+    ///
+    /// Structs and maps are filled in by column name, tuples and sequences by position.
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// struct Total { name: String, total: i64 }
+    /// let total: Total = row.deserialize()?;
+    /// let json: serde_json::Value = row.deserialize()?;
+    /// ```
+    ///
+    /// Dates, times, uuids, decimals and json are read as text (see `Row::value_at`),
+    /// so they can be read into a `String` or any type that deserializes from one (chrono, uuid, ...).
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        T::deserialize(RowDeserializer { row: self })
+    }
+}
+
+struct RowDeserializer<'r> {
+    row: &'r Row,
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(Columns::new(self.row))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Columns::new(self.row))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct enum identifier ignored_any
+    }
+}
+
+/// Walks the columns of a row, for both maps and sequences
+struct Columns<'r> {
+    row: &'r Row,
+    index: usize,
+}
+
+impl<'r> Columns<'r> {
+    fn new(row: &'r Row) -> Self {
+        Self { row, index: 0 }
+    }
+
+    fn next_column(&mut self) -> Option<&'r str> {
+        let name = self.row.column_name(self.index)?;
+        self.index += 1;
+        Some(name)
+    }
+
+    /// Deserializes the column that was just moved past
+    fn read<'de, T: DeserializeSeed<'de>>(&self, seed: T) -> Result<T::Value> {
+        let index = self.index - 1;
+        let name = self.row.column_name(index).unwrap_or_default();
+        let value = self.row.value_at(index)?;
+        seed.deserialize(ValueDeserializer { value })
+            .map_err(|err| Error::Deserialize(format!("column {name}: {err}")))
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Columns<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.next_column() {
+            Some(name) => seed.deserialize(name.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        self.read(seed)
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Columns<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.next_column() {
+            Some(_) => self.read(seed).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Deserializes the value of a single column
+struct ValueDeserializer {
+    value: Value,
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            Value::Bool(x) => visitor.visit_bool(x),
+            Value::Int(x) => visitor.visit_i64(x),
+            Value::UInt(x) => visitor.visit_u64(x),
+            Value::Float(x) => visitor.visit_f64(x),
+            Value::Text(x) => visitor.visit_string(x),
+            Value::Bytes(x) => visitor.visit_byte_buf(x),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    // Some databases (sqlite, mysql) store bools as numbers
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::Int(x) => visitor.visit_bool(x != 0),
+            Value::UInt(x) => visitor.visit_bool(x != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::Bytes(x) => match String::from_utf8(x) {
+                Ok(x) => visitor.visit_string(x),
+                Err(_) => Err(de::Error::custom("the bytes are not valid utf8")),
+            },
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // enums are read from their name stored as text
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.value {
+            Value::Text(x) => visitor.visit_enum(x.into_deserializer()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(all(test, feature = "noop"))]
+mod tests {
+    use super::*;
+    use crate::noop::ParamValue;
+    use crate::row::MockRow;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Car {
+        id: i64,
+        name: String,
+        nickname: Option<String>,
+        sold: bool,
+        price: f64,
+    }
+
+    fn car_row() -> Row {
+        let row = MockRow::new()
+            .column("id", 1_i32)
+            .column("name", "civic".to_string())
+            .column("nickname", None::<String>)
+            .column("sold", true)
+            .column("price", 9.5_f64);
+        Row::from(row)
+    }
+
+    #[test]
+    fn should_fill_in_a_struct_by_column_name() {
+        let car: Car = car_row().deserialize().unwrap();
+        let expected = Car {
+            id: 1,
+            name: "civic".to_string(),
+            nickname: None,
+            sold: true,
+            price: 9.5,
+        };
+        assert_eq!(car, expected);
+    }
+
+    #[test]
+    fn should_read_each_kind_of_value() {
+        #[derive(Debug, Deserialize)]
+        struct Kinds {
+            null: Option<i64>,
+            some: Option<i64>,
+            bool: bool,
+            int: i64,
+            uint: u64,
+            float: f64,
+            text: String,
+            bytes: String,
+        }
+        let row = MockRow::new()
+            .value("null", ParamValue::Null)
+            .value("some", ParamValue::Int(4))
+            .value("bool", ParamValue::Bool(true))
+            .value("int", ParamValue::Int(-3))
+            .column("uint", 7_u32)
+            .value("float", ParamValue::Float(1.5))
+            .value("text", ParamValue::Text("hi".to_string()))
+            .value("bytes", ParamValue::Bytes(b"utf8".to_vec()));
+        let kinds: Kinds = Row::from(row).deserialize().unwrap();
+        assert_eq!(kinds.null, None);
+        assert_eq!(kinds.some, Some(4));
+        assert!(kinds.bool);
+        assert_eq!(kinds.int, -3);
+        assert_eq!(kinds.uint, 7);
+        assert_eq!(kinds.float, 1.5);
+        assert_eq!(kinds.text, "hi");
+        assert_eq!(kinds.bytes, "utf8");
+    }
+
+    #[test]
+    fn should_read_bools_stored_as_numbers() {
+        #[derive(Deserialize)]
+        struct Flags {
+            on: bool,
+            off: bool,
+        }
+        let row = MockRow::new()
+            .value("on", ParamValue::Int(1))
+            .value("off", ParamValue::Int(0));
+        let flags: Flags = Row::from(row).deserialize().unwrap();
+        assert!(flags.on);
+        assert!(!flags.off);
+    }
+
+    #[test]
+    fn should_read_enums_from_text() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Color {
+            Red,
+            Blue,
+        }
+        #[derive(Deserialize)]
+        struct Paint {
+            color: Color,
+        }
+        let row = MockRow::new().value("color", ParamValue::Text("Blue".to_string()));
+        let paint: Paint = Row::from(row).deserialize().unwrap();
+        assert_eq!(paint.color, Color::Blue);
+    }
+
+    #[test]
+    fn should_fill_in_tuples_by_position() {
+        let (id, name): (i64, String) = {
+            let row = MockRow::new()
+                .column("a", 2_i64)
+                .column("b", "accord".to_string());
+            Row::from(row).deserialize().unwrap()
+        };
+        assert_eq!(id, 2);
+        assert_eq!(name, "accord");
+    }
+
+    #[test]
+    fn should_error_with_the_column_when_the_type_does_not_match() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Wrong {
+            name: i64,
+        }
+        let row = MockRow::new().column("name", "civic".to_string());
+        let err = Row::from(row).deserialize::<Wrong>().unwrap_err();
+        match err {
+            Error::Deserialize(message) => assert!(message.contains("column name"), "{message}"),
+            other => panic!("expected a deserialize error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_error_when_null_is_read_into_a_non_option() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Required {
+            name: String,
+        }
+        let row = MockRow::new().value("name", ParamValue::Null);
+        let result = Row::from(row).deserialize::<Required>();
+        assert!(matches!(result, Err(Error::Deserialize(_))));
+    }
+
+    #[test]
+    fn should_error_when_a_column_is_missing() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Wider {
+            id: i64,
+            color: String,
+        }
+        let result = car_row().deserialize::<Wider>();
+        assert!(matches!(result, Err(Error::Deserialize(_))));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn should_read_dates_decimals_and_json_from_sqlite_as_text() {
+        use crate::Client;
+        #[derive(Debug, Deserialize)]
+        struct Order {
+            placed: String,
+            total: f64,
+            data: String,
+            shipped: Option<String>,
+        }
+        let client = crate::sqlite::connect("sqlite::memory:").await.unwrap();
+        let create =
+            "CREATE TABLE orders (placed DATETIME, total DECIMAL(10, 2), data JSON, shipped DATE)";
+        client.execute(create, &[]).await.unwrap();
+        let insert = "INSERT INTO orders VALUES ('2024-01-31 13:45:00', 12.5, '{\"a\":1}', NULL)";
+        client.execute(insert, &[]).await.unwrap();
+        let rows = client
+            .fetch_rows("SELECT * FROM orders", &[])
+            .await
+            .unwrap();
+        let order: Order = rows[0].deserialize().unwrap();
+        assert_eq!(order.placed, "2024-01-31 13:45:00");
+        assert_eq!(order.total, 12.5);
+        assert_eq!(order.data, "{\"a\":1}");
+        assert_eq!(order.shipped, None);
+    }
+}
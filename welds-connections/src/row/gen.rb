@@ -12,7 +12,7 @@ def blocky(cfg, wheres)
 #{cfg}
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
-      where T: #{wheres}
+      where T: #{wheres} + RowValue
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -23,11 +23,13 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
-      where T: #{wheres}
+      where T: #{wheres} + RowValue
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -38,6 +40,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -0,0 +1,305 @@
+use crate::errors::Error;
+use crate::errors::Result;
+use crate::noop::ParamValue;
+use crate::row::value::Value;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// A row of canned data, built by hand for testing. No database is needed.
+///
+/// Values added with `column` are read back out as the exact type they were added as.
+/// A column added as an `i32` can NOT be read as an `i64`,
+/// and a nullable column should be added as an `Option<T>`.
+///
+/// Values added with `value` are converted to whatever type they are read as
+/// (numbers, bool, String, `Vec<u8>` and Options of those).
+/// Text can also be read as any type registered with `parse_text`, such as a date or uuid.
+///
+/// ```rust,ignore
+/// let row = MockRow::new()
+///     .column("id", 1_i32)
+///     .column("name", "Bobby".to_string())
+///     .column("nickname", None::<String>)
+///     .value("age", ParamValue::Int(42));
+/// ```
+#[derive(Clone, Default)]
+pub struct MockRow {
+    cells: Vec<MockCell>,
+    parsers: HashMap<TypeId, TextParser>,
+}
+
+/// Reads a text value into one of the types registered with `parse_text`
+pub(crate) type TextParser = Arc<dyn Fn(&ParamValue) -> Option<Box<dyn Any>> + Send + Sync>;
+
+#[derive(Clone)]
+struct MockCell {
+    name: String,
+    data: MockData,
+}
+
+#[derive(Clone)]
+enum MockData {
+    Typed {
+        type_name: &'static str,
+        make: Arc<dyn Fn() -> Box<dyn Any> + Send + Sync>,
+    },
+    Value {
+        type_name: Option<String>,
+        value: ParamValue,
+    },
+}
+
+impl MockRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a column with a value to the end of the row
+    pub fn column<T>(mut self, name: impl Into<String>, value: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.cells.push(MockCell {
+            name: name.into(),
+            data: MockData::Typed {
+                type_name: std::any::type_name::<T>(),
+                make: Arc::new(move || Box::new(value.clone())),
+            },
+        });
+        self
+    }
+
+    /// Adds a column with a loosely typed value to the end of the row.
+    /// The value is converted to the type it is read as.
+    pub fn value(mut self, name: impl Into<String>, value: ParamValue) -> Self {
+        self.cells.push(MockCell {
+            name: name.into(),
+            data: MockData::Value {
+                type_name: None,
+                value,
+            },
+        });
+        self
+    }
+
+    /// Adds a column with a loosely typed value, and the database type it was read from
+    pub fn value_of_type(
+        mut self,
+        name: impl Into<String>,
+        type_name: impl Into<String>,
+        value: ParamValue,
+    ) -> Self {
+        self.cells.push(MockCell {
+            name: name.into(),
+            data: MockData::Value {
+                type_name: Some(type_name.into()),
+                value,
+            },
+        });
+        self
+    }
+
+    /// Text values of this row can be read as a `T` (or `Option<T>`), by parsing them.
+    ///
+    /// Dates, times, uuids, decimals and json are read from the database as text.
+    /// Register the types your models use for them (chrono, uuid, rust_decimal, ...) to read them back.
+    pub fn parse_text<T>(mut self) -> Self
+    where
+        T: FromStr + 'static,
+    {
+        let parse: TextParser = Arc::new(|value| match value {
+            ParamValue::Text(text) => Some(Box::new(text.parse::<T>().ok()?)),
+            _ => None,
+        });
+        let parse_option: TextParser = Arc::new(|value| match value {
+            ParamValue::Null => Some(Box::new(None::<T>)),
+            ParamValue::Text(text) => Some(Box::new(Some(text.parse::<T>().ok()?))),
+            _ => None,
+        });
+        self.parsers.insert(TypeId::of::<T>(), parse);
+        self.parsers.insert(TypeId::of::<Option<T>>(), parse_option);
+        self
+    }
+
+    /// Returns the names of the columns in this row
+    pub fn column_names(&self) -> Vec<&str> {
+        self.cells.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    /// Returns the name of the type of the value at this position
+    pub(crate) fn type_name_at(&self, idx: usize) -> Option<&str> {
+        match &self.cells.get(idx)?.data {
+            MockData::Typed { type_name, .. } => Some(type_name),
+            MockData::Value {
+                type_name: Some(type_name),
+                ..
+            } => Some(type_name),
+            MockData::Value { value, .. } => Some(value.kind()),
+        }
+    }
+
+    /// Returns the value at this position, if it is one of the supported types
+    pub(crate) fn dyn_value_at(&self, idx: usize) -> Option<Value> {
+        match &self.cells.get(idx)?.data {
+            MockData::Value { value, .. } => Value::from_param_value(value),
+            MockData::Typed { make, .. } => typed_value(make()),
+        }
+    }
+
+    pub fn try_get<T: 'static>(&self, name: &str) -> Result<T> {
+        match self.cells.iter().find(|c| c.name == name) {
+            Some(cell) => cell.read(&self.parsers),
+            None => Err(Error::ColumnNotFound(name.to_owned())),
+        }
+    }
+
+    pub fn try_get_by_position<T: 'static>(&self, idx: usize) -> Result<T> {
+        match self.cells.get(idx) {
+            Some(cell) => cell.read(&self.parsers),
+            None => Err(Error::ColumnNotFound(idx.to_string())),
+        }
+    }
+}
+
+impl MockCell {
+    fn read<T: 'static>(&self, parsers: &HashMap<TypeId, TextParser>) -> Result<T> {
+        let (found, value) = match &self.data {
+            MockData::Typed { type_name, make } => (*type_name, make().downcast::<T>().ok()),
+            MockData::Value { value, .. } => (
+                "value",
+                convert::<T>(value).or_else(|| parse::<T>(value, parsers)),
+            ),
+        };
+        match value {
+            Some(value) => Ok(*value),
+            None => Err(Error::Mock(format!(
+                "column {} holds a {found}, it can not be read as a {}",
+                self.name,
+                std::any::type_name::<T>()
+            ))),
+        }
+    }
+}
+
+/// Converts a loosely typed value into T, if T is one of the supported types
+fn convert<T: 'static>(value: &ParamValue) -> Option<Box<T>> {
+    fn cast<A: 'static, B: 'static>(a: A) -> Option<Box<B>> {
+        let boxed: Box<dyn Any> = Box::new(a);
+        boxed.downcast::<B>().ok()
+    }
+
+    macro_rules! convert_to {
+        ($ty:ty, $read:expr) => {
+            if TypeId::of::<T>() == TypeId::of::<$ty>() {
+                let read: fn(&ParamValue) -> Option<$ty> = $read;
+                return read(value).and_then(cast);
+            }
+            if TypeId::of::<T>() == TypeId::of::<Option<$ty>>() {
+                let read: fn(&ParamValue) -> Option<$ty> = $read;
+                return match value {
+                    ParamValue::Null => cast(None::<$ty>),
+                    _ => read(value).map(Some).and_then(cast),
+                };
+            }
+        };
+    }
+
+    convert_to!(i8, |v| as_int(v).and_then(|x| x.try_into().ok()));
+    convert_to!(i16, |v| as_int(v).and_then(|x| x.try_into().ok()));
+    convert_to!(i32, |v| as_int(v).and_then(|x| x.try_into().ok()));
+    convert_to!(i64, as_int);
+    convert_to!(u8, |v| as_int(v).and_then(|x| x.try_into().ok()));
+    convert_to!(u16, |v| as_int(v).and_then(|x| x.try_into().ok()));
+    convert_to!(u32, |v| as_int(v).and_then(|x| x.try_into().ok()));
+    convert_to!(u64, |v| as_int(v).and_then(|x| x.try_into().ok()));
+    convert_to!(f32, |v| as_float(v).map(|x| x as f32));
+    convert_to!(f64, as_float);
+    convert_to!(bool, |v| match v {
+        ParamValue::Bool(x) => Some(*x),
+        ParamValue::Int(x) => Some(*x != 0),
+        _ => None,
+    });
+    convert_to!(String, |v| match v {
+        ParamValue::Text(x) => Some(x.clone()),
+        _ => None,
+    });
+    convert_to!(Vec<u8>, |v| match v {
+        ParamValue::Bytes(x) => Some(x.clone()),
+        ParamValue::Text(x) => Some(x.as_bytes().to_vec()),
+        _ => None,
+    });
+    None
+}
+
+/// Parses text into T with a parser registered with `parse_text`
+fn parse<T: 'static>(value: &ParamValue, parsers: &HashMap<TypeId, TextParser>) -> Option<Box<T>> {
+    let parse = parsers.get(&TypeId::of::<T>())?;
+    parse(value)?.downcast::<T>().ok()
+}
+
+fn as_int(value: &ParamValue) -> Option<i64> {
+    match value {
+        ParamValue::Int(x) => Some(*x),
+        ParamValue::Bool(x) => Some(*x as i64),
+        _ => None,
+    }
+}
+
+fn as_float(value: &ParamValue) -> Option<f64> {
+    match value {
+        ParamValue::Float(x) => Some(*x),
+        ParamValue::Int(x) => Some(*x as f64),
+        _ => None,
+    }
+}
+
+/// Reads a value added with `column`, if it is one of the supported types
+fn typed_value(any: Box<dyn Any>) -> Option<Value> {
+    macro_rules! read_as {
+        ($($ty:ty => $make:expr),* $(,)?) => {
+            $(
+                if let Some(x) = any.downcast_ref::<$ty>() {
+                    let make: fn($ty) -> Value = $make;
+                    return Some(make(x.clone()));
+                }
+                if let Some(x) = any.downcast_ref::<Option<$ty>>() {
+                    let make: fn($ty) -> Value = $make;
+                    return Some(x.clone().map(make).unwrap_or(Value::Null));
+                }
+            )*
+        };
+    }
+    read_as!(
+        i8 => |x| Value::Int(x as i64),
+        i16 => |x| Value::Int(x as i64),
+        i32 => |x| Value::Int(x as i64),
+        i64 => Value::Int,
+        u8 => |x| Value::UInt(x as u64),
+        u16 => |x| Value::UInt(x as u64),
+        u32 => |x| Value::UInt(x as u64),
+        u64 => Value::UInt,
+        f32 => |x| Value::Float(x as f64),
+        f64 => Value::Float,
+        bool => Value::Bool,
+        String => Value::Text,
+        Vec<u8> => Value::Bytes,
+    );
+    None
+}
+
+impl Value {
+    pub(crate) fn from_param_value(value: &ParamValue) -> Option<Value> {
+        Some(match value {
+            ParamValue::Null => Value::Null,
+            ParamValue::Bool(x) => Value::Bool(*x),
+            ParamValue::Int(x) => Value::Int(*x),
+            ParamValue::Float(x) => Value::Float(*x),
+            ParamValue::Text(x) => Value::Text(x.clone()),
+            ParamValue::Bytes(x) => Value::Bytes(x.clone()),
+            ParamValue::Other(x) => Value::Text(x.clone()),
+            ParamValue::Unknown => return None,
+        })
+    }
+}
@@ -22,7 +22,41 @@ use tiberius::Row as MssqlRow;
 #[cfg(feature = "mssql")]
 mod mssql_row_wrapper;
 #[cfg(feature = "mssql")]
-pub use mssql_row_wrapper::MssqlRowWrapper;
+pub use mssql_row_wrapper::{MssqlRowWrapper, TiberiusDecode};
+
+#[cfg(feature = "noop")]
+mod mock_row;
+#[cfg(feature = "noop")]
+pub use mock_row::MockRow;
+
+#[cfg(feature = "replay")]
+mod record;
+
+mod value;
+pub use value::{ColumnInfo, Value};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "mssql"))]
+pub(crate) mod text_value;
+
+#[cfg(feature = "serde")]
+mod de;
+
+/// A bound on the types read out of a `Row`.
+///
+/// With the `noop` feature, values of a `MockRow` are downcast to the type asked for,
+/// so they must be `'static`. Without it, every type meets this bound.
+#[cfg(feature = "noop")]
+pub trait RowValue: 'static {}
+#[cfg(feature = "noop")]
+impl<T: 'static> RowValue for T {}
+
+/// A bound on the types read out of a `Row`.
+///
+/// With the `noop` feature, values of a `MockRow` are downcast to the type asked for,
+/// so they must be `'static`. Without it, every type meets this bound.
+#[cfg(not(feature = "noop"))]
+pub trait RowValue {}
+#[cfg(not(feature = "noop"))]
+impl<T> RowValue for T {}
 
 /// all kinds of rows
 pub enum RowInner {
@@ -34,6 +68,8 @@ pub enum RowInner {
     Postgres(PgRow),
     #[cfg(feature = "mysql")]
     Mysql(MySqlRow),
+    #[cfg(feature = "noop")]
+    Mock(MockRow),
 }
 
 #[cfg(feature = "sqlite")]
@@ -72,6 +108,15 @@ impl From<MySqlRow> for Row {
     }
 }
 
+#[cfg(feature = "noop")]
+impl From<MockRow> for Row {
+    fn from(r: MockRow) -> Row {
+        Row {
+            inner: RowInner::Mock(r),
+        }
+    }
+}
+
 #[cfg(feature = "sqlite")]
 impl Row {
     pub fn as_sqlite_row(self) -> Option<SqliteRow> {
@@ -112,8 +157,5 @@ impl Row {
     }
 }
 
-#[cfg(feature = "mssql")]
-use mssql_row_wrapper::TiberiusDecode;
-
 // This code is scripted out cuz writing it for all the features to be to much
 mod row_gen;
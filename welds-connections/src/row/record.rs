@@ -0,0 +1,29 @@
+use super::*;
+use crate::errors::Error;
+use crate::replay::RecordedColumn;
+
+impl Row {
+    /// Reads all the columns of the row as loosely typed values, along with their database type.
+    /// Used to record rows so they can be replayed later.
+    ///
+    /// Dates, times, uuids, decimals and json are recorded as text.
+    /// Returns `Error::Replay` for a column that can't be read as a value.
+    pub(crate) fn recorded_values(&self) -> Result<Vec<RecordedColumn>> {
+        (0..self.len())
+            .map(|i| {
+                let name = self.column_name(i).unwrap_or_default().to_string();
+                let type_name = self.column_type(i).unwrap_or_default();
+                let value = self.value_at(i).map_err(|_| {
+                    Error::Replay(format!(
+                        "unsupported column type: {name} is a {type_name}, which can't be recorded"
+                    ))
+                })?;
+                Ok(RecordedColumn {
+                    name,
+                    type_name,
+                    value: value.into(),
+                })
+            })
+            .collect()
+    }
+}
@@ -12,7 +12,7 @@ use sqlx::Row as SqlxRow;
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite>,
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -23,12 +23,14 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite>,
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -39,6 +41,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -52,7 +56,7 @@ impl Row {
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres>,
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -63,12 +67,14 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres>,
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -79,6 +85,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -92,7 +100,7 @@ impl Row {
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql>,
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -103,12 +111,14 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql>,
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -119,6 +129,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -132,7 +144,7 @@ impl Row {
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: TiberiusDecode,
+        T: TiberiusDecode + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -143,12 +155,14 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: TiberiusDecode,
+        T: TiberiusDecode + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -159,6 +173,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -175,7 +191,8 @@ impl Row {
         T: for<'r> Decode<'r, sqlx::Sqlite>
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::Postgres>
-            + Type<sqlx::Postgres>,
+            + Type<sqlx::Postgres>
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -186,6 +203,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -194,7 +213,8 @@ impl Row {
         T: for<'r> Decode<'r, sqlx::Sqlite>
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::Postgres>
-            + Type<sqlx::Postgres>,
+            + Type<sqlx::Postgres>
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -205,6 +225,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -221,7 +243,8 @@ impl Row {
         T: for<'r> Decode<'r, sqlx::Sqlite>
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -232,6 +255,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -240,7 +265,8 @@ impl Row {
         T: for<'r> Decode<'r, sqlx::Sqlite>
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -251,6 +277,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -264,7 +292,7 @@ impl Row {
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -275,12 +303,14 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Sqlite> + Type<sqlx::Sqlite> + TiberiusDecode + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -291,6 +321,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -307,7 +339,8 @@ impl Row {
         T: for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -318,6 +351,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -326,7 +361,8 @@ impl Row {
         T: for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -337,6 +373,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -350,7 +388,7 @@ impl Row {
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -361,12 +399,14 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::Postgres> + Type<sqlx::Postgres> + TiberiusDecode + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -377,6 +417,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -390,7 +432,7 @@ impl Row {
 impl Row {
     pub fn get<T>(&self, name: &str) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -401,12 +443,14 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
     pub fn get_by_position<T>(&self, index: usize) -> Result<T>
     where
-        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode,
+        T: for<'r> Decode<'r, sqlx::MySql> + Type<sqlx::MySql> + TiberiusDecode + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -417,6 +461,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -435,7 +481,8 @@ impl Row {
             + for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -446,6 +493,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -456,7 +505,8 @@ impl Row {
             + for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
-            + Type<sqlx::MySql>,
+            + Type<sqlx::MySql>
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -467,6 +517,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -484,7 +536,8 @@ impl Row {
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -495,6 +548,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -504,7 +559,8 @@ impl Row {
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::Postgres>
             + Type<sqlx::Postgres>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -515,6 +571,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -532,7 +590,8 @@ impl Row {
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -543,6 +602,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -552,7 +613,8 @@ impl Row {
             + Type<sqlx::Sqlite>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -563,6 +625,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -580,7 +644,8 @@ impl Row {
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -591,6 +656,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -600,7 +667,8 @@ impl Row {
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -611,6 +679,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
@@ -630,7 +700,8 @@ impl Row {
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -641,6 +712,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(name)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(name)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get(name),
         }
     }
 
@@ -652,7 +725,8 @@ impl Row {
             + Type<sqlx::Postgres>
             + for<'r> Decode<'r, sqlx::MySql>
             + Type<sqlx::MySql>
-            + TiberiusDecode,
+            + TiberiusDecode
+            + RowValue,
     {
         match &self.inner {
             #[cfg(feature = "sqlite")]
@@ -663,6 +737,8 @@ impl Row {
             RowInner::Postgres(r) => Ok(r.try_get(index)?),
             #[cfg(feature = "mysql")]
             RowInner::Mysql(r) => Ok(r.try_get(index)?),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.try_get_by_position(index),
         }
     }
 }
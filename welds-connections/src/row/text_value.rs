@@ -0,0 +1,449 @@
+// ******************************************************************************
+// Dates, times, uuids, decimals and json don't have a `Value` of their own.
+// They are read as `Value::Text`, written in a standard format so they can be
+// parsed into whatever type the caller uses (chrono, time, uuid, rust_decimal, ...)
+//
+// date:      2024-01-31
+// time:      13:45:00.25
+// datetime:  2024-01-31T13:45:00.25
+// with zone: 2024-01-31T13:45:00.25+00:00
+// uuid:      67e55044-10b1-426f-9247-bb680e5fe0c8
+// decimal:   -12.3400 (all the digits of the scale are kept)
+// json:      the json text
+// ******************************************************************************
+
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// days from 1970-01-01 to 2000-01-01 (postgres dates start here)
+#[cfg(feature = "postgres")]
+const DAYS_TO_2000: i64 = 10_957;
+
+/// days from 0001-01-01 to 1970-01-01 (mssql date)
+#[cfg(feature = "mssql")]
+const DAYS_FROM_YEAR_1: i64 = 719_162;
+
+/// days from 1900-01-01 to 1970-01-01 (mssql datetime)
+#[cfg(feature = "mssql")]
+const DAYS_FROM_1900: i64 = 25_567;
+
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+/// The year, month and day of a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+/// A date from the number of days since 1970-01-01
+pub(crate) fn date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// A time of day from the nanoseconds since midnight
+pub(crate) fn time(nanos: i64) -> String {
+    let secs = nanos / 1_000_000_000;
+    let fraction = nanos % 1_000_000_000;
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    let text = format!("{h:02}:{m:02}:{s:02}");
+    if fraction == 0 {
+        return text;
+    }
+    let fraction = format!("{fraction:09}");
+    format!("{text}.{}", fraction.trim_end_matches('0'))
+}
+
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+/// A date and time from the nanoseconds since 1970-01-01
+pub(crate) fn datetime(nanos: i128) -> String {
+    let days = nanos.div_euclid(NANOS_PER_DAY as i128) as i64;
+    let nanos = nanos.rem_euclid(NANOS_PER_DAY as i128) as i64;
+    format!("{}T{}", date(days), time(nanos))
+}
+
+/// The offset from UTC, written as +HH:MM
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+pub(crate) fn offset(minutes: i64) -> String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let minutes = minutes.abs();
+    format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+#[cfg(feature = "postgres")]
+pub(crate) fn uuid(bytes: &[u8]) -> Option<String> {
+    if bytes.len() != 16 {
+        return None;
+    }
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    ))
+}
+
+/// Reads the binary format postgres sends a column as.
+/// Returns None for types that are not a date, time, uuid, decimal or json
+#[cfg(feature = "postgres")]
+pub(crate) fn postgres(type_name: &str, bytes: &[u8]) -> Option<String> {
+    let int = |bytes: &[u8]| -> Option<i64> {
+        match bytes.len() {
+            4 => Some(i32::from_be_bytes(bytes.try_into().ok()?) as i64),
+            8 => Some(i64::from_be_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        }
+    };
+    let infinity = |x: i64| match x {
+        i64::MAX | 0x7FFF_FFFF => Some("infinity".to_string()),
+        i64::MIN | -0x8000_0000 => Some("-infinity".to_string()),
+        _ => None,
+    };
+    // microseconds since 2000-01-01
+    let timestamp = |bytes: &[u8]| -> Option<String> {
+        let micros = int(bytes)?;
+        let since_2000 = micros as i128 * 1000;
+        let epoch = DAYS_TO_2000 as i128 * NANOS_PER_DAY as i128;
+        Some(infinity(micros).unwrap_or_else(|| datetime(epoch + since_2000)))
+    };
+
+    match type_name {
+        "UUID" => uuid(bytes),
+        "DATE" => {
+            let days = int(bytes)?;
+            Some(infinity(days).unwrap_or_else(|| date(DAYS_TO_2000 + days)))
+        }
+        "TIME" => Some(time(int(bytes)? * 1000)),
+        "TIMESTAMP" => timestamp(bytes),
+        "TIMESTAMPTZ" => Some(format!("{}{}", timestamp(bytes)?, offset(0))),
+        "NUMERIC" => postgres_numeric(bytes),
+        "JSON" => String::from_utf8(bytes.to_vec()).ok(),
+        // jsonb starts with a version number
+        "JSONB" => String::from_utf8(bytes.get(1..)?.to_vec()).ok(),
+        _ => None,
+    }
+}
+
+/// postgres sends numerics as a list of base 10000 digits
+#[cfg(feature = "postgres")]
+fn postgres_numeric(bytes: &[u8]) -> Option<String> {
+    let word = |i: usize| -> Option<i16> {
+        let pair = bytes.get(i * 2..i * 2 + 2)?;
+        Some(i16::from_be_bytes([pair[0], pair[1]]))
+    };
+    let ndigits = word(0)? as usize;
+    let weight = word(1)? as i64;
+    let sign = word(2)? as u16;
+    let scale = word(3)? as usize;
+    match sign {
+        0xC000 => return Some("NaN".to_string()),
+        0xD000 => return Some("Infinity".to_string()),
+        0xF000 => return Some("-Infinity".to_string()),
+        _ => {}
+    }
+    let digits: Option<Vec<i16>> = (0..ndigits).map(|i| word(4 + i)).collect();
+    let digits = digits?;
+    // the digit at this position, 0 is the ones group, -1 the first group after the point
+    let at = |position: i64| -> i16 {
+        match usize::try_from(weight - position) {
+            Ok(i) => digits.get(i).copied().unwrap_or_default(),
+            Err(_) => 0,
+        }
+    };
+
+    let mut text = String::default();
+    if sign == 0x4000 {
+        text.push('-');
+    }
+    if weight < 0 {
+        text.push('0');
+    }
+    for position in (0..=weight).rev() {
+        match position == weight {
+            true => text.push_str(&at(position).to_string()),
+            false => text.push_str(&format!("{:04}", at(position))),
+        }
+    }
+    if scale > 0 {
+        let groups = scale.div_ceil(4) as i64;
+        let fraction: String = (1..=groups)
+            .map(|group| format!("{:04}", at(-group)))
+            .collect();
+        text.push('.');
+        text.push_str(&fraction[..scale]);
+    }
+    Some(text)
+}
+
+/// Reads the format mysql sends a column as.
+/// Returns None for types that are not a date, time, decimal or json
+#[cfg(feature = "mysql")]
+pub(crate) fn mysql(type_name: &str, bytes: &[u8]) -> Option<String> {
+    let text = || String::from_utf8(bytes.to_vec()).ok();
+    // values are sent as text, unless the query was a prepared statement.
+    // Binary dates start with their length. Text dates start with a digit
+    let is_text = bytes.first().map(|b| b.is_ascii_digit() || *b == b'-');
+    match type_name {
+        "DECIMAL" | "JSON" => text(),
+        "DATE" | "DATETIME" | "TIMESTAMP" | "TIME" if is_text == Some(true) => text(),
+        "DATE" => Some(mysql_datetime(bytes)?.0),
+        "DATETIME" | "TIMESTAMP" => {
+            let (date, nanos) = mysql_datetime(bytes)?;
+            Some(format!("{date}T{}", time(nanos)))
+        }
+        "TIME" => mysql_time(bytes),
+        _ => None,
+    }
+}
+
+/// [len, year(2), month, day, hour, minute, second, micros(4)]
+#[cfg(feature = "mysql")]
+fn mysql_datetime(bytes: &[u8]) -> Option<(String, i64)> {
+    let len = *bytes.first()? as usize;
+    let bytes = bytes.get(1..1 + len)?;
+    if len == 0 {
+        return Some(("0000-00-00".to_string(), 0));
+    }
+    let year = u16::from_le_bytes([bytes[0], *bytes.get(1)?]);
+    let (month, day) = (*bytes.get(2)?, *bytes.get(3)?);
+    let date = format!("{year:04}-{month:02}-{day:02}");
+    let (h, m, s) = match len >= 7 {
+        true => (bytes[4] as i64, bytes[5] as i64, bytes[6] as i64),
+        false => (0, 0, 0),
+    };
+    let micros = match len >= 11 {
+        true => u32::from_le_bytes(bytes[7..11].try_into().ok()?) as i64,
+        false => 0,
+    };
+    let nanos = ((h * 60 + m) * 60 + s) * 1_000_000_000 + micros * 1000;
+    Some((date, nanos))
+}
+
+/// [len, is_negative, days(4), hour, minute, second, micros(4)]
+#[cfg(feature = "mysql")]
+fn mysql_time(bytes: &[u8]) -> Option<String> {
+    let len = *bytes.first()? as usize;
+    let bytes = bytes.get(1..1 + len)?;
+    if len == 0 {
+        return Some(time(0));
+    }
+    let negative = bytes[0] == 1;
+    let days = u32::from_le_bytes(bytes.get(1..5)?.try_into().ok()?) as i64;
+    let (h, m, s) = (*bytes.get(5)?, *bytes.get(6)?, *bytes.get(7)?);
+    let micros = match len >= 12 {
+        true => u32::from_le_bytes(bytes[8..12].try_into().ok()?) as i64,
+        false => 0,
+    };
+    let hours = days * 24 + h as i64;
+    let nanos = ((hours * 60 + m as i64) * 60 + s as i64) * 1_000_000_000 + micros * 1000;
+    let sign = if negative { "-" } else { "" };
+    Some(format!("{sign}{}", time(nanos)))
+}
+
+#[cfg(feature = "mssql")]
+pub(crate) fn mssql_date(days_from_year_1: i64) -> String {
+    date(days_from_year_1 - DAYS_FROM_YEAR_1)
+}
+
+/// datetime (1/300 of a second) and smalldatetime (minutes) count days from 1900-01-01
+#[cfg(feature = "mssql")]
+pub(crate) fn mssql_datetime(days_from_1900: i64, nanos: i64) -> String {
+    let days = days_from_1900 - DAYS_FROM_1900;
+    datetime(days as i128 * NANOS_PER_DAY as i128 + nanos as i128)
+}
+
+/// datetime2 and datetimeoffset count days from 0001-01-01, with an offset in minutes.
+/// The date and time of a datetimeoffset are in UTC
+#[cfg(feature = "mssql")]
+pub(crate) fn mssql_datetime2(
+    days_from_year_1: i64,
+    nanos: i64,
+    offset_minutes: Option<i64>,
+) -> String {
+    let days = days_from_year_1 - DAYS_FROM_YEAR_1;
+    let utc = days as i128 * NANOS_PER_DAY as i128 + nanos as i128;
+    match offset_minutes {
+        None => datetime(utc),
+        Some(minutes) => {
+            let local = utc + minutes as i128 * 60_000_000_000;
+            format!("{}{}", datetime(local), offset(minutes))
+        }
+    }
+}
+
+/// A decimal from its digits as a whole number, and how many of them are after the point
+#[cfg(feature = "mssql")]
+pub(crate) fn mssql_numeric(value: i128, scale: u8) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let scale = scale as usize;
+    let digits = format!("{:0width$}", value.unsigned_abs(), width = scale + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - scale);
+    match scale {
+        0 => format!("{sign}{whole}"),
+        _ => format!("{sign}{whole}.{fraction}"),
+    }
+}
+
+/// The nanoseconds of a count of 10^-scale second increments
+#[cfg(feature = "mssql")]
+pub(crate) fn mssql_time_nanos(increments: u64, scale: u8) -> i64 {
+    let scale = scale.min(9) as u32;
+    (increments * 10_u64.pow(9 - scale)) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "postgres", feature = "mssql"))]
+    #[test]
+    fn should_write_dates_and_times() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(19_753), "2024-01-31");
+        assert_eq!(date(-1), "1969-12-31");
+        assert_eq!(date(11_016), "2000-02-29");
+        assert_eq!(time(0), "00:00:00");
+        assert_eq!(time(49_500_250_000_000), "13:45:00.25");
+        assert_eq!(datetime(1_706_708_700_000_000_000), "2024-01-31T13:45:00");
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn should_write_uuids() {
+        let bytes = [
+            0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+            0xe0, 0xc8,
+        ];
+        assert_eq!(offset(-330), "-05:30");
+        assert_eq!(offset(0), "+00:00");
+        let text = uuid(&bytes).unwrap();
+        assert_eq!(text, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(uuid(&bytes[1..]), None);
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn should_read_postgres_dates_and_times() {
+        // 2024-01-31 is 8796 days after 2000-01-01
+        let days = 8796_i32.to_be_bytes();
+        assert_eq!(postgres("DATE", &days).unwrap(), "2024-01-31");
+        assert_eq!(
+            postgres("DATE", &i32::MAX.to_be_bytes()).unwrap(),
+            "infinity"
+        );
+        let micros = 8796_i64 * 86_400_000_000 + 49_500_250_000;
+        let micros = micros.to_be_bytes();
+        let expected = "2024-01-31T13:45:00.25";
+        assert_eq!(postgres("TIMESTAMP", &micros).unwrap(), expected);
+        let expected = "2024-01-31T13:45:00.25+00:00";
+        assert_eq!(postgres("TIMESTAMPTZ", &micros).unwrap(), expected);
+        let micros = 49_500_250_000_i64.to_be_bytes();
+        assert_eq!(postgres("TIME", &micros).unwrap(), "13:45:00.25");
+        // before 2000
+        let micros = (-86_400_000_000_i64).to_be_bytes();
+        assert_eq!(
+            postgres("TIMESTAMP", &micros).unwrap(),
+            "1999-12-31T00:00:00"
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    fn numeric(weight: i16, sign: u16, scale: u16, digits: &[i16]) -> Vec<u8> {
+        let mut bytes = Vec::default();
+        bytes.extend((digits.len() as i16).to_be_bytes());
+        bytes.extend(weight.to_be_bytes());
+        bytes.extend(sign.to_be_bytes());
+        bytes.extend(scale.to_be_bytes());
+        for digit in digits {
+            bytes.extend(digit.to_be_bytes());
+        }
+        bytes
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn should_read_postgres_numerics() {
+        let read = |bytes: Vec<u8>| postgres("NUMERIC", &bytes).unwrap();
+        assert_eq!(read(numeric(0, 0, 2, &[12, 3400])), "12.34");
+        assert_eq!(read(numeric(0, 0x4000, 4, &[12, 3400])), "-12.3400");
+        assert_eq!(read(numeric(1, 0, 0, &[1, 0])), "10000");
+        assert_eq!(read(numeric(1, 0, 0, &[1])), "10000");
+        assert_eq!(read(numeric(1, 0, 1, &[2, 30, 5000])), "20030.5");
+        assert_eq!(read(numeric(-1, 0, 3, &[50])), "0.005");
+        assert_eq!(read(numeric(-2, 0, 6, &[5000])), "0.000050");
+        assert_eq!(read(numeric(0, 0, 0, &[])), "0");
+        assert_eq!(read(numeric(0, 0xC000, 0, &[])), "NaN");
+        assert_eq!(postgres("NUMERIC", &[0, 1]), None);
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn should_read_postgres_json() {
+        let json = br#"{"a":1}"#;
+        assert_eq!(postgres("JSON", json).unwrap(), r#"{"a":1}"#);
+        let mut jsonb = vec![1];
+        jsonb.extend(json);
+        assert_eq!(postgres("JSONB", &jsonb).unwrap(), r#"{"a":1}"#);
+        assert_eq!(postgres("INT4", &[0, 0, 0, 1]), None);
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn should_read_mysql_dates_and_times() {
+        let year = 2024_u16.to_le_bytes();
+        let date = [4, year[0], year[1], 1, 31];
+        assert_eq!(mysql("DATE", &date).unwrap(), "2024-01-31");
+        let datetime = [7, year[0], year[1], 1, 31, 13, 45, 0];
+        let expected = "2024-01-31T13:45:00";
+        assert_eq!(mysql("DATETIME", &datetime).unwrap(), expected);
+        let micros = 250_000_u32.to_le_bytes();
+        let mut precise = vec![11, year[0], year[1], 1, 31, 13, 45, 0];
+        precise.extend(micros);
+        let expected = "2024-01-31T13:45:00.25";
+        assert_eq!(mysql("TIMESTAMP", &precise).unwrap(), expected);
+        let time = [8, 1, 1, 0, 0, 0, 2, 30, 0];
+        assert_eq!(mysql("TIME", &time).unwrap(), "-26:30:00");
+        // the text protocol is left as is
+        let text = b"2024-01-31 13:45:00";
+        assert_eq!(mysql("DATETIME", text).unwrap(), "2024-01-31 13:45:00");
+        assert_eq!(mysql("DECIMAL", b"-12.30").unwrap(), "-12.30");
+        assert_eq!(mysql("INT", b"1"), None);
+    }
+
+    #[cfg(feature = "mssql")]
+    #[test]
+    fn should_write_mssql_dates_and_times() {
+        // 2024-01-31
+        assert_eq!(mssql_date(738_915), "2024-01-31");
+        let nanos = mssql_time_nanos(4_950_025, 2);
+        assert_eq!(nanos, 49_500_250_000_000);
+        let expected = "2024-01-31T13:45:00.25";
+        assert_eq!(mssql_datetime2(738_915, nanos, None), expected);
+        let expected = "2024-01-31T15:45:00.25+02:00";
+        assert_eq!(mssql_datetime2(738_915, nanos, Some(120)), expected);
+        // datetime counts from 1900-01-01
+        let expected = "2024-01-31T00:00:00";
+        assert_eq!(mssql_datetime(45_320, 0), expected);
+    }
+
+    #[cfg(feature = "mssql")]
+    #[test]
+    fn should_write_mssql_numerics() {
+        assert_eq!(mssql_numeric(-123400, 4), "-12.3400");
+        assert_eq!(mssql_numeric(5, 3), "0.005");
+        assert_eq!(mssql_numeric(-5, 3), "-0.005");
+        assert_eq!(mssql_numeric(42, 0), "42");
+    }
+}
@@ -0,0 +1,446 @@
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "mssql"))]
+use super::text_value;
+use super::*;
+use crate::errors::Error;
+use std::fmt::Display;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use sqlx::{Column, Row as SqlxRow, TypeInfo, ValueRef};
+
+/// A value read out of a row without knowing its type ahead of time.
+/// The same for all databases.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+/// The name and database type of a column in a row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// The name the database uses for the type of this column. (`INT4`, `VARCHAR`, ...)
+    pub type_name: String,
+}
+
+impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(x) => Some(*x),
+            Value::Int(x) => Some(*x != 0),
+            Value::UInt(x) => Some(*x != 0),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(x) => Some(*x),
+            Value::UInt(x) => (*x).try_into().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float(x) => Some(*x),
+            Value::Int(x) => Some(*x as f64),
+            Value::UInt(x) => Some(*x as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Text(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(x) => Some(x),
+            Value::Text(x) => Some(x.as_bytes()),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the value as text. Null is written as an empty string, bytes as hex.
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => Ok(()),
+            Value::Bool(x) => write!(f, "{x}"),
+            Value::Int(x) => write!(f, "{x}"),
+            Value::UInt(x) => write!(f, "{x}"),
+            Value::Float(x) => write!(f, "{x}"),
+            Value::Text(x) => f.write_str(x),
+            Value::Bytes(x) => x.iter().try_for_each(|b| write!(f, "{b:02x}")),
+        }
+    }
+}
+
+// Reads a column out of a sqlx row as the first type it can be decoded as
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+macro_rules! read_sqlx {
+    ($row:expr, $index:expr, $($ty:ty => $make:expr),* $(,)?) => {{
+        let is_null = $row.try_get_raw($index).map(|v| v.is_null()).unwrap_or(true);
+        if is_null {
+            Some(Value::Null)
+        }
+        $( else if let Ok(v) = $row.try_get::<$ty, _>($index) {
+            Some($make(v))
+        } )*
+        else {
+            None
+        }
+    }};
+}
+
+impl Row {
+    /// The number of columns in this row
+    pub fn len(&self) -> usize {
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => r.columns().len(),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => r.columns().len(),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => r.columns().len(),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => r.cells().len(),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.column_names().len(),
+        }
+    }
+
+    /// Returns true if the row has no columns
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The name of the column at this position
+    pub fn column_name(&self, index: usize) -> Option<&str> {
+        if index >= self.len() {
+            return None;
+        }
+        match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => Some(r.column(index).name()),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => Some(r.column(index).name()),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => Some(r.column(index).name()),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => Some(r.cells()[index].column().name()),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.column_names().get(index).copied(),
+        }
+    }
+
+    /// The names of all the columns in this row, in order
+    pub fn column_names(&self) -> Vec<&str> {
+        (0..self.len())
+            .filter_map(|i| self.column_name(i))
+            .collect()
+    }
+
+    /// The name the database uses for the type of the column at this position
+    pub fn column_type(&self, index: usize) -> Option<String> {
+        if index >= self.len() {
+            return None;
+        }
+        let type_name = match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => r.column(index).type_info().name().to_string(),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => r.column(index).type_info().name().to_string(),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => r.column(index).type_info().name().to_string(),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => format!("{:?}", r.cells()[index].column().column_type()),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.type_name_at(index)?.to_string(),
+        };
+        Some(type_name)
+    }
+
+    /// The name and database type of all the columns in this row, in order
+    pub fn columns(&self) -> Vec<ColumnInfo> {
+        (0..self.len())
+            .map(|i| ColumnInfo {
+                name: self.column_name(i).unwrap_or_default().to_string(),
+                type_name: self.column_type(i).unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Reads the value at this position, whatever type it is.
+    ///
+    /// Dates, times, uuids, decimals and json are read as `Value::Text`,
+    /// in ISO 8601 (`2024-01-31T13:45:00.25`), hyphenated, plain decimal and json text.
+    /// Returns `Error::UnsupportedType` for any other type that isn't a number, bool, text or bytes.
+    pub fn value_at(&self, index: usize) -> Result<Value> {
+        let name = self
+            .column_name(index)
+            .ok_or_else(|| Error::ColumnNotFound(index.to_string()))?;
+        let value = match &self.inner {
+            #[cfg(feature = "sqlite")]
+            RowInner::Sqlite(r) => read_sqlx!(r, index,
+                i64 => Value::Int,
+                f64 => Value::Float,
+                String => Value::Text,
+                Vec<u8> => Value::Bytes,
+            ),
+            #[cfg(feature = "postgres")]
+            RowInner::Postgres(r) => read_sqlx!(r, index,
+                bool => Value::Bool,
+                i16 => |x: i16| Value::Int(x as i64),
+                i32 => |x: i32| Value::Int(x as i64),
+                i64 => Value::Int,
+                f32 => |x: f32| Value::Float(x as f64),
+                f64 => Value::Float,
+                String => Value::Text,
+                Vec<u8> => Value::Bytes,
+            )
+            .or_else(|| postgres_text(r, index)),
+            #[cfg(feature = "mysql")]
+            RowInner::Mysql(r) => read_sqlx!(r, index,
+                bool => Value::Bool,
+                i64 => Value::Int,
+                u64 => Value::UInt,
+                f64 => Value::Float,
+                f32 => |x: f32| Value::Float(x as f64),
+                String => Value::Text,
+                Vec<u8> => Value::Bytes,
+            )
+            .or_else(|| mysql_text(r, index)),
+            #[cfg(feature = "mssql")]
+            RowInner::Mssql(r) => Value::from_column_data(r.cells()[index].data()),
+            #[cfg(feature = "noop")]
+            RowInner::Mock(r) => r.dyn_value_at(index),
+        };
+        value.ok_or_else(|| Error::UnsupportedType(name.to_owned()))
+    }
+
+    /// Reads the value of the column with this name, whatever type it is
+    pub fn value(&self, name: &str) -> Result<Value> {
+        let index = (0..self.len())
+            .find(|i| self.column_name(*i) == Some(name))
+            .ok_or_else(|| Error::ColumnNotFound(name.to_owned()))?;
+        self.value_at(index)
+    }
+
+    /// Reads all the columns of the row, in order
+    pub fn values(&self) -> Result<Vec<(String, Value)>> {
+        (0..self.len())
+            .map(|i| {
+                let name = self.column_name(i).unwrap_or_default().to_string();
+                Ok((name, self.value_at(i)?))
+            })
+            .collect()
+    }
+}
+
+/// Reads a date, time, uuid, decimal or json column from postgres as text
+#[cfg(feature = "postgres")]
+fn postgres_text(row: &sqlx::postgres::PgRow, index: usize) -> Option<Value> {
+    use sqlx::postgres::PgValueFormat;
+    let raw = row.try_get_raw(index).ok()?;
+    let text = match raw.format() {
+        PgValueFormat::Text => raw.as_str().ok()?.to_string(),
+        PgValueFormat::Binary => {
+            let type_name = raw.type_info().name().to_string();
+            text_value::postgres(&type_name, raw.as_bytes().ok()?)?
+        }
+    };
+    Some(Value::Text(text))
+}
+
+/// Reads a date, time, decimal or json column from mysql as text
+#[cfg(feature = "mysql")]
+fn mysql_text(row: &sqlx::mysql::MySqlRow, index: usize) -> Option<Value> {
+    let type_name = row.column(index).type_info().name();
+    let bytes: Vec<u8> = row.try_get_unchecked(index).ok()?;
+    text_value::mysql(type_name, &bytes).map(Value::Text)
+}
+
+#[cfg(feature = "mssql")]
+impl Value {
+    pub(crate) fn from_column_data(data: &tiberius::ColumnData<'_>) -> Option<Value> {
+        use tiberius::ColumnData;
+        let value = match data {
+            ColumnData::U8(x) => x.map(|x| Value::Int(x as i64)),
+            ColumnData::I16(x) => x.map(|x| Value::Int(x as i64)),
+            ColumnData::I32(x) => x.map(|x| Value::Int(x as i64)),
+            ColumnData::I64(x) => x.map(Value::Int),
+            ColumnData::F32(x) => x.map(|x| Value::Float(x as f64)),
+            ColumnData::F64(x) => x.map(Value::Float),
+            ColumnData::Bit(x) => x.map(Value::Bool),
+            ColumnData::String(x) => x.as_ref().map(|x| Value::Text(x.to_string())),
+            ColumnData::Binary(x) => x.as_ref().map(|x| Value::Bytes(x.to_vec())),
+            // dates, times, uuids, decimals and xml are read as text
+            ColumnData::Guid(x) => x.map(|x| Value::Text(x.to_string())),
+            ColumnData::Numeric(x) => {
+                x.map(|x| Value::Text(text_value::mssql_numeric(x.value(), x.scale())))
+            }
+            ColumnData::Xml(x) => x.as_ref().map(|x| Value::Text(x.to_string())),
+            ColumnData::DateTime(x) => x.map(|x| {
+                let nanos = x.seconds_fragments() as i64 * 1_000_000_000 / 300;
+                Value::Text(text_value::mssql_datetime(x.days() as i64, nanos))
+            }),
+            ColumnData::SmallDateTime(x) => x.map(|x| {
+                let nanos = x.seconds_fragments() as i64 * 60_000_000_000;
+                Value::Text(text_value::mssql_datetime(x.days() as i64, nanos))
+            }),
+            ColumnData::Date(x) => x.map(|x| Value::Text(text_value::mssql_date(x.days() as i64))),
+            ColumnData::Time(x) => x.map(|x| {
+                let nanos = text_value::mssql_time_nanos(x.increments(), x.scale());
+                Value::Text(text_value::time(nanos))
+            }),
+            ColumnData::DateTime2(x) => x.map(|x| {
+                let days = x.date().days() as i64;
+                let nanos = text_value::mssql_time_nanos(x.time().increments(), x.time().scale());
+                Value::Text(text_value::mssql_datetime2(days, nanos, None))
+            }),
+            ColumnData::DateTimeOffset(x) => x.map(|x| {
+                let dt = x.datetime2();
+                let days = dt.date().days() as i64;
+                let nanos = text_value::mssql_time_nanos(dt.time().increments(), dt.time().scale());
+                let offset = Some(x.offset() as i64);
+                Value::Text(text_value::mssql_datetime2(days, nanos, offset))
+            }),
+        };
+        Some(value.unwrap_or(Value::Null))
+    }
+}
+
+#[cfg(all(test, feature = "noop"))]
+mod tests {
+    use super::*;
+    use crate::noop::ParamValue;
+    use crate::row::MockRow;
+
+    fn row() -> Row {
+        let row = MockRow::new()
+            .column("id", 1_i32)
+            .column("name", "civic".to_string())
+            .column("nickname", None::<String>)
+            .value("data", ParamValue::Bytes(vec![0xAB, 0x01]));
+        Row::from(row)
+    }
+
+    #[test]
+    fn should_list_the_columns_of_a_row() {
+        let row = row();
+        assert_eq!(row.len(), 4);
+        assert!(!row.is_empty());
+        assert_eq!(row.column_names(), vec!["id", "name", "nickname", "data"]);
+        assert_eq!(row.column_name(4), None);
+        let columns = row.columns();
+        assert_eq!(columns[0].type_name, "i32");
+        assert_eq!(columns[3].type_name, "Bytes");
+    }
+
+    #[test]
+    fn should_read_values_by_name_and_position() {
+        let row = row();
+        assert_eq!(row.value_at(0).unwrap(), Value::Int(1));
+        assert_eq!(row.value("name").unwrap(), Value::Text("civic".to_string()));
+        assert!(row.value("nickname").unwrap().is_null());
+        assert_eq!(row.value("data").unwrap(), Value::Bytes(vec![0xAB, 0x01]));
+        let values = row.values().unwrap();
+        assert_eq!(values[1].0, "name");
+        assert!(matches!(row.value("color"), Err(Error::ColumnNotFound(_))));
+        assert!(matches!(row.value_at(9), Err(Error::ColumnNotFound(_))));
+    }
+
+    #[test]
+    fn should_error_on_an_unsupported_type() {
+        let row = Row::from(MockRow::new().column("when", std::time::Duration::ZERO));
+        let err = row.value("when").unwrap_err();
+        assert!(matches!(err, Error::UnsupportedType(name) if name == "when"));
+    }
+
+    #[test]
+    fn should_convert_values() {
+        assert_eq!(Value::Int(0).as_bool(), Some(false));
+        assert_eq!(Value::UInt(3).as_i64(), Some(3));
+        assert_eq!(Value::UInt(u64::MAX).as_i64(), None);
+        assert_eq!(Value::Int(2).as_f64(), Some(2.0));
+        assert_eq!(Value::Text("a".to_string()).as_bytes(), Some(&b"a"[..]));
+        assert_eq!(Value::Float(1.5).as_str(), None);
+    }
+
+    #[test]
+    fn should_write_values_as_text() {
+        assert_eq!(Value::Null.to_string(), "");
+        assert_eq!(Value::Bool(true).to_string(), "true");
+        assert_eq!(Value::Float(1.5).to_string(), "1.5");
+        assert_eq!(Value::Bytes(vec![0xAB, 0x01]).to_string(), "ab01");
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn should_read_each_kind_of_sqlite_column() {
+        use crate::Client;
+        let client = crate::sqlite::connect("sqlite::memory:").await.unwrap();
+        let sql =
+            "SELECT 1 AS i, 1.5 AS f, 'a' AS t, x'AB' AS b, NULL AS n, date('2024-01-31') AS d";
+        let rows = client.fetch_rows(sql, &[]).await.unwrap();
+        let values: Vec<Value> = rows[0].values().unwrap().into_iter().map(|v| v.1).collect();
+        let expected = vec![
+            Value::Int(1),
+            Value::Float(1.5),
+            Value::Text("a".to_string()),
+            Value::Bytes(vec![0xAB]),
+            Value::Null,
+            Value::Text("2024-01-31".to_string()),
+        ];
+        assert_eq!(values, expected);
+    }
+
+    #[cfg(feature = "mssql")]
+    #[test]
+    fn should_read_mssql_dates_uuids_and_decimals_as_text() {
+        use tiberius::numeric::Numeric;
+        use tiberius::time::{Date, DateTime, DateTime2, DateTimeOffset, Time};
+        use tiberius::ColumnData;
+        let text = |data: ColumnData<'_>| match Value::from_column_data(&data) {
+            Some(Value::Text(text)) => text,
+            other => panic!("expected text, got {other:?}"),
+        };
+
+        let uuid = tiberius::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let expected = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        assert_eq!(text(ColumnData::Guid(Some(uuid))), expected);
+        let numeric = Numeric::new_with_scale(-123400, 4);
+        assert_eq!(text(ColumnData::Numeric(Some(numeric))), "-12.3400");
+        let date = Date::new(738_915);
+        assert_eq!(text(ColumnData::Date(Some(date))), "2024-01-31");
+        let time = Time::new(4_950_025, 2);
+        assert_eq!(text(ColumnData::Time(Some(time))), "13:45:00.25");
+        let dt2 = DateTime2::new(date, time);
+        let expected = "2024-01-31T13:45:00.25";
+        assert_eq!(text(ColumnData::DateTime2(Some(dt2))), expected);
+        let offset = DateTimeOffset::new(dt2, -300);
+        let expected = "2024-01-31T08:45:00.25-05:00";
+        assert_eq!(text(ColumnData::DateTimeOffset(Some(offset))), expected);
+        // 300 fragments is one second
+        let datetime = DateTime::new(45_320, 300);
+        let expected = "2024-01-31T00:00:01";
+        assert_eq!(text(ColumnData::DateTime(Some(datetime))), expected);
+        let null = Value::from_column_data(&ColumnData::Guid(None));
+        assert_eq!(null, Some(Value::Null));
+    }
+}
@@ -3,15 +3,23 @@ use super::Row;
 use super::TransactStart;
 use super::{Client, Param};
 use crate::errors::Result;
+use crate::timeout::{self, deadline, Cancelable};
+use crate::trace;
 use crate::ExecuteResult;
+use crate::PoolConfig;
 use async_trait::async_trait;
 use sqlx::query::Query;
 use sqlx::sqlite::SqliteArguments;
+use sqlx::sqlite::SqliteConnectOptions;
 use sqlx::{Sqlite, SqlitePool};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct SqliteClient {
     pool: Arc<SqlitePool>,
+    timeout: Option<Duration>,
 }
 
 #[async_trait]
@@ -24,9 +32,22 @@ impl TransactStart for SqliteClient {
 }
 
 pub async fn connect(url: &str) -> Result<SqliteClient> {
-    let pool = SqlitePool::connect(url).await?;
+    connect_with(url, &PoolConfig::default()).await
+}
+
+/// Connect using custom connection pool settings
+pub async fn connect_with(url: &str, config: &PoolConfig) -> Result<SqliteClient> {
+    let mut options = SqliteConnectOptions::from_str(url)?;
+    if let Some(size) = config.statement_cache_size {
+        options = options.statement_cache_capacity(size);
+    }
+    let pool = config
+        .sqlx_pool_options::<Sqlite>()
+        .connect_with(options)
+        .await?;
     Ok(SqliteClient {
         pool: Arc::new(pool),
+        timeout: config.statement_timeout,
     })
 }
 
@@ -34,6 +55,7 @@ impl From<sqlx::SqlitePool> for SqliteClient {
     fn from(pool: sqlx::SqlitePool) -> SqliteClient {
         SqliteClient {
             pool: Arc::new(pool),
+            timeout: None,
         }
     }
 }
@@ -42,52 +64,83 @@ impl SqliteClient {
     pub fn as_sqlx_pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    /// Checks out a connection to run a statement on. When the statement is running under a
+    /// deadline, a progress handler is set so the statement can be interrupted if the deadline passes
+    async fn acquire(&self) -> Result<Cancelable<Sqlite>> {
+        let mut conn = self.pool.acquire().await?;
+        if !timeout::limited() {
+            return Ok(Cancelable::new(conn));
+        }
+        let canceled = Arc::new(AtomicBool::new(false));
+        let flag = canceled.clone();
+        conn.lock_handle()
+            .await?
+            .set_progress_handler(1000, move || !flag.load(Ordering::Relaxed));
+        Ok(Cancelable::new(conn).on_timeout(async move {
+            canceled.store(true, Ordering::Relaxed);
+            Ok(())
+        }))
+    }
 }
 
+#[cfg(feature = "noop")]
+use crate::noop::ParamValue;
 use sqlx::encode::Encode;
 use sqlx::types::Type;
 
 #[async_trait]
 impl Client for SqliteClient {
     async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
-        let mut query = sqlx::query::<Sqlite>(sql);
-        for param in params {
-            query = SqliteParam::add_param(*param, query);
-        }
-        let r = query.execute(&*self.pool).await?;
-        Ok(ExecuteResult {
-            rows_affected: r.rows_affected(),
-        })
+        let running = deadline(self.timeout, async {
+            let mut query = sqlx::query::<Sqlite>(sql);
+            for param in params {
+                query = SqliteParam::add_param(*param, query);
+            }
+            let mut conn = self.acquire().await?;
+            let r = query.execute(&mut **conn).await?;
+            Ok(ExecuteResult {
+                rows_affected: r.rows_affected(),
+            })
+        });
+        trace::execute(self.syntax(), sql, params.len(), running).await
     }
 
     async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
-        let mut query = sqlx::query::<Sqlite>(sql);
-        for param in params {
-            query = SqliteParam::add_param(*param, query);
-        }
-        let mut raw_rows = query.fetch_all(&*self.pool).await?;
-        let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-        Ok(rows)
+        let running = deadline(self.timeout, async {
+            let mut query = sqlx::query::<Sqlite>(sql);
+            for param in params {
+                query = SqliteParam::add_param(*param, query);
+            }
+            let mut conn = self.acquire().await?;
+            let mut raw_rows = query.fetch_all(&mut **conn).await?;
+            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+            Ok(rows)
+        });
+        trace::fetch_rows(self.syntax(), sql, params.len(), running).await
     }
 
     async fn fetch_many<'s, 'args, 't>(
         &self,
         fetches: &[crate::Fetch<'s, 'args, 't>],
     ) -> Result<Vec<Vec<Row>>> {
-        let mut datasets = Vec::default();
-        let mut conn = self.pool.acquire().await?;
-        for fetch in fetches {
-            let sql = fetch.sql;
-            let params = fetch.params;
-            let mut query = sqlx::query::<Sqlite>(sql);
-            for param in params {
-                query = SqliteParam::add_param(*param, query);
+        let running = deadline(self.timeout, async {
+            let mut datasets = Vec::default();
+            let mut conn = self.acquire().await?;
+            for fetch in fetches {
+                let sql = fetch.sql;
+                let params = fetch.params;
+                let mut query = sqlx::query::<Sqlite>(sql);
+                for param in params {
+                    query = SqliteParam::add_param(*param, query);
+                }
+                let mut raw_rows = query.fetch_all(&mut **conn).await?;
+                let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
+                datasets.push(rows);
             }
-            let mut raw_rows = query.fetch_all(&mut *conn).await?;
-            let rows: Vec<Row> = raw_rows.drain(..).map(Row::from).collect();
-            datasets.push(rows);
-        }
-        Ok(datasets)
+            Ok(datasets)
+        });
+        trace::fetch_many(self.syntax(), fetches, running).await
     }
 
     fn syntax(&self) -> crate::Syntax {
@@ -100,6 +153,10 @@ pub trait SqliteParam {
         &'q self,
         query: Query<'q, Sqlite, SqliteArguments<'q>>,
     ) -> Query<'q, Sqlite, SqliteArguments<'q>>;
+
+    /// Reads the value out of the param so it can be recorded by the `MockClient`
+    #[cfg(feature = "noop")]
+    fn param_value(&self) -> ParamValue;
 }
 
 impl<T> SqliteParam for T
@@ -113,4 +170,25 @@ where
     ) -> Query<'q, Sqlite, SqliteArguments<'q>> {
         query.bind(self)
     }
+
+    #[cfg(feature = "noop")]
+    fn param_value(&self) -> ParamValue {
+        use sqlx::encode::IsNull;
+        use sqlx::sqlite::SqliteArgumentValue;
+        let mut buf = Vec::default();
+        match Encode::<Sqlite>::encode_by_ref(self, &mut buf) {
+            Ok(IsNull::Yes) => return ParamValue::Null,
+            Ok(IsNull::No) => {}
+            Err(_) => return ParamValue::Unknown,
+        }
+        match buf.pop() {
+            Some(SqliteArgumentValue::Null) => ParamValue::Null,
+            Some(SqliteArgumentValue::Text(x)) => ParamValue::Text(x.to_string()),
+            Some(SqliteArgumentValue::Blob(x)) => ParamValue::Bytes(x.to_vec()),
+            Some(SqliteArgumentValue::Double(x)) => ParamValue::Float(x),
+            Some(SqliteArgumentValue::Int(x)) => ParamValue::Int(x as i64),
+            Some(SqliteArgumentValue::Int64(x)) => ParamValue::Int(x),
+            None => ParamValue::Unknown,
+        }
+    }
 }
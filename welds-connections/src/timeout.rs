@@ -0,0 +1,409 @@
+use super::Row;
+use super::{Client, Param};
+use crate::errors::{Error, Result};
+use crate::{ExecuteResult, Fetch, Syntax};
+use async_trait::async_trait;
+use futures_timer::Delay;
+use std::cell::RefCell;
+use std::future::{poll_fn, Future};
+use std::pin::{pin, Pin};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+/// Wraps a client so every statement run through it is given a time limit.
+///
+/// If a statement runs longer than the timeout, it is canceled and `Error::Timeout` is returned.
+/// Useful for setting a timeout on a single query.
+///
+/// ```rust,ignore
+/// let client = TimeoutClient::new(&client, Duration::from_secs(2));
+/// let cars = Car::all().run(&client).await?;
+/// ```
+///
+/// When a statement times out on a Postgres, MySql or Sqlite client, welds cancels it on the
+/// database (`pg_cancel_backend` / `KILL QUERY` / interrupting sqlite) and closes the connection
+/// it was running on rather than returning it to the pool.
+/// MSSQL statements are dropped and their connection is closed.
+///
+/// NOTE: When wrapping a `Transaction` the statement is dropped but NOT canceled on the database.
+/// The transaction's connection waits for the statement to finish before running the next one,
+/// and the statement may or may not have been applied. Roll back the transaction after a timeout.
+/// To have the database cancel long running statements inside transactions,
+/// set a `statement_timeout` with `PoolConfig` when connecting to Postgres or MySql.
+pub struct TimeoutClient<'c> {
+    inner: &'c dyn Client,
+    timeout: Duration,
+}
+
+impl<'c> TimeoutClient<'c> {
+    pub fn new(inner: &'c dyn Client, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+
+    /// The time limit given to each statement
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+#[async_trait]
+impl Client for TimeoutClient<'_> {
+    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+        deadline(Some(self.timeout), self.inner.execute(sql, params)).await
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        deadline(Some(self.timeout), self.inner.fetch_rows(sql, params)).await
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self,
+        args: &[Fetch<'s, 'args, 't>],
+    ) -> Result<Vec<Vec<Row>>> {
+        deadline(Some(self.timeout), self.inner.fetch_many(args)).await
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.inner.syntax()
+    }
+}
+
+/// Runs the future, giving up and returning `Error::Timeout` if it takes longer than the timeout.
+/// The future is dropped when it times out, and any statement it was running is canceled.
+pub(crate) async fn deadline<F, T>(timeout: Option<Duration>, fut: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let timeout = match timeout {
+        Some(t) => t,
+        None => return fut.await,
+    };
+    // a deadline inside another deadline shares its statements, so whichever passes first cancels them
+    let ends = Instant::now() + timeout;
+    let scope = match current() {
+        Some(outer) => Scope {
+            running: outer.running,
+            ends: outer.ends.min(ends),
+            #[cfg(feature = "postgres")]
+            own: ends,
+        },
+        None => Scope {
+            running: Arc::default(),
+            ends,
+            #[cfg(feature = "postgres")]
+            own: ends,
+        },
+    };
+    let running = scope.running.clone();
+    let mut fut = Box::pin(fut);
+    let scoped = poll_fn(|cx| {
+        let _enter = Enter::new(&scope);
+        fut.as_mut().poll(cx)
+    });
+    if let Some(out) = within(timeout, scoped).await {
+        return out;
+    }
+    // take the cancels before dropping the statements, dropping them unregisters them
+    let cancels = running.take();
+    drop(fut);
+    let cancel_all = async {
+        for cancel in cancels {
+            cancel.await;
+        }
+    };
+    if within(timeout, cancel_all).await.is_none() {
+        log::warn!("gave up canceling a statement that timed out");
+    }
+    Err(Error::Timeout)
+}
+
+/// Polls the future until it finishes or the timeout passes
+async fn within<F: Future>(timeout: Duration, fut: F) -> Option<F::Output> {
+    let mut fut = pin!(fut);
+    let mut delay = Delay::new(timeout);
+    poll_fn(|cx| {
+        if let Poll::Ready(out) = fut.as_mut().poll(cx) {
+            return Poll::Ready(Some(out));
+        }
+        if Pin::new(&mut delay).poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+type CancelFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The statements running under a deadline, and how to cancel each of them on the database
+#[derive(Default)]
+struct Running {
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    next_id: AtomicU64,
+    cancels: Mutex<Vec<(u64, Option<CancelFuture>)>>,
+}
+
+impl Running {
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    fn add(&self) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.cancels.lock().unwrap().push((id, None));
+        id
+    }
+
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    fn set_cancel(&self, id: u64, cancel: CancelFuture) {
+        let mut cancels = self.cancels.lock().unwrap();
+        if let Some((_, slot)) = cancels.iter_mut().find(|(x, _)| *x == id) {
+            *slot = Some(cancel);
+        }
+    }
+
+    /// Returns false if the cancel was already taken because the deadline passed
+    #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    fn remove(&self, id: u64) -> bool {
+        let mut cancels = self.cancels.lock().unwrap();
+        let count = cancels.len();
+        cancels.retain(|(x, _)| *x != id);
+        cancels.len() != count
+    }
+
+    fn take(&self) -> Vec<CancelFuture> {
+        let cancels = std::mem::take(&mut *self.cancels.lock().unwrap());
+        cancels
+            .into_iter()
+            .filter_map(|(_, cancel)| cancel)
+            .collect()
+    }
+}
+
+/// The deadline that is being polled
+#[derive(Clone)]
+struct Scope {
+    running: Arc<Running>,
+    // the soonest of this deadline and the ones it is inside of
+    ends: Instant,
+    // when this deadline passes
+    #[cfg(feature = "postgres")]
+    own: Instant,
+}
+
+thread_local! {
+    // the deadline that is being polled on this thread
+    static CURRENT: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+fn current() -> Option<Scope> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+/// Makes a deadline current on this thread until dropped
+struct Enter(Option<Scope>);
+
+impl Enter {
+    fn new(scope: &Scope) -> Self {
+        Enter(CURRENT.with(|c| c.replace(Some(scope.clone()))))
+    }
+}
+
+impl Drop for Enter {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+        CURRENT.with(|c| *c.borrow_mut() = outer);
+    }
+}
+
+/// True if the statement about to be run is inside a deadline.
+/// Used to skip the work of making a statement cancelable when it will never be canceled.
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+pub(crate) fn limited() -> bool {
+    current().is_some()
+}
+
+/// True if a deadline the current one is inside of passes before it does
+#[cfg(feature = "postgres")]
+pub(crate) fn limited_by_outer() -> bool {
+    current().is_some_and(|scope| scope.ends < scope.own)
+}
+
+/// A pooled connection that is running a statement.
+///
+/// If the statement timed out the connection is closed when dropped rather than returned to the pool.
+/// `on_timeout` sets how to cancel the statement on the database as well.
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+pub(crate) struct Cancelable<DB: sqlx::Database> {
+    conn: sqlx::pool::PoolConnection<DB>,
+    registered: Option<(Arc<Running>, u64)>,
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+impl<DB: sqlx::Database> Cancelable<DB> {
+    pub(crate) fn new(conn: sqlx::pool::PoolConnection<DB>) -> Self {
+        let registered = current().map(|scope| {
+            let id = scope.running.add();
+            (scope.running, id)
+        });
+        Self { conn, registered }
+    }
+
+    /// Sets how to cancel the statement on the database if the deadline it is running under passes
+    pub(crate) fn on_timeout<F>(self, cancel: F) -> Self
+    where
+        F: Future<Output = std::result::Result<(), sqlx::Error>> + Send + 'static,
+    {
+        if let Some((running, id)) = &self.registered {
+            running.set_cancel(
+                *id,
+                Box::pin(async move {
+                    if let Err(err) = cancel.await {
+                        log::warn!("failed to cancel a statement that timed out: {err}");
+                    }
+                }),
+            );
+        }
+        self
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+impl<DB: sqlx::Database> std::ops::Deref for Cancelable<DB> {
+    type Target = sqlx::pool::PoolConnection<DB>;
+    fn deref(&self) -> &Self::Target {
+        &self.conn
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+impl<DB: sqlx::Database> std::ops::DerefMut for Cancelable<DB> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.conn
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+impl<DB: sqlx::Database> Drop for Cancelable<DB> {
+    fn drop(&mut self) {
+        if let Some((running, id)) = self.registered.take() {
+            if !running.remove(id) {
+                self.conn.close_on_drop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    // flags when the future it lives in is dropped
+    struct DropFlag(Arc<AtomicBool>);
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_time_out_and_drop_a_slow_future() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = DropFlag(dropped.clone());
+        let slow = async move {
+            let _flag = flag;
+            std::future::pending::<Result<u32>>().await
+        };
+        let result = deadline(Some(Duration::from_millis(20)), slow).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn should_return_the_result_of_a_fast_future() {
+        let fast = async { Ok(7) };
+        let result = deadline(Some(Duration::from_secs(5)), fast).await;
+        assert_eq!(result.unwrap(), 7);
+
+        let failed = async { Err::<u32, _>(Error::Mock("nope".to_string())) };
+        let result = deadline(Some(Duration::from_secs(5)), failed).await;
+        assert!(matches!(result, Err(Error::Mock(_))));
+    }
+
+    #[tokio::test]
+    async fn should_wait_forever_without_a_timeout() {
+        let slow = async {
+            futures_timer::Delay::new(Duration::from_millis(30)).await;
+            Ok(1)
+        };
+        assert_eq!(deadline(None, slow).await.unwrap(), 1);
+    }
+
+    #[cfg(feature = "noop")]
+    #[tokio::test]
+    async fn timeout_client_should_pass_statements_through() {
+        use crate::noop::MockClient;
+        let mock = MockClient::new(Syntax::Postgres);
+        mock.expect_execute("UPDATE cars", 3);
+        let client = TimeoutClient::new(&mock, Duration::from_secs(5));
+        assert_eq!(client.timeout(), Duration::from_secs(5));
+        assert_eq!(client.syntax(), Syntax::Postgres);
+        let result = client.execute("UPDATE cars SET x = 1", &[]).await;
+        assert_eq!(result.unwrap().rows_affected(), 3);
+        mock.assert_all_consumed();
+    }
+
+    #[cfg(feature = "postgres")]
+    #[tokio::test]
+    async fn should_know_when_an_outer_deadline_passes_first() {
+        let check = || async { Ok(limited_by_outer()) };
+        let (short, long) = (Duration::from_secs(1), Duration::from_secs(5));
+        assert!(!deadline(Some(short), check()).await.unwrap());
+        let nested = deadline(Some(short), deadline(Some(long), check()));
+        assert!(nested.await.unwrap());
+        let nested = deadline(Some(long), deadline(Some(short), check()));
+        assert!(!nested.await.unwrap());
+        let nested = deadline(Some(short), deadline(None, check()));
+        assert!(!nested.await.unwrap());
+    }
+
+    // counts forever, only stops if interrupted
+    #[cfg(feature = "sqlite")]
+    const SLOW_SQLITE: &str =
+        "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT count(*) FROM c";
+
+    #[cfg(feature = "sqlite")]
+    async fn sqlite_pool_of_one(config: crate::PoolConfig) -> crate::sqlite::SqliteClient {
+        let config = config
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(5));
+        crate::sqlite::connect_with("sqlite::memory:", &config)
+            .await
+            .unwrap()
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn should_interrupt_a_slow_sqlite_statement() {
+        let config = crate::PoolConfig::new().statement_timeout(Duration::from_millis(50));
+        let client = sqlite_pool_of_one(config).await;
+        let result = client.fetch_rows(SLOW_SQLITE, &[]).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        // the only connection in the pool is free again, the statement isn't still running on it
+        let rows = client.fetch_rows("SELECT 1 AS x", &[]).await.unwrap();
+        assert_eq!(rows[0].get::<i32>("x").unwrap(), 1);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn timeout_client_should_interrupt_a_slow_sqlite_statement() {
+        let client = sqlite_pool_of_one(crate::PoolConfig::new()).await;
+        let limited = TimeoutClient::new(&client, Duration::from_millis(50));
+        let result = limited.execute(SLOW_SQLITE, &[]).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        let rows = limited.fetch_rows("SELECT 1 AS x", &[]).await.unwrap();
+        assert_eq!(rows[0].get::<i32>("x").unwrap(), 1);
+    }
+}
@@ -0,0 +1,240 @@
+// ******************************************************************************
+// Wraps each statement sent to the database in a `tracing` span.
+//
+// When the `tracing` feature is NOT enabled, these are just passthroughs.
+//
+// span fields:
+// db.system, db.statement, db.params, db.rows, db.rows_affected, duration_ms, error
+// ******************************************************************************
+
+use crate::errors::Result;
+use crate::{ExecuteResult, Fetch, Row, Syntax};
+use std::future::Future;
+
+#[cfg(feature = "tracing")]
+use tracing::{field::Empty, Instrument};
+
+#[cfg(feature = "tracing")]
+pub(crate) async fn execute<F>(
+    syntax: Syntax,
+    sql: &str,
+    params: usize,
+    fut: F,
+) -> Result<ExecuteResult>
+where
+    F: Future<Output = Result<ExecuteResult>>,
+{
+    let span = tracing::info_span!(
+        "welds.execute",
+        db.system = ?syntax,
+        db.statement = sql,
+        db.params = params,
+        db.rows_affected = Empty,
+        duration_ms = Empty,
+        error = Empty,
+    );
+    let start = std::time::Instant::now();
+    let result = fut.instrument(span.clone()).await;
+    span.record("duration_ms", start.elapsed().as_millis() as u64);
+    match &result {
+        Ok(r) => span.record("db.rows_affected", r.rows_affected()),
+        Err(err) => span.record("error", tracing::field::display(err)),
+    };
+    result
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) async fn fetch_rows<F>(
+    syntax: Syntax,
+    sql: &str,
+    params: usize,
+    fut: F,
+) -> Result<Vec<Row>>
+where
+    F: Future<Output = Result<Vec<Row>>>,
+{
+    let span = tracing::info_span!(
+        "welds.fetch_rows",
+        db.system = ?syntax,
+        db.statement = sql,
+        db.params = params,
+        db.rows = Empty,
+        duration_ms = Empty,
+        error = Empty,
+    );
+    let start = std::time::Instant::now();
+    let result = fut.instrument(span.clone()).await;
+    span.record("duration_ms", start.elapsed().as_millis() as u64);
+    match &result {
+        Ok(rows) => span.record("db.rows", rows.len()),
+        Err(err) => span.record("error", tracing::field::display(err)),
+    };
+    result
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) async fn fetch_many<F>(
+    syntax: Syntax,
+    fetches: &[Fetch<'_, '_, '_>],
+    fut: F,
+) -> Result<Vec<Vec<Row>>>
+where
+    F: Future<Output = Result<Vec<Vec<Row>>>>,
+{
+    let sql: Vec<&str> = fetches.iter().map(|f| f.sql).collect();
+    let params: usize = fetches.iter().map(|f| f.params.len()).sum();
+    let span = tracing::info_span!(
+        "welds.fetch_many",
+        db.system = ?syntax,
+        db.statement = sql.join(";\n"),
+        db.params = params,
+        db.rows = Empty,
+        duration_ms = Empty,
+        error = Empty,
+    );
+    let start = std::time::Instant::now();
+    let result = fut.instrument(span.clone()).await;
+    span.record("duration_ms", start.elapsed().as_millis() as u64);
+    match &result {
+        Ok(sets) => span.record("db.rows", sets.iter().map(|s| s.len()).sum::<usize>()),
+        Err(err) => span.record("error", tracing::field::display(err)),
+    };
+    result
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn execute<F>(_: Syntax, _: &str, _: usize, fut: F) -> Result<ExecuteResult>
+where
+    F: Future<Output = Result<ExecuteResult>>,
+{
+    fut.await
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn fetch_rows<F>(_: Syntax, _: &str, _: usize, fut: F) -> Result<Vec<Row>>
+where
+    F: Future<Output = Result<Vec<Row>>>,
+{
+    fut.await
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn fetch_many<F>(
+    _: Syntax,
+    _: &[Fetch<'_, '_, '_>],
+    fut: F,
+) -> Result<Vec<Vec<Row>>>
+where
+    F: Future<Output = Result<Vec<Vec<Row>>>>,
+{
+    fut.await
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use crate::errors::Error;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Collects the name and fields of every span, as (field, value)
+    #[derive(Default, Clone)]
+    struct Captured(Arc<Mutex<Vec<(String, String)>>>);
+
+    impl Captured {
+        fn get(&self, field: &str) -> Option<String> {
+            let list = self.0.lock().unwrap();
+            list.iter()
+                .find(|(f, _)| f == field)
+                .map(|(_, v)| v.clone())
+        }
+    }
+
+    struct Fields<'a>(&'a mut Vec<(String, String)>);
+
+    impl Visit for Fields<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push((field.name().to_string(), value.to_string()));
+        }
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+
+    impl Subscriber for Captured {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut list = self.0.lock().unwrap();
+            list.push(("span".to_string(), span.metadata().name().to_string()));
+            span.record(&mut Fields(&mut list));
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            values.record(&mut Fields(&mut self.0.lock().unwrap()));
+        }
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[tokio::test]
+    async fn execute_should_record_the_statement_and_rows_affected() {
+        let captured = Captured::default();
+        let _guard = tracing::subscriber::set_default(captured.clone());
+        let ran = async { Ok(ExecuteResult { rows_affected: 3 }) };
+        let sql = "UPDATE cars SET x = $1 WHERE id = $2";
+        execute(Syntax::Postgres, sql, 2, ran).await.unwrap();
+
+        assert_eq!(captured.get("span").unwrap(), "welds.execute");
+        assert_eq!(captured.get("db.system").unwrap(), "Postgres");
+        assert_eq!(captured.get("db.statement").unwrap(), sql);
+        assert_eq!(captured.get("db.params").unwrap(), "2");
+        assert_eq!(captured.get("db.rows_affected").unwrap(), "3");
+        assert!(captured.get("duration_ms").is_some());
+        assert!(captured.get("error").is_none());
+    }
+
+    #[tokio::test]
+    async fn fetch_rows_should_record_errors() {
+        let captured = Captured::default();
+        let _guard = tracing::subscriber::set_default(captured.clone());
+        let failed = async { Err(Error::Mock("boom".to_string())) };
+        let result = fetch_rows(Syntax::Sqlite, "SELECT 1", 0, failed).await;
+        assert!(result.is_err());
+
+        assert_eq!(captured.get("span").unwrap(), "welds.fetch_rows");
+        assert!(captured.get("error").unwrap().contains("boom"));
+        assert!(captured.get("db.rows").is_none());
+    }
+
+    #[tokio::test]
+    async fn fetch_many_should_record_every_statement() {
+        let captured = Captured::default();
+        let _guard = tracing::subscriber::set_default(captured.clone());
+        let one: i32 = 1;
+        let params: [&(dyn crate::Param + Sync); 1] = [&one];
+        let fetches = [
+            Fetch {
+                sql: "SELECT a",
+                params: &params,
+            },
+            Fetch {
+                sql: "SELECT b",
+                params: &[],
+            },
+        ];
+        let ran = async { Ok(vec![Vec::default(), Vec::default()]) };
+        fetch_many(Syntax::Mysql, &fetches, ran).await.unwrap();
+
+        assert_eq!(captured.get("span").unwrap(), "welds.fetch_many");
+        assert_eq!(captured.get("db.statement").unwrap(), "SELECT a;\nSELECT b");
+        assert_eq!(captured.get("db.params").unwrap(), "1");
+        assert_eq!(captured.get("db.rows").unwrap(), "0");
+    }
+}
@@ -1,16 +1,27 @@
 use super::Row;
 use super::{Client, Param};
+use crate::errors::Error::ClosedTransaction;
 use crate::errors::Result;
+use crate::middleware::Chain;
+use crate::trace;
 use crate::{ExecuteResult, Syntax};
+use async_mutex::Mutex as AsyncMutex;
 use async_trait::async_trait;
-use std::sync::Mutex;
 
 #[cfg(feature = "mssql")]
 use crate::mssql::transaction::MssqlTransaction;
 
+/// A database transaction.
+///
+/// The transaction is locked to a single connection. It is safe to use the same
+/// transaction from several futures at once (`futures::join!`, etc.),
+/// the statements will be run one at a time in the order they acquire the connection.
 pub struct Transaction<'t> {
-    inner: Mutex<Option<TransT<'t>>>,
+    // NOTE: this is an option so it can be taken on commit/rollback
+    inner: AsyncMutex<Option<TransT<'t>>>,
     syntax: crate::Syntax,
+    // set when started from a MiddlewareClient
+    middleware: Option<Chain>,
 }
 
 impl<'t> Transaction<'t> {
@@ -24,48 +35,45 @@ impl<'t> Transaction<'t> {
             TransT::Postgres(_) => Syntax::Postgres,
             #[cfg(feature = "mysql")]
             TransT::Mysql(_) => Syntax::Mysql,
+            #[cfg(feature = "noop")]
+            TransT::Mock(client) => client.syntax(),
         };
 
         Self {
             syntax,
-            inner: Mutex::new(Some(inner)),
+            inner: AsyncMutex::new(Some(inner)),
+            middleware: None,
         }
     }
 
+    /// Runs the statements of this transaction through a chain of middleware.
+    /// The chain is put in front of any middleware the transaction already has
+    pub(crate) fn with_middleware(mut self, chain: Chain) -> Self {
+        self.middleware = Some(match self.middleware.take() {
+            Some(existing) => chain.then(existing),
+            None => chain,
+        });
+        self
+    }
+
     pub async fn rollback(self) -> Result<()> {
-        let inner = self.take_conn();
+        let inner = self.take_conn().await?;
         inner.rollback().await?;
         Ok(())
     }
     pub async fn commit(self) -> Result<()> {
-        let inner = self.take_conn();
+        let inner = self.take_conn().await?;
         inner.commit().await?;
         Ok(())
     }
 }
 
 impl<'t> Transaction<'t> {
-    // HACK - CODE SMELL:
-    // we need a &mut conn for the connection pool
-    // this (take_conn/return_conn) acts like a CellRef
-    // It will panic if you try to the conn more one at at time
-    //
-    fn take_conn(&self) -> TransT<'t> {
-        let mut placeholder = None;
-        let mut m = self.inner.lock().unwrap();
-        let inner: &mut Option<TransT<'t>> = &mut m;
-        // Panic if the conn is already taken
-        assert!(inner.is_some(), "Pool was already taken");
-        std::mem::swap(&mut placeholder, inner);
-        placeholder.unwrap()
-    }
-    fn return_conn(&self, conn: TransT<'t>) {
-        let mut placeholder = Some(conn);
-        let mut m = self.inner.lock().unwrap();
-        let inner: &mut Option<TransT<'t>> = &mut m;
-        // Panic if we already have a the conn
-        assert!(inner.is_none(), "Overriding existing pool");
-        std::mem::swap(&mut placeholder, inner);
+    /// Removes the connection from the transaction so it can be closed.
+    /// Waits for any statements that are still running to finish first.
+    async fn take_conn(&self) -> Result<TransT<'t>> {
+        let mut guard = self.inner.lock().await;
+        guard.take().ok_or(ClosedTransaction)
     }
 }
 
@@ -77,7 +85,11 @@ pub(crate) enum TransT<'t> {
     #[cfg(feature = "mysql")]
     Mysql(sqlx::Transaction<'t, sqlx::MySql>),
     #[cfg(feature = "mssql")]
-    Mssql(MssqlTransaction<'t>),
+    Mssql(Box<MssqlTransaction<'t>>),
+    /// A transaction started from a client that doesn't talk to a database (`MockClient`, `ReplayClient`).
+    /// Statements are run on the client, committing and rolling back do nothing
+    #[cfg(feature = "noop")]
+    Mock(&'t dyn Client),
 }
 
 impl TransT<'_> {
@@ -91,6 +103,8 @@ impl TransT<'_> {
             TransT::Postgres(t) => t.rollback().await?,
             #[cfg(feature = "mysql")]
             TransT::Mysql(t) => t.rollback().await?,
+            #[cfg(feature = "noop")]
+            TransT::Mock(_) => {}
         }
         Ok(())
     }
@@ -104,6 +118,8 @@ impl TransT<'_> {
             TransT::Postgres(t) => t.commit().await?,
             #[cfg(feature = "mysql")]
             TransT::Mysql(t) => t.commit().await?,
+            #[cfg(feature = "noop")]
+            TransT::Mock(_) => {}
         }
         Ok(())
     }
@@ -123,39 +139,105 @@ impl Client for Transaction<'_> {
     }
 
     async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
-        let mut inner = self.take_conn();
-        let results = execute_inner(&mut inner, sql, params).await;
-        self.return_conn(inner);
-        results
+        match &self.middleware {
+            Some(chain) => chain.execute(&Unhooked(self), true, sql, params).await,
+            None => self.execute_unhooked(sql, params).await,
+        }
     }
 
     async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
-        let mut inner = self.take_conn();
-        let results = fetch_rows_inner(&mut inner, sql, params).await;
-        self.return_conn(inner);
-        results
+        match &self.middleware {
+            Some(chain) => chain.fetch_rows(&Unhooked(self), true, sql, params).await,
+            None => self.fetch_rows_unhooked(sql, params).await,
+        }
     }
 
     async fn fetch_many<'s, 'args, 'i>(
         &self,
         fetches: &[crate::Fetch<'s, 'args, 'i>],
+    ) -> Result<Vec<Vec<Row>>> {
+        match &self.middleware {
+            Some(chain) => chain.fetch_many(&Unhooked(self), true, fetches).await,
+            None => self.fetch_many_unhooked(fetches).await,
+        }
+    }
+}
+
+impl Transaction<'_> {
+    async fn execute_unhooked(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync)],
+    ) -> Result<ExecuteResult> {
+        let running = async {
+            let mut guard = self.inner.lock().await;
+            let inner = guard.as_mut().ok_or(ClosedTransaction)?;
+            execute_inner(inner, sql, params).await
+        };
+        trace::execute(self.syntax, sql, params.len(), running).await
+    }
+
+    async fn fetch_rows_unhooked(
+        &self,
+        sql: &str,
+        params: &[&(dyn Param + Sync)],
+    ) -> Result<Vec<Row>> {
+        let running = async {
+            let mut guard = self.inner.lock().await;
+            let inner = guard.as_mut().ok_or(ClosedTransaction)?;
+            fetch_rows_inner(inner, sql, params).await
+        };
+        trace::fetch_rows(self.syntax, sql, params.len(), running).await
+    }
+
+    async fn fetch_many_unhooked(
+        &self,
+        fetches: &[crate::Fetch<'_, '_, '_>],
     ) -> Result<Vec<Vec<Row>>> {
         // transactions are already locked to a single connection.
         // Just run the batch of fetches
-        let mut datasets = Vec::default();
-        let mut inner = self.take_conn();
-        for fetch in fetches {
-            let sql = fetch.sql;
-            let params = fetch.params;
-            let r = fetch_rows_inner(&mut inner, sql, params).await;
-            let is_err = r.is_err();
-            datasets.push(r);
-            if is_err {
-                break;
+        let running = async {
+            let mut datasets = Vec::default();
+            let mut guard = self.inner.lock().await;
+            let inner = guard.as_mut().ok_or(ClosedTransaction)?;
+            for fetch in fetches {
+                let sql = fetch.sql;
+                let params = fetch.params;
+                let r = fetch_rows_inner(inner, sql, params).await;
+                let is_err = r.is_err();
+                datasets.push(r);
+                if is_err {
+                    break;
+                }
             }
-        }
-        self.return_conn(inner);
-        datasets.drain(..).collect()
+            datasets.drain(..).collect()
+        };
+        trace::fetch_many(self.syntax, fetches, running).await
+    }
+}
+
+/// The transaction without its middleware. What the middleware chain sends statements to
+struct Unhooked<'a, 't>(&'a Transaction<'t>);
+
+#[async_trait]
+impl Client for Unhooked<'_, '_> {
+    fn syntax(&self) -> crate::Syntax {
+        self.0.syntax
+    }
+
+    async fn execute(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<ExecuteResult> {
+        self.0.execute_unhooked(sql, params).await
+    }
+
+    async fn fetch_rows(&self, sql: &str, params: &[&(dyn Param + Sync)]) -> Result<Vec<Row>> {
+        self.0.fetch_rows_unhooked(sql, params).await
+    }
+
+    async fn fetch_many<'s, 'args, 'i>(
+        &self,
+        fetches: &[crate::Fetch<'s, 'args, 'i>],
+    ) -> Result<Vec<Vec<Row>>> {
+        self.0.fetch_many_unhooked(fetches).await
     }
 }
 
@@ -212,6 +294,9 @@ async fn execute_inner(
             }
             result
         }
+
+        #[cfg(feature = "noop")]
+        TransT::Mock(client) => client.execute(sql, params).await,
     }
 }
 
@@ -265,5 +350,8 @@ async fn fetch_rows_inner(
             }
             result
         }
+
+        #[cfg(feature = "noop")]
+        TransT::Mock(client) => client.fetch_rows(sql, params).await,
     }
 }
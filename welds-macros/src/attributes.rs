@@ -2,7 +2,10 @@ use crate::errors::Result;
 use crate::utils::as_typepath;
 use crate::{
     column::Column,
+    db_enum::{EnumVariant, Storage},
+    factory::{FactoryField, Variant},
     hook::{Hook, HookKind},
+    inheritance::{Discriminator, ModelVariant},
     relation::Relation,
 };
 use proc_macro2::{Ident, Span};
@@ -26,12 +29,24 @@ pub(crate) fn get_columns(ast: &syn::DeriveInput) -> Vec<Column> {
             let is_option = field_type.is_some();
             let field_type = field_type.unwrap_or(&f.ty).clone();
             let field = f.ident.as_ref().unwrap().clone();
+            let with = read_with(f);
+            let flatten = read_flatten(f);
+            if flatten.is_some() && is_option {
+                panic!("welds(flatten) is not supported on an Option: {}", field);
+            }
+            let db_enum = is_welds_db_enum(f);
+            if db_enum && !is_option {
+                panic!("welds(db_enum) is only used on an Option: {}", field);
+            }
             Column {
                 field,
                 ignore,
                 dbname,
                 field_type,
                 is_option,
+                with,
+                flatten,
+                db_enum,
             }
         })
         .collect()
@@ -56,12 +71,19 @@ pub(crate) fn get_pks(ast: &syn::DeriveInput) -> Vec<Column> {
             let is_option = field_type.is_some();
             let field_type = field_type.unwrap_or(&f.ty).clone();
             let field = f.ident.as_ref().unwrap().clone();
+            let with = read_with(f);
+            if read_flatten(f).is_some() {
+                panic!("welds(flatten) can not be a primary_key: {}", field);
+            }
             Column {
                 field,
                 ignore: false,
                 dbname,
                 field_type,
                 is_option,
+                with,
+                flatten: None,
+                db_enum: is_welds_db_enum(f),
             }
         })
         .collect()
@@ -88,6 +110,52 @@ fn read_rename(field: &Field) -> Option<String> {
     db_col_name
 }
 
+/// returns the converter type from [welds(with = "Converter")]
+fn read_with(field: &Field) -> Option<Type> {
+    let metas = welds_meta(&field.attrs);
+    // Read out the inner meta from [welds(this, and_this)]
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    let with = inners
+        .iter()
+        .filter_map(|m| as_meta_namevalue_ref(m))
+        .filter(|m| m.path.is_ident("with"))
+        .filter_map(|nv| lit_as_litstr(&nv.lit))
+        .next()?;
+    match with.parse() {
+        Ok(ty) => Some(ty),
+        Err(_) => panic!(
+            "Invalid converter type in welds(with = \"{}\")",
+            with.value()
+        ),
+    }
+}
+
+/// returns the column prefix from [welds(flatten)] or [welds(flatten, prefix = "addr_")]
+/// The prefix defaults to the name of the field followed by an underscore
+fn read_flatten(field: &Field) -> Option<String> {
+    let metas = welds_meta(&field.attrs);
+    // Read out the inner meta from [welds(this, and_this)]
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    if !inners.iter().any(|m| m.path().is_ident("flatten")) {
+        return None;
+    }
+    let prefix = inners
+        .iter()
+        .filter_map(|m| as_meta_namevalue_ref(m))
+        .filter(|m| m.path.is_ident("prefix"))
+        .filter_map(|nv| lit_as_litstr(&nv.lit))
+        .map(|x| x.value())
+        .next();
+    Some(prefix.unwrap_or_else(|| format!("{}_", field.ident.as_ref().unwrap())))
+}
+
+/// returns true if the field is marked [welds(db_enum)], an Option of a WeldsEnum
+fn is_welds_db_enum(field: &Field) -> bool {
+    let metas = welds_meta(&field.attrs);
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    inners.iter().any(|m| m.path().is_ident("db_enum"))
+}
+
 /// returns the `inner_type`:  the `T` type inside the `Option<T>`
 fn as_option_inner(ftype: &Type) -> Option<&Type> {
     let tp = as_typepath(ftype)?;
@@ -191,6 +259,89 @@ pub(crate) fn get_hooks(ast: &syn::DeriveInput) -> Result<Vec<Hook>> {
     Ok(hooks)
 }
 
+pub(crate) fn get_discriminator(ast: &syn::DeriveInput) -> Result<Option<Discriminator>> {
+    let metas = welds_meta(&ast.attrs);
+
+    // Read out the inner meta from [welds(this, and_this)]
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+
+    inners
+        .iter()
+        .filter_map(|m| as_metalist_ref(m))
+        .find(|m| m.path.is_ident("discriminator"))
+        .map(Discriminator::new)
+        .transpose()
+}
+
+pub(crate) fn get_model_variants(ast: &syn::DeriveInput) -> Result<Vec<ModelVariant>> {
+    let enum_def = match &ast.data {
+        syn::Data::Enum(d) => d,
+        _ => return Err("Expected an enum".to_owned()),
+    };
+    crate::inheritance::read_variants(enum_def)
+}
+
+pub(crate) fn get_factory_fields(ast: &syn::DeriveInput) -> Result<Vec<FactoryField>> {
+    let struct_def = match &ast.data {
+        syn::Data::Struct(d) => d,
+        _ => return Err("Only Structs are supported by WeldsFactory".to_owned()),
+    };
+    struct_def
+        .fields
+        .iter()
+        .filter_map(|f| Some((f.ident.as_ref()?, f)))
+        .map(|(ident, f)| {
+            let metas = factory_meta(&f.attrs);
+            // Read out the inner meta from [factory(this, and_this)]
+            let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+            FactoryField::new(ident.clone(), &inners)
+        })
+        .collect()
+}
+
+pub(crate) fn get_factory_variants(ast: &syn::DeriveInput) -> Result<Vec<Variant>> {
+    let metas = factory_meta(&ast.attrs);
+    // Read out the inner meta from [factory(this, and_this)]
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    inners
+        .iter()
+        .filter_map(|m| as_metalist_ref(m))
+        .filter(|m| m.path.is_ident("variant"))
+        .map(Variant::new)
+        .collect()
+}
+
+pub(crate) fn get_enum_storage(ast: &syn::DeriveInput) -> Result<Storage> {
+    let metas = welds_meta(&ast.attrs);
+    // Read out the inner meta from [welds(this, and_this)]
+    let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+    Storage::new(&inners)
+}
+
+pub(crate) fn get_enum_variants(ast: &syn::DeriveInput) -> Result<Vec<EnumVariant>> {
+    let enum_def = match &ast.data {
+        syn::Data::Enum(d) => d,
+        _ => return Err("Only Enums are supported by WeldsEnum".to_owned()),
+    };
+    let renames: Vec<Option<String>> = enum_def
+        .variants
+        .iter()
+        .map(|v| {
+            let metas = welds_meta(&v.attrs);
+            // Read out the inner meta from [welds(this, and_this)]
+            let inners: Vec<&syn::Meta> = metas.iter().flat_map(as_metalist_nested_meta).collect();
+            inners
+                .iter()
+                .filter_map(|m| as_meta_namevalue_ref(m))
+                .filter(|m| m.path.is_ident("rename"))
+                .filter_map(|nv| lit_as_litstr(&nv.lit))
+                .map(|x| x.value())
+                .next()
+        })
+        .collect();
+    crate::db_enum::read_variants(enum_def, &renames)
+}
+
 pub(crate) fn get_scructname(ast: &syn::DeriveInput) -> syn::Ident {
     ast.ident.clone()
 }
@@ -295,6 +446,16 @@ fn welds_meta(attrs: &[Attribute]) -> Vec<syn::MetaList> {
         .collect()
 }
 
+/// pull out all the factory attrs as metalists
+fn factory_meta(attrs: &[Attribute]) -> Vec<syn::MetaList> {
+    attrs
+        .iter()
+        .filter_map(|a| a.parse_meta().ok())
+        .filter_map(as_metalist)
+        .filter(|m| m.path.is_ident("factory"))
+        .collect()
+}
+
 /// pull out all the welds_path attrs as metalists
 fn welds_path_meta(attrs: &[Attribute]) -> Vec<syn::MetaList> {
     attrs
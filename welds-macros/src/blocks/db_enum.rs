@@ -0,0 +1,142 @@
+use crate::db_enum::{EnumVariant, Storage};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+pub(crate) fn write(
+    ident: &Ident,
+    wp: &syn::Path,
+    storage: &Storage,
+    variants: &[EnumVariant],
+) -> TokenStream {
+    let storage_q = match storage {
+        Storage::Text => quote! { #wp::connections::enums::EnumStorage::Text },
+        Storage::Integer => quote! { #wp::connections::enums::EnumStorage::Integer },
+        Storage::PostgresEnum(name) => {
+            quote! { #wp::connections::enums::EnumStorage::PostgresEnum(#name) }
+        }
+    };
+    let conversions = match storage {
+        Storage::Integer => write_integer(variants),
+        _ => write_text(variants),
+    };
+
+    quote! {
+
+        impl #wp::connections::enums::DbEnum for #ident {
+            const STORAGE: #wp::connections::enums::EnumStorage = #storage_q;
+            #conversions
+        }
+
+        #wp::connections::__welds_enum!(#ident);
+
+    }
+}
+
+fn write_text(variants: &[EnumVariant]) -> TokenStream {
+    let idents: Vec<_> = variants.iter().map(|v| &v.ident).collect();
+    let texts: Vec<_> = variants.iter().map(|v| v.text.as_str()).collect();
+    quote! {
+        type Repr = String;
+        const TEXT_VALUES: &'static [&'static str] = &[ #(#texts),* ];
+        fn to_db(&self) -> String {
+            match self {
+                #( Self::#idents => #texts, )*
+            }.to_owned()
+        }
+        fn from_db(value: &String) -> Option<Self> {
+            match value.as_str() {
+                #( #texts => Some(Self::#idents), )*
+                _ => None,
+            }
+        }
+    }
+}
+
+fn write_integer(variants: &[EnumVariant]) -> TokenStream {
+    let idents: Vec<_> = variants.iter().map(|v| &v.ident).collect();
+    let numbers: Vec<_> = variants.iter().map(|v| v.number).collect();
+    quote! {
+        type Repr = i32;
+        const TEXT_VALUES: &'static [&'static str] = &[];
+        fn to_db(&self) -> i32 {
+            match self {
+                #( Self::#idents => #numbers, )*
+            }
+        }
+        fn from_db(value: &i32) -> Option<Self> {
+            match *value {
+                #( #numbers => Some(Self::#idents), )*
+                _ => None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn variant(name: &str, text: &str, number: i32) -> EnumVariant {
+        EnumVariant {
+            ident: Ident::new(name, Span::call_site()),
+            text: text.to_owned(),
+            number,
+        }
+    }
+
+    #[test]
+    fn should_write_text_conversions_using_renames() {
+        let variants = vec![variant("Happy", "happy", 0), variant("Sad", "Sad", 1)];
+        let code = write_text(&variants).to_string();
+
+        let expected: &str = r#"
+            type Repr = String;
+            const TEXT_VALUES: &'static [&'static str] = &["happy", "Sad"];
+            fn to_db(&self) -> String {
+                match self {
+                    Self::Happy => "happy",
+                    Self::Sad => "Sad",
+                }.to_owned()
+            }
+            fn from_db(value: &String) -> Option<Self> {
+                match value.as_str() {
+                    "happy" => Some(Self::Happy),
+                    "Sad" => Some(Self::Sad),
+                    _ => None,
+                }
+            }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    #[test]
+    fn should_write_integer_conversions_using_discriminants() {
+        let variants = vec![variant("Low", "Low", 1), variant("High", "High", 10)];
+        let code = write_integer(&variants).to_string();
+
+        let expected: &str = r#"
+            type Repr = i32;
+            const TEXT_VALUES: &'static [&'static str] = &[];
+            fn to_db(&self) -> i32 {
+                match self {
+                    Self::Low => 1i32,
+                    Self::High => 10i32,
+                }
+            }
+            fn from_db(value: &i32) -> Option<Self> {
+                match *value {
+                    1i32 => Some(Self::Low),
+                    10i32 => Some(Self::High),
+                    _ => None,
+                }
+            }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    fn cleaned(input: &str) -> String {
+        input.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+}
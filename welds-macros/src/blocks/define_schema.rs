@@ -42,14 +42,31 @@ fn def_field(info: &Info, col: &Column) -> TokenStream {
     let name = &col.field;
     let wp = &info.welds_path;
     let type_inner = &col.field_type;
-    let ty = quote! { #type_inner };
-    let clause = get_clause(type_inner, col.is_option);
-    let full_type = quote! { #wp::query::clause::#clause<#ty> };
+    if col.flatten.is_some() {
+        return quote! { pub #name: <#type_inner as #wp::model_traits::EmbeddedColumns>::Schema };
+    }
+    let full_type = clause_type(wp, col);
     quote! { pub #name: #full_type }
 }
 
-fn get_clause(ty: &syn::Type, nullable: bool) -> TokenStream {
-    let clasename = crate::utils::get_clause(ty, nullable);
+/// The type of the clause used to query a column
+pub(crate) fn clause_type(wp: &syn::Path, col: &Column) -> TokenStream {
+    let type_inner = &col.field_type;
+    let ty = match &col.with {
+        Some(with) => quote! { #wp::connections::convert::Converted<#with, #type_inner> },
+        None => quote! { #type_inner },
+    };
+    let clause = get_clause(col);
+    quote! { #wp::query::clause::#clause<#ty> }
+}
+
+pub(crate) fn get_clause(col: &Column) -> TokenStream {
+    // Only equality makes sense for a converted field
+    let clasename = match col.with {
+        Some(_) if col.is_option => "BasicOpt".to_owned(),
+        Some(_) => "Basic".to_owned(),
+        None => crate::utils::get_clause(&col.field_type, col.is_option),
+    };
     let id = Ident::new(clasename.as_str(), Span::call_site());
     quote! { #id }
 }
@@ -57,8 +74,11 @@ fn get_clause(ty: &syn::Type, nullable: bool) -> TokenStream {
 fn default_fields(info: &Info, col: &Column) -> TokenStream {
     let wp = &info.welds_path;
     let name = &col.field;
-    let type_inner = &col.field_type;
-    let clause = get_clause(type_inner, col.is_option);
+    if let Some(prefix) = &col.flatten {
+        let ty = &col.field_type;
+        return quote! { #name: <#ty as #wp::model_traits::EmbeddedColumns>::schema(#prefix) };
+    }
+    let clause = get_clause(col);
     let dbname = col.dbname.as_str();
     let fieldname: String = col.field.to_string();
     quote! { #name: #wp::query::clause::#clause::new(#dbname, #fieldname) }
@@ -90,6 +110,28 @@ mod tests {
         assert_eq!(cleaned(&code), cleaned(expected));
     }
 
+    #[test]
+    fn should_use_the_embedded_schema_for_flattened_fields() {
+        let mut info = Info::mock().add_column("address", "Address", false);
+        info.columns[0].flatten = Some("address_".to_owned());
+        let code = write(&info).to_string();
+
+        let expected: &str = r#"
+            pub struct MockSchema {
+                pub address: <Address as welds::model_traits::EmbeddedColumns>::Schema
+            }
+            impl Default for MockSchema {
+                fn default() -> Self {
+                    Self {
+                        address: <Address as welds::model_traits::EmbeddedColumns>::schema("address_")
+                    }
+                }
+            }
+        "#;
+
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
     fn cleaned(input: &str) -> String {
         input.chars().filter(|c| !c.is_whitespace()).collect()
     }
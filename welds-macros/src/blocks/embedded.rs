@@ -0,0 +1,203 @@
+use crate::blocks::define_schema::{clause_type, get_clause};
+use crate::blocks::write_col_default_check::col_switch;
+use crate::blocks::write_to_args::write_col_normal;
+use crate::column::Column;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+/// Writes the EmbeddedColumns impl (and the schema struct it uses) for a WeldsEmbedded struct.
+/// The columns are named at runtime, prefix + dbname, so the same type can be flattened more than once
+pub(crate) fn write(ident: &Ident, wp: &syn::Path, columns: &[Column]) -> TokenStream {
+    let schemastruct = Ident::new(&format!("{}Schema", ident), ident.span());
+    let has_ignored = columns.iter().any(|x| x.ignore);
+    let mut columns: Vec<_> = columns.iter().filter(|x| !x.ignore).collect();
+
+    let schema_fields: Vec<_> = columns.iter().map(|c| schema_field(wp, c)).collect();
+    let schema_defaults: Vec<_> = columns.iter().map(|c| schema_default(wp, c)).collect();
+    let cols: Vec<_> = columns.iter().map(|c| column(wp, c)).collect();
+    let reads: Vec<_> = columns.iter().map(|c| read_field(wp, c)).collect();
+
+    // bind and col_is_default match on the column without its prefix, the same way a model does.
+    // nested flattened fields match on a prefix, so they must come after the columns matched by name
+    columns.sort_by_key(|x| x.flatten.is_some());
+    let binds: Vec<_> = columns.iter().map(|c| write_col_normal(wp, c)).collect();
+    let default_checks: Vec<_> = columns.iter().map(|c| col_switch(wp, c)).collect();
+
+    let defaults = if has_ignored {
+        quote! { ..Default::default() }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+
+        pub struct #schemastruct {
+            #(#schema_fields),*
+        }
+
+        impl #wp::model_traits::EmbeddedColumns for #ident {
+            type Schema = #schemastruct;
+
+            fn columns(prefix: &str) -> Vec<#wp::model_traits::Column> {
+                [ #(#cols),* ].concat()
+            }
+
+            fn schema(prefix: &str) -> #schemastruct {
+                #schemastruct {
+                    #(#schema_defaults),*
+                }
+            }
+
+            fn bind<'s, 'c, 'a, 'p>(
+                &'s self,
+                column: &'c str,
+                args: &'a mut #wp::query::clause::ParamArgs<'p>,
+            ) -> #wp::errors::Result<()>
+            where
+                's: 'p,
+            {
+                match column {
+                    #(#binds)*
+                    _ => {
+                        return Err(#wp::errors::WeldsError::MissingDbColumn(
+                            column.to_owned(),
+                        ).into())
+                    }
+                }
+                Ok(())
+            }
+
+            fn col_is_default(&self, column: &str) -> #wp::errors::Result<bool> {
+                let v = match column {
+                    #(#default_checks)*
+                    _ => {
+                        return Err(#wp::errors::WeldsError::MissingDbColumn(
+                            column.to_owned(),
+                        ).into())
+                    }
+                };
+                Ok(v)
+            }
+
+            fn from_row(row: &#wp::Row, prefix: &str) -> #wp::errors::Result<Self> {
+                Ok(Self {
+                    #(#reads)*
+                    #defaults
+                })
+            }
+        }
+
+    }
+}
+
+/// The full name of the column, prefix + dbname
+fn full_name(dbname: &str) -> TokenStream {
+    quote! { format!("{}{}", prefix, #dbname) }
+}
+
+fn schema_field(wp: &syn::Path, col: &Column) -> TokenStream {
+    let name = &col.field;
+    let ty = &col.field_type;
+    match col.flatten {
+        Some(_) => quote! { pub #name: <#ty as #wp::model_traits::EmbeddedColumns>::Schema },
+        None => {
+            let full_type = clause_type(wp, col);
+            quote! { pub #name: #full_type }
+        }
+    }
+}
+
+fn schema_default(wp: &syn::Path, col: &Column) -> TokenStream {
+    let name = &col.field;
+    let ty = &col.field_type;
+    match &col.flatten {
+        Some(inner) => {
+            let inner = full_name(inner);
+            quote! { #name: <#ty as #wp::model_traits::EmbeddedColumns>::schema(&#inner) }
+        }
+        None => {
+            let full = full_name(&col.dbname);
+            let clause = get_clause(col);
+            quote! { #name: #wp::query::clause::#clause::new(#full, #full) }
+        }
+    }
+}
+
+fn column(wp: &syn::Path, col: &Column) -> TokenStream {
+    let ty = &col.field_type;
+    match &col.flatten {
+        Some(inner) => {
+            let inner = full_name(inner);
+            quote! { <#ty as #wp::model_traits::EmbeddedColumns>::columns(&#inner) }
+        }
+        None => {
+            let full = full_name(&col.dbname);
+            let rust_type = quote! { #ty }.to_string();
+            let nullable = col.is_option;
+            quote! { vec![#wp::model_traits::Column::new(#full, #rust_type, #nullable)] }
+        }
+    }
+}
+
+fn read_field(wp: &syn::Path, col: &Column) -> TokenStream {
+    let field = &col.field;
+    let ty = &col.field_type;
+    if let Some(inner) = &col.flatten {
+        let inner = full_name(inner);
+        return quote! { #field: <#ty as #wp::model_traits::EmbeddedColumns>::from_row(row, &#inner)?, };
+    }
+    let full = full_name(&col.dbname);
+    match &col.with {
+        Some(with) => quote! {
+            #field: row.get::<#wp::connections::convert::Converted<#with, _>>(&#full)?.into_inner(),
+        },
+        None => quote! { #field: row.get(&#full)?, },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::Info;
+    use proc_macro2::Span;
+
+    #[test]
+    fn should_prefix_the_embedded_columns() {
+        let info = Info::mock()
+            .add_column("city", "String", false)
+            .add_column("zip", "String", true);
+        let ident = Ident::new("Address", Span::call_site());
+        let code = write(&ident, &info.welds_path, &info.columns).to_string();
+
+        let expected: &str = r#"
+            fn columns(prefix: &str) -> Vec<welds::model_traits::Column> {
+                [
+                    vec![welds::model_traits::Column::new(format!("{}{}", prefix, "city"), "String", false)],
+                    vec![welds::model_traits::Column::new(format!("{}{}", prefix, "zip"), "String", true)]
+                ].concat()
+            }
+            fn schema(prefix: &str) -> AddressSchema {
+                AddressSchema {
+                    city: welds::query::clause::Text::new(format!("{}{}", prefix, "city"), format!("{}{}", prefix, "city")),
+                    zip: welds::query::clause::TextOpt::new(format!("{}{}", prefix, "zip"), format!("{}{}", prefix, "zip"))
+                }
+            }
+        "#;
+        assert!(cleaned(&code).contains(&cleaned(expected)));
+
+        let expected: &str = r#"
+            fn from_row(row: &welds::Row, prefix: &str) -> welds::errors::Result<Self> {
+                Ok(Self {
+                    city: row.get(&format!("{}{}", prefix, "city"))?,
+                    zip: row.get(&format!("{}{}", prefix, "zip"))?,
+                })
+            }
+        "#;
+        assert!(cleaned(&code).contains(&cleaned(expected)));
+    }
+
+    fn cleaned(input: &str) -> String {
+        input.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+}
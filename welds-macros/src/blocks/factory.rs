@@ -0,0 +1,212 @@
+use crate::errors::Result;
+use crate::factory::{FactoryField, FieldDefault, Variant};
+use crate::info::Info;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn write(
+    info: &Info,
+    fields: &[FactoryField],
+    variants: &[Variant],
+) -> Result<TokenStream> {
+    let defstruct = &info.defstruct;
+    let wp = &info.welds_path;
+
+    let build = write_build(info, fields)?;
+    let variant = write_variant(variants);
+    let key = write_key(info);
+    let parents = write_create_parents(info)?;
+
+    Ok(quote! {
+
+        impl #wp::factory::WeldsFactory for #defstruct {
+            fn next_sequence() -> u64 {
+                static SEQUENCE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+                SEQUENCE.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            }
+            #build
+            #variant
+            #key
+            #parents
+        }
+
+    })
+}
+
+fn write_build(info: &Info, fields: &[FactoryField]) -> Result<TokenStream> {
+    let wp = &info.welds_path;
+
+    let values: Result<Vec<_>> = fields
+        .iter()
+        .map(|f| {
+            let field = &f.field;
+            let column = info.columns.iter().find(|c| &c.field == field);
+            let is_option = column.map(|c| c.is_option).unwrap_or_default();
+            let value = match &f.default {
+                FieldDefault::Expr(expr) => quote! { #expr },
+                FieldDefault::Sequence(Some(text)) => quote! {
+                    std::convert::Into::into(#text.replace("{n}", &sequence.to_string()))
+                },
+                FieldDefault::Sequence(None) => {
+                    quote! { #wp::factory::FromSequence::from_sequence(sequence) }
+                }
+                FieldDefault::ForType if is_option => quote! { None },
+                FieldDefault::ForType => {
+                    let column = column.ok_or_else(|| format!("Unknown field {field}"))?;
+                    let ty = &column.field_type;
+                    let name = field.to_string();
+                    quote! { <#ty as #wp::factory::FactoryDefault>::factory_default(#name, sequence) }
+                }
+            };
+            Ok(quote! { #field: #value, })
+        })
+        .collect();
+    let values = values?;
+
+    Ok(quote! {
+        fn factory_build(sequence: u64) -> Self {
+            Self {
+                #(#values)*
+            }
+        }
+    })
+}
+
+fn write_variant(variants: &[Variant]) -> TokenStream {
+    let arms: Vec<_> = variants
+        .iter()
+        .map(|v| {
+            let name = v.name.to_string();
+            let callback = &v.callback;
+            quote! { #name => { #callback(self); true } }
+        })
+        .collect();
+
+    quote! {
+        fn factory_variant(&mut self, name: &str) -> bool {
+            match name {
+                #(#arms)*
+                _ => false,
+            }
+        }
+    }
+}
+
+fn write_key(info: &Info) -> TokenStream {
+    // Only models with a single primary key can be used as a parent
+    let pk = match info.pks.as_slice() {
+        [pk] => pk,
+        _ => {
+            return quote! {
+                type Key = ();
+                fn factory_key(&self) -> Self::Key {}
+            }
+        }
+    };
+    let field = &pk.field;
+    let ty = &pk.field_type;
+    let ty = if pk.is_option {
+        quote! { Option<#ty> }
+    } else {
+        quote! { #ty }
+    };
+
+    quote! {
+        type Key = #ty;
+        fn factory_key(&self) -> Self::Key {
+            self.#field.clone()
+        }
+    }
+}
+
+/// For each BelongsTo with a required foreign_key,
+/// create the parent if the foreign_key was left unset
+fn write_create_parents(info: &Info) -> Result<TokenStream> {
+    let wp = &info.welds_path;
+
+    let parents: Result<Vec<_>> = info
+        .relations
+        .iter()
+        .filter(|r| r.kind == "BelongsTo")
+        .map(|r| {
+            let column = info
+                .columns
+                .iter()
+                .find(|c| c.dbname == r.foreign_key)
+                .ok_or_else(|| format!("Unknown foreign_key {} for {}", r.foreign_key, r.field))?;
+            Ok((r, column))
+        })
+        .collect();
+    let parents: Vec<_> = parents?
+        .iter()
+        .filter(|(_, column)| !column.is_option)
+        .map(|(relation, column)| {
+            let fk = &column.field;
+            let fk_type = &column.field_type;
+            let parent = &relation.foreign_struct;
+            quote! {
+                if self.#fk == <#fk_type as Default>::default() {
+                    let parent = <#parent as #wp::factory::WeldsFactory>::factory().create(client).await?;
+                    self.#fk = <#parent as #wp::factory::WeldsFactory>::factory_key(&parent);
+                }
+            }
+        })
+        .collect();
+
+    Ok(quote! {
+        fn factory_create_parents<'a>(
+            &'a mut self,
+            client: &'a dyn #wp::Client,
+        ) -> #wp::factory::BoxFuture<'a, #wp::errors::Result<()>> {
+            Box::pin(async move {
+                #(#parents)*
+                Ok(())
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::{Ident, Span};
+
+    #[test]
+    fn should_write_sequences_and_defaults_for_the_factory() {
+        let info = Info::mock()
+            .add_pk("id", "i64")
+            .add_column("name", "String", false)
+            .add_column("email", "String", false)
+            .add_column("nick", "String", true);
+        let field = |name: &str, default| FactoryField {
+            field: Ident::new(name, Span::call_site()),
+            default,
+        };
+        let fields = vec![
+            field("id", FieldDefault::ForType),
+            field("name", FieldDefault::Sequence(None)),
+            field(
+                "email",
+                FieldDefault::Sequence(Some("u{n}@x.com".to_owned())),
+            ),
+            field("nick", FieldDefault::ForType),
+        ];
+        let code = write_build(&info, &fields).unwrap().to_string();
+
+        let expected: &str = r#"
+            fn factory_build(sequence: u64) -> Self {
+                Self {
+                    id: <i64 as welds::factory::FactoryDefault>::factory_default("id", sequence),
+                    name: welds::factory::FromSequence::from_sequence(sequence),
+                    email: std::convert::Into::into("u{n}@x.com".replace("{n}", &sequence.to_string())),
+                    nick: None,
+                }
+            }
+        "#;
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    fn cleaned(input: &str) -> String {
+        input.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+}
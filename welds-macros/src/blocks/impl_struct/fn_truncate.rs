@@ -4,21 +4,16 @@ use quote::quote;
 
 pub(crate) fn write(info: &Info) -> TokenStream {
     let wp = &info.welds_path;
+    if info.readonly {
+        return quote!();
+    }
 
     quote! {
-    pub async fn truncate<C, DB>(conn: &C) -> #wp::errors::Result<()>
-    where
-        C: #wp::connection::Connection<DB>,
-        DB: #wp::connection::Database,
-    {
-        let nameparts =
-            <<Self as #wp::table::HasSchema>::Schema as #wp::table::TableInfo>::identifier();
-        let identifier = nameparts.join(".");
-        let sql = format!("TRUNCATE {}", identifier);
-        conn.execute(&sql, Default::default()).await?;
-        Ok(())
-    }
 
+        /// Removes every row from this table
+        pub async fn truncate(client: &dyn #wp::Client) -> #wp::errors::Result<()> {
+            #wp::query::truncate::truncate::<Self>(client).await
+        }
 
     }
 }
@@ -7,6 +7,7 @@ pub(crate) mod fn_find_by_id;
 pub(crate) mod fn_from_raw_sql;
 pub(crate) mod fn_new;
 pub(crate) mod fn_select;
+pub(crate) mod fn_truncate;
 pub(crate) mod fn_where_col;
 
 pub(crate) fn write(infos: &Info) -> TokenStream {
@@ -18,6 +19,7 @@ pub(crate) fn write(infos: &Info) -> TokenStream {
     let p4 = fn_find_by_id::write(infos);
     let p5 = fn_from_raw_sql::write(infos);
     let p6 = fn_select::write(infos);
+    let p7 = fn_truncate::write(infos);
 
     quote! {
 
@@ -28,6 +30,7 @@ pub(crate) fn write(infos: &Info) -> TokenStream {
             #p4
             #p5
             #p6
+            #p7
         }
 
     }
@@ -0,0 +1,375 @@
+use crate::inheritance::ModelVariant;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+/// Writes the model traits for an enum of models sharing one table. (single-table inheritance)
+///
+/// The table and primary keys come from the first variant.
+/// Each variant is picked by the discriminator of its model
+pub(crate) fn write(
+    ident: &Ident,
+    wp: &syn::Path,
+    variants: &[ModelVariant],
+    readonly: bool,
+) -> TokenStream {
+    let schemastruct = Ident::new(&format!("{}Schema", ident), ident.span());
+    let first = &variants[0].model;
+    let first_schema = quote! { <#first as #wp::model_traits::HasSchema>::Schema };
+
+    let schema_fields: Vec<_> = variants
+        .iter()
+        .map(|v| {
+            let field = &v.field;
+            let model = &v.model;
+            quote! { pub #field: <#model as #wp::model_traits::HasSchema>::Schema }
+        })
+        .collect();
+    let schema_defaults: Vec<_> = variants
+        .iter()
+        .map(|v| {
+            let field = &v.field;
+            quote! { #field: Default::default() }
+        })
+        .collect();
+    let variant_columns: Vec<_> = variants
+        .iter()
+        .map(|v| {
+            let model = &v.model;
+            quote! { <<#model as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableColumns>::columns() }
+        })
+        .collect();
+
+    let reads: Vec<_> = variants.iter().map(|v| read_variant(wp, v)).collect();
+    let checks: Vec<_> = variants
+        .iter()
+        .map(|v| check_variant(ident, wp, &variants[0], v))
+        .collect();
+    let update_from_row = delegate(
+        variants,
+        quote! { #wp::model_traits::UpdateFromRow::update_from_row(inner, row) },
+    );
+    let ident_str = ident.to_string();
+
+    let writes = if readonly {
+        quote!()
+    } else {
+        write_traits(ident, wp, variants)
+    };
+
+    quote! {
+
+        const _: () = {
+            #(#checks)*
+        };
+
+        pub struct #schemastruct {
+            #(#schema_fields),*
+        }
+
+        impl Default for #schemastruct {
+            fn default() -> Self {
+                Self {
+                    #(#schema_defaults),*
+                }
+            }
+        }
+
+        impl #wp::model_traits::HasSchema for #ident {
+            type Schema = #schemastruct;
+        }
+
+        impl #wp::model_traits::TableInfo for #schemastruct {
+            fn identifier() -> &'static [&'static str] {
+                <#first_schema as #wp::model_traits::TableInfo>::identifier()
+            }
+        }
+
+        impl #wp::model_traits::TableColumns for #schemastruct {
+            type ColumnStruct = #schemastruct;
+
+            fn primary_keys() -> Vec<#wp::model_traits::Column> {
+                <#first_schema as #wp::model_traits::TableColumns>::primary_keys()
+            }
+
+            fn columns() -> Vec<#wp::model_traits::Column> {
+                #wp::model_traits::union_columns(&[ #(#variant_columns),* ])
+            }
+        }
+
+        impl TryFrom<#wp::Row> for #ident {
+            type Error = #wp::WeldsError;
+            fn try_from(row: #wp::Row) -> std::result::Result<Self, Self::Error> {
+                #(#reads)*
+                Err(#wp::WeldsError::UnknownDiscriminator(#ident_str.to_owned()))
+            }
+        }
+
+        impl #wp::model_traits::UpdateFromRow for #ident {
+            fn update_from_row(&mut self, row: &mut #wp::Row) -> #wp::errors::Result<()> {
+                #update_from_row
+            }
+        }
+
+        #writes
+
+        impl #ident {
+
+            pub fn all() -> #wp::query::builder::QueryBuilder<Self> {
+                #wp::query::builder::QueryBuilder::new()
+            }
+
+            pub fn where_col(
+                lam: impl Fn(#schemastruct) -> Box<dyn #wp::query::clause::ClauseAdder>,
+            ) -> #wp::query::builder::QueryBuilder<Self> {
+                let select = #wp::query::builder::QueryBuilder::new();
+                select.where_col(lam)
+            }
+
+        }
+
+    }
+}
+
+/// Returns the variant if the row has its discriminator
+fn read_variant(wp: &syn::Path, variant: &ModelVariant) -> TokenStream {
+    let ident = &variant.ident;
+    let model = &variant.model;
+    quote! {
+        if let Some(d) = <<#model as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableInfo>::discriminator() {
+            if row.get::<String>(d.column)? == d.value {
+                return Ok(Self::#ident(<#model as TryFrom<#wp::Row>>::try_from(row)?));
+            }
+        }
+    }
+}
+
+/// Fails to compile if the variant doesn't have a discriminator on the same table and column as the first variant
+fn check_variant(
+    ident: &Ident,
+    wp: &syn::Path,
+    first: &ModelVariant,
+    variant: &ModelVariant,
+) -> TokenStream {
+    let first_model = &first.model;
+    let model = &variant.model;
+    let first_schema = quote! { <<#first_model as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableInfo> };
+    let schema = quote! { <<#model as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableInfo> };
+    let name = format!("{}::{}", ident, variant.ident);
+    let first_name = format!("{}::{}", ident, first.ident);
+    let missing =
+        format!("{name} needs a #[welds(discriminator(column = \"...\", value = \"...\"))]");
+    let table = format!("{name} must be in the same table as {first_name}");
+    let column = format!("{name} must have its discriminator on the same column as {first_name}");
+    quote! {
+        assert!(#schema::DISCRIMINATOR.is_some(), #missing);
+        assert!(#wp::model_traits::same_table(#first_schema::IDENTIFIER, #schema::IDENTIFIER), #table);
+        assert!(#wp::model_traits::same_discriminator_column(#first_schema::DISCRIMINATOR, #schema::DISCRIMINATOR), #column);
+    }
+}
+
+/// Writing to the database and the hooks. The model in the variant does the work
+fn write_traits(ident: &Ident, wp: &syn::Path, variants: &[ModelVariant]) -> TokenStream {
+    let bind = delegate(
+        variants,
+        quote! { #wp::model_traits::WriteToArgs::bind(inner, column, args) },
+    );
+    let writes_column: Vec<_> = variants
+        .iter()
+        .map(|v| {
+            let ident = &v.ident;
+            let model = &v.model;
+            quote! {
+                Self::#ident(_) => <<#model as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableColumns>::columns()
+                    .iter()
+                    .any(|c| c.name() == column),
+            }
+        })
+        .collect();
+    let col_is_default = delegate(
+        variants,
+        quote! { #wp::model_traits::ColumnDefaultCheck::col_is_default(inner, column) },
+    );
+    let hooks = ["BeforeCreate", "BeforeUpdate", "BeforeDelete"]
+        .iter()
+        .map(|name| {
+            let hook = Ident::new(name, ident.span());
+            let call = delegate(
+                variants,
+                quote! { #wp::model_traits::hooks::#hook::before(inner) },
+            );
+            let recv = if *name == "BeforeDelete" {
+                quote! { &self }
+            } else {
+                quote! { &mut self }
+            };
+            quote! {
+                impl #wp::model_traits::hooks::#hook for #ident {
+                    fn before(#recv) -> #wp::errors::Result<()> {
+                        #call
+                    }
+                }
+            }
+        });
+    let after_hooks = ["AfterCreate", "AfterUpdate", "AfterDelete"]
+        .iter()
+        .map(|name| {
+            let hook = Ident::new(name, ident.span());
+            let call = delegate(
+                variants,
+                quote! { #wp::model_traits::hooks::#hook::after(inner) },
+            );
+            quote! {
+                impl #wp::model_traits::hooks::#hook for #ident {
+                    fn after(&self) {
+                        #call
+                    }
+                }
+            }
+        });
+
+    quote! {
+
+        impl #wp::model_traits::WriteToArgs for #ident {
+            fn bind<'s, 'c, 'a, 'p>(
+                &'s self,
+                column: &'c str,
+                args: &'a mut #wp::query::clause::ParamArgs<'p>,
+            ) -> #wp::errors::Result<()>
+            where
+                's: 'p,
+            {
+                #bind
+            }
+
+            fn writes_column(&self, column: &str) -> bool {
+                match self {
+                    #(#writes_column)*
+                }
+            }
+        }
+
+        impl #wp::model_traits::ColumnDefaultCheck for #ident {
+            fn col_is_default(&self, column: &str) -> #wp::errors::Result<bool> {
+                #col_is_default
+            }
+        }
+
+        #(#hooks)*
+        #(#after_hooks)*
+
+    }
+}
+
+/// match self { Self::Variant(inner) => ... }
+fn delegate(variants: &[ModelVariant], body: TokenStream) -> TokenStream {
+    let arms: Vec<_> = variants
+        .iter()
+        .map(|v| {
+            let ident = &v.ident;
+            quote! { Self::#ident(inner) => #body, }
+        })
+        .collect();
+    quote! {
+        match self {
+            #(#arms)*
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn mock_variants() -> Vec<ModelVariant> {
+        ["Car", "PickupTruck"]
+            .iter()
+            .map(|name| ModelVariant {
+                ident: Ident::new(name, Span::call_site()),
+                field: Ident::new(&name.to_lowercase(), Span::call_site()),
+                model: syn::parse_str(name).unwrap(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_read_the_variant_matching_the_discriminator() {
+        let ident = Ident::new("Vehicle", Span::call_site());
+        let wp: syn::Path = Ident::new("welds", Span::call_site()).into();
+        let code = write(&ident, &wp, &mock_variants(), false).to_string();
+
+        let expected: &str = r#"
+            impl TryFrom<welds::Row> for Vehicle {
+                type Error = welds::WeldsError;
+                fn try_from(row: welds::Row) -> std::result::Result<Self, Self::Error> {
+                    if let Some(d) = <<Car as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableInfo>::discriminator() {
+                        if row.get::<String>(d.column)? == d.value {
+                            return Ok(Self::Car(<Car as TryFrom<welds::Row>>::try_from(row)?));
+                        }
+                    }
+                    if let Some(d) = <<PickupTruck as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableInfo>::discriminator() {
+                        if row.get::<String>(d.column)? == d.value {
+                            return Ok(Self::PickupTruck(<PickupTruck as TryFrom<welds::Row>>::try_from(row)?));
+                        }
+                    }
+                    Err(welds::WeldsError::UnknownDiscriminator("Vehicle".to_owned()))
+                }
+            }
+        "#;
+        assert!(cleaned(&code).contains(&cleaned(expected)));
+    }
+
+    #[test]
+    fn should_union_the_columns_of_the_variants() {
+        let ident = Ident::new("Vehicle", Span::call_site());
+        let wp: syn::Path = Ident::new("welds", Span::call_site()).into();
+        let code = write(&ident, &wp, &mock_variants(), false).to_string();
+
+        let expected: &str = r#"
+            fn columns() -> Vec<welds::model_traits::Column> {
+                welds::model_traits::union_columns(&[
+                    <<Car as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableColumns>::columns(),
+                    <<PickupTruck as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableColumns>::columns()
+                ])
+            }
+        "#;
+        assert!(cleaned(&code).contains(&cleaned(expected)));
+    }
+
+    #[test]
+    fn should_check_the_discriminators_when_compiled() {
+        let ident = Ident::new("Vehicle", Span::call_site());
+        let wp: syn::Path = Ident::new("welds", Span::call_site()).into();
+        let code = write(&ident, &wp, &mock_variants(), false).to_string();
+
+        let expected: &str = r#"
+            assert!(
+                welds::model_traits::same_table(
+                    <<Car as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableInfo>::IDENTIFIER,
+                    <<PickupTruck as welds::model_traits::HasSchema>::Schema as welds::model_traits::TableInfo>::IDENTIFIER
+                ),
+                "Vehicle::PickupTruck must be in the same table as Vehicle::Car"
+            );
+        "#;
+        assert!(
+            cleaned(&code).contains(&cleaned(expected)),
+            "CODE: \n\n{}\n\n",
+            code
+        );
+        assert!(code.contains("Vehicle::PickupTruck needs a #[welds(discriminator"));
+    }
+
+    #[test]
+    fn should_not_write_for_readonly_enums() {
+        let ident = Ident::new("Vehicle", Span::call_site());
+        let wp: syn::Path = Ident::new("welds", Span::call_site()).into();
+        let code = write(&ident, &wp, &mock_variants(), true).to_string();
+        assert!(!code.contains("WriteToArgs"));
+        assert!(!code.contains("BeforeCreate"));
+    }
+
+    fn cleaned(input: &str) -> String {
+        input.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+}
@@ -1,6 +1,10 @@
+mod db_enum;
 mod define_schema;
+mod embedded;
+mod factory;
 mod has_schema;
 mod impl_struct;
+mod inheritance;
 mod relations;
 mod table_columns;
 mod table_info;
@@ -12,9 +16,13 @@ mod write_col_default_check;
 mod write_hooks;
 mod write_to_args;
 
+pub(crate) use db_enum::write as db_enum;
 pub(crate) use define_schema::write as define_schema;
+pub(crate) use embedded::write as embedded;
+pub(crate) use factory::write as factory;
 pub(crate) use has_schema::write as has_schema;
 pub(crate) use impl_struct::write as impl_struct;
+pub(crate) use inheritance::write as inheritance;
 pub(crate) use relations::write as relations;
 pub(crate) use table_columns::write as table_columns;
 pub(crate) use table_info::write as table_info;
@@ -7,8 +7,9 @@ pub(crate) fn write(info: &Info) -> TokenStream {
     let defstruct = &info.defstruct;
     let relations_struct = &info.relations_struct;
     let relations = info.relations.as_slice();
+    let list_relations = write_list_relations(info);
     if relations.is_empty() {
-        return quote! {};
+        return list_relations;
     }
 
     let struct_fields: Vec<_> = relations.iter().map(|x| fielddef(info, x)).collect();
@@ -34,6 +35,40 @@ pub(crate) fn write(info: &Info) -> TokenStream {
             }
         }
 
+        #list_relations
+
+    }
+}
+
+// Used to give runtime info about the BelongsTo relationships. (fixtures)
+fn write_list_relations(info: &Info) -> TokenStream {
+    let wp = &info.welds_path;
+    let defstruct = &info.defstruct;
+    let infos: Vec<_> = info
+        .relations
+        .iter()
+        .filter(|r| r.kind == "BelongsTo")
+        .map(|relation| {
+            let field = relation.field.to_string();
+            let fk = &relation.foreign_key;
+            let other = &relation.foreign_struct;
+            quote! {
+                #wp::relations::BelongsToInfo {
+                    field: #field,
+                    foreign_key: #fk,
+                    table: <<#other as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableInfo>::identifier(),
+                    primary_keys: <<#other as #wp::model_traits::HasSchema>::Schema as #wp::model_traits::TableColumns>::primary_keys(),
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        impl #wp::relations::ListRelations for #defstruct {
+            fn belongs_to() -> Vec<#wp::relations::BelongsToInfo> {
+                vec![ #(#infos),* ]
+            }
+        }
     }
 }
 
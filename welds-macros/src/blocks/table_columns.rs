@@ -5,8 +5,9 @@ use quote::quote;
 
 pub(crate) fn write(info: &Info) -> TokenStream {
     let colstruct = write_colstruct(info);
-    let columns = write_cols(&info.columns);
-    let pks = write_cols(&info.pks);
+    let columns = write_cols(info, &info.columns);
+    let columns = with_discriminator(info, columns);
+    let pks = write_cols(info, &info.pks);
     write_for_db(info, &colstruct, &pks, &columns)
 }
 
@@ -17,7 +18,7 @@ pub(crate) fn write_colstruct(info: &Info) -> TokenStream {
     let fields: Vec<_> = info
         .columns
         .iter()
-        .filter(|c| !c.ignore)
+        .filter(|c| !c.ignore && c.flatten.is_none())
         .map(|c| {
             let name = &c.field;
             quote! { pub #name: #wp::model_traits::Column }
@@ -27,7 +28,7 @@ pub(crate) fn write_colstruct(info: &Info) -> TokenStream {
     let default_fields: Vec<_> = info
         .columns
         .iter()
-        .filter(|x| !x.ignore)
+        .filter(|x| !x.ignore && x.flatten.is_none())
         .map(|c| {
             let name = &c.field;
             let ft = &c.field_type;
@@ -57,16 +58,47 @@ pub(crate) fn write_colstruct(info: &Info) -> TokenStream {
     }
 }
 
-pub(crate) fn write_cols(columns: &[Column]) -> TokenStream {
+pub(crate) fn write_cols(info: &Info, columns: &[Column]) -> TokenStream {
+    let columns: Vec<_> = columns.iter().filter(|x| !x.ignore).collect();
+    if columns.iter().all(|c| c.flatten.is_none()) {
+        let parts: Vec<_> = columns
+            .iter()
+            .map(|c| {
+                let name = &c.field;
+                quote! { columns.#name }
+            })
+            .collect();
+        return quote! { vec![ #(#parts),* ] };
+    }
+
+    // Flattened fields add all the columns of the embedded type, in field order
+    let wp = &info.welds_path;
     let parts: Vec<_> = columns
         .iter()
-        .filter(|x| !x.ignore)
-        .map(|c| {
-            let name = &c.field;
-            quote! { columns.#name }
+        .map(|c| match &c.flatten {
+            Some(prefix) => {
+                let ty = &c.field_type;
+                quote! { <#ty as #wp::model_traits::EmbeddedColumns>::columns(#prefix) }
+            }
+            None => {
+                let name = &c.field;
+                quote! { vec![columns.#name] }
+            }
         })
         .collect();
-    quote! { vec![ #(#parts),* ] }
+    quote! { [ #(#parts),* ].concat() }
+}
+
+/// The discriminator column isn't a field, but it is written with the other columns
+fn with_discriminator(info: &Info, columns: TokenStream) -> TokenStream {
+    let wp = &info.welds_path;
+    match &info.discriminator {
+        Some(d) => {
+            let column = &d.column;
+            quote! { [ #columns, vec![#wp::model_traits::Column::new(#column, "String", false)] ].concat() }
+        }
+        None => columns,
+    }
 }
 
 pub(crate) fn write_for_db(
@@ -170,4 +202,22 @@ mod tests {
         eprintln!("CODE: \n{ts}\n");
         assert_eq!(ts.to_string(), expected.to_string());
     }
+
+    #[test]
+    fn should_list_the_columns_of_flattened_fields() {
+        let mut info = Info::mock()
+            .add_pk("id", "i64")
+            .add_column("address", "Address", false);
+        info.columns[1].flatten = Some("address_".to_owned());
+        let ts = write_cols(&info, &info.columns);
+
+        let expected = quote! {
+            [
+                vec![columns.id],
+                <Address as welds::model_traits::EmbeddedColumns>::columns("address_")
+            ].concat()
+        };
+
+        assert_eq!(ts.to_string(), expected.to_string());
+    }
 }
@@ -15,12 +15,23 @@ pub(crate) fn write(info: &Info) -> TokenStream {
     let parts: Vec<_> = parts.drain(..).rev().collect();
     let schema = &info.schemastruct;
 
+    let discriminator = info.discriminator.as_ref().map(|d| {
+        let column = &d.column;
+        let value = &d.value;
+        quote! {
+            const DISCRIMINATOR: Option<#wp::model_traits::Discriminator> =
+                Some(#wp::model_traits::Discriminator { column: #column, value: #value });
+        }
+    });
+
     quote! {
 
         impl #wp::model_traits::TableInfo for #schema {
+            const IDENTIFIER: &'static [&'static str] = &[#(#parts),*];
             fn identifier() -> &'static [&'static str] {
-                &[#(#parts),*]
+                Self::IDENTIFIER
             }
+            #discriminator
         }
 
     }
@@ -29,6 +40,7 @@ pub(crate) fn write(info: &Info) -> TokenStream {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inheritance::Discriminator;
 
     #[test]
     fn should_write_basic_table_info() {
@@ -38,14 +50,36 @@ mod tests {
 
         let expected: &str = r#"
         impl welds::model_traits::TableInfo for MockSchema {
+            const IDENTIFIER: &'static [&'static str] = &[ "daschema","datables"];
             fn identifier() -> &'static [&'static str] {
-                &[ "daschema","datables"]
+                Self::IDENTIFIER
             }
         }
         "#;
         assert_eq!(cleaned(&code), cleaned(expected), "CODE: \n\n{}\n\n", code);
     }
 
+    #[test]
+    fn should_write_the_discriminator() {
+        let mut info = Info::mock().add_pk("id", "i64");
+        info.discriminator = Some(Discriminator {
+            column: "kind".to_owned(),
+            value: "car".to_owned(),
+        });
+        let ts = write(&info);
+        let code = ts.to_string();
+
+        let expected: &str = r#"
+            const DISCRIMINATOR: Option<welds::model_traits::Discriminator> =
+                Some(welds::model_traits::Discriminator { column: "kind", value: "car" });
+        "#;
+        assert!(
+            cleaned(&code).contains(&cleaned(expected)),
+            "CODE: \n\n{}\n\n",
+            code
+        );
+    }
+
     fn cleaned(input: &str) -> String {
         input.chars().filter(|c| !c.is_whitespace()).collect()
     }
@@ -8,7 +8,7 @@ pub(crate) fn write(info: &Info) -> TokenStream {
         .columns
         .iter()
         .filter(|x| !x.ignore)
-        .map(setfield)
+        .map(|x| setfield(info, x))
         .collect();
     let fields = quote! { #(#fields)* };
 
@@ -18,10 +18,20 @@ pub(crate) fn write(info: &Info) -> TokenStream {
     write_for_db(info, &fields, &ignored)
 }
 
-pub(crate) fn setfield(col: &Column) -> TokenStream {
+pub(crate) fn setfield(info: &Info, col: &Column) -> TokenStream {
+    let wp = &info.welds_path;
     let dbname = col.dbname.as_str();
     let field = &col.field;
-    quote! { #field: row.get(#dbname)?, }
+    if let Some(prefix) = &col.flatten {
+        let ty = &col.field_type;
+        return quote! { #field: <#ty as #wp::model_traits::EmbeddedColumns>::from_row(&row, #prefix)?, };
+    }
+    match &col.with {
+        Some(with) => quote! {
+            #field: row.get::<#wp::connections::convert::Converted<#with, _>>(#dbname)?.into_inner(),
+        },
+        None => quote! { #field: row.get(#dbname)?, },
+    }
 }
 
 pub(crate) fn write_for_db(
@@ -83,6 +93,32 @@ mod tests {
         assert_eq!(cleaned(&code), cleaned(expected));
     }
 
+    #[test]
+    fn should_read_fields_with_a_converter() {
+        let mut info = Info::mock().add_column("total", "Money", true);
+        info.columns[0].with = Some(syn::parse_str("MoneyCents").unwrap());
+        let code = setfield(&info, &info.columns[0]).to_string();
+
+        let expected: &str = r#"
+            total: row.get::<welds::connections::convert::Converted<MoneyCents, _>>("total")?.into_inner(),
+        "#;
+
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    #[test]
+    fn should_read_flattened_fields_from_the_embedded_type() {
+        let mut info = Info::mock().add_column("address", "Address", false);
+        info.columns[0].flatten = Some("addr_".to_owned());
+        let code = setfield(&info, &info.columns[0]).to_string();
+
+        let expected: &str = r#"
+            address: <Address as welds::model_traits::EmbeddedColumns>::from_row(&row, "addr_")?,
+        "#;
+
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
     fn cleaned(input: &str) -> String {
         input.chars().filter(|c| !c.is_whitespace()).collect()
     }
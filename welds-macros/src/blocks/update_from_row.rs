@@ -8,17 +8,27 @@ pub(crate) fn write(info: &Info) -> TokenStream {
         .columns
         .iter()
         .filter(|x| !x.ignore)
-        .map(setfield)
+        .map(|x| setfield(info, x))
         .collect();
     let fields = quote! { #(#fields)* };
 
     write_for_db(info, &fields)
 }
 
-pub(crate) fn setfield(col: &Column) -> TokenStream {
+pub(crate) fn setfield(info: &Info, col: &Column) -> TokenStream {
+    let wp = &info.welds_path;
     let dbname = col.dbname.as_str();
     let field = &col.field;
-    quote! { self.#field = row.get(#dbname)?; }
+    if let Some(prefix) = &col.flatten {
+        let ty = &col.field_type;
+        return quote! { self.#field = <#ty as #wp::model_traits::EmbeddedColumns>::from_row(row, #prefix)?; };
+    }
+    match &col.with {
+        Some(with) => quote! {
+            self.#field = row.get::<#wp::connections::convert::Converted<#with, _>>(#dbname)?.into_inner();
+        },
+        None => quote! { self.#field = row.get(#dbname)?; },
+    }
 }
 
 pub(crate) fn write_for_db(info: &Info, fieldsets: &TokenStream) -> TokenStream {
@@ -10,22 +10,34 @@ pub(crate) fn write(info: &Info) -> TokenStream {
         return quote!();
     }
 
-    let fields: Vec<_> = info
-        .columns
+    let mut columns: Vec<_> = info.columns.iter().filter(|x| !x.ignore).collect();
+    // flattened fields match on a prefix, so they must come after the columns matched by name
+    columns.sort_by_key(|x| x.flatten.is_some());
+    let fields: Vec<_> = columns
         .iter()
-        .filter(|x| !x.ignore)
-        .map(col_switch)
+        .map(|x| col_switch(&info.welds_path, x))
         .collect();
-    let fields = quote! { #(#fields)* };
+    let discriminator = info.discriminator.as_ref().map(|d| {
+        let column = &d.column;
+        quote! { #column => false, }
+    });
+    let fields = quote! { #discriminator #(#fields)* };
 
     write_default_check_impl(info, &fields)
 }
 
-pub(crate) fn col_switch(col: &Column) -> TokenStream {
+pub(crate) fn col_switch(wp: &syn::Path, col: &Column) -> TokenStream {
     let dbname = col.dbname.as_str();
     let field = &col.field;
     let field_type = &col.field_type;
 
+    if let Some(prefix) = &col.flatten {
+        let len = prefix.len();
+        return quote! {
+            column if column.starts_with(#prefix) => #wp::model_traits::EmbeddedColumns::col_is_default(&self.#field, &column[#len..])?,
+        };
+    }
+
     if col.is_option {
         return quote! { #dbname => self.#field.is_none(), };
     }
@@ -9,21 +9,49 @@ pub(crate) fn write(info: &Info) -> TokenStream {
         return quote!();
     }
 
-    let fields: Vec<_> = info
-        .columns
+    let mut columns: Vec<_> = info.columns.iter().filter(|x| !x.ignore).collect();
+    // flattened fields match on a prefix, so they must come after the columns matched by name
+    columns.sort_by_key(|x| x.flatten.is_some());
+    let fields: Vec<_> = columns
         .iter()
-        .filter(|x| !x.ignore)
-        .map(write_col_normal)
+        .map(|x| write_col_normal(&info.welds_path, x))
         .collect();
-    let fields = quote! { #(#fields)* };
+    let discriminator = info.discriminator.as_ref().map(|d| {
+        let wp = &info.welds_path;
+        let column = &d.column;
+        let value = &d.value;
+        quote! { #column => args.push(&#wp::connections::convert::StaticText(#value)), }
+    });
+    let fields = quote! { #discriminator #(#fields)* };
 
     write_for_db(info, &fields)
 }
 
-pub(crate) fn write_col_normal(col: &Column) -> TokenStream {
+pub(crate) fn write_col_normal(wp: &syn::Path, col: &Column) -> TokenStream {
     let dbname = col.dbname.as_str();
     let field = &col.field;
-    quote! { #dbname => args.push(&self.#field), }
+    if let Some(prefix) = &col.flatten {
+        let len = prefix.len();
+        return quote! {
+            column if column.starts_with(#prefix) => #wp::model_traits::EmbeddedColumns::bind(&self.#field, &column[#len..], args)?,
+        };
+    }
+    match &col.with {
+        Some(with) => quote! {
+            #dbname => args.push(#wp::connections::convert::Converted::<#with, _>::from_ref(&self.#field)),
+        },
+        // an Option of a WeldsEnum isn't a Param on MSSQL, a None is bound as a NULL of the enum
+        None if col.db_enum => {
+            let ty = &col.field_type;
+            quote! {
+                #dbname => match &self.#field {
+                    Some(value) => args.push(value),
+                    None => args.push(&#wp::connections::enums::EnumNull::<#ty>::NULL),
+                },
+            }
+        }
+        None => quote! { #dbname => args.push(&self.#field), },
+    }
 }
 
 pub(crate) fn write_for_db(info: &Info, matches: &TokenStream) -> TokenStream {
@@ -95,6 +123,61 @@ mod tests {
         assert_eq!(cleaned(&code), cleaned(expected));
     }
 
+    #[test]
+    fn should_bind_fields_with_a_converter() {
+        let mut info = Info::mock().add_column("total", "Money", false);
+        info.columns[0].with = Some(syn::parse_str("MoneyCents").unwrap());
+        let code = write_col_normal(&info.welds_path, &info.columns[0]).to_string();
+
+        let expected: &str = r#"
+            "total" => args.push(welds::connections::convert::Converted::<MoneyCents, _>::from_ref(&self.total)),
+        "#;
+
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    #[test]
+    fn should_bind_optional_fields_as_themselves() {
+        let info = Info::mock().add_column("nickname", "String", true);
+        let code = write_col_normal(&info.welds_path, &info.columns[0]).to_string();
+        let expected: &str = r#" "nickname" => args.push(&self.nickname), "#;
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    #[test]
+    fn should_bind_optional_enums_as_a_null_of_the_enum() {
+        let mut info = Info::mock().add_column("mood", "Mood", true);
+        info.columns[0].db_enum = true;
+        let code = write_col_normal(&info.welds_path, &info.columns[0]).to_string();
+
+        let expected: &str = r#"
+            "mood" => match &self.mood {
+                Some(value) => args.push(value),
+                None => args.push(&welds::connections::enums::EnumNull::<Mood>::NULL),
+            },
+        "#;
+
+        assert_eq!(cleaned(&code), cleaned(expected));
+    }
+
+    #[test]
+    fn should_bind_flattened_fields_after_the_other_columns() {
+        let mut info = Info::mock()
+            .add_column("address", "Address", false)
+            .add_pk("id", "i64");
+        info.columns[0].flatten = Some("address_".to_owned());
+        let code = write(&info).to_string();
+
+        let expected: &str = r#"
+            match column {
+                "id" => args.push(&self.id),
+                column if column.starts_with("address_") => welds::model_traits::EmbeddedColumns::bind(&self.address, &column[8usize..], args)?,
+                _ => {
+        "#;
+
+        assert!(cleaned(&code).contains(&cleaned(expected)));
+    }
+
     fn cleaned(input: &str) -> String {
         input.chars().filter(|c| !c.is_whitespace()).collect()
     }
@@ -8,4 +8,10 @@ pub(crate) struct Column {
     pub(crate) dbname: String,
     pub(crate) field_type: Type,
     pub(crate) is_option: bool,
+    /// The converter from [welds(with = "Converter")]
+    pub(crate) with: Option<Type>,
+    /// The column prefix from [welds(flatten)], the field is a group of columns
+    pub(crate) flatten: Option<String>,
+    /// From [welds(db_enum)], the field is an Option of a WeldsEnum
+    pub(crate) db_enum: bool,
 }
@@ -0,0 +1,90 @@
+use crate::errors::Result;
+use syn::Ident;
+
+/// How the enum is stored in the database
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Storage {
+    /// [ welds(text) ] the default
+    Text,
+    /// [ welds(integer) ]
+    Integer,
+    /// [ welds(postgres_enum = "type_name") ]
+    PostgresEnum(String),
+}
+
+#[derive(Debug)]
+pub(crate) struct EnumVariant {
+    pub(crate) ident: Ident,
+    /// The text stored for this variant: [ welds(rename = "name") ] or the name of the variant
+    pub(crate) text: String,
+    /// The discriminant of this variant
+    pub(crate) number: i32,
+}
+
+impl Storage {
+    pub(crate) fn new(metas: &[&syn::Meta]) -> Result<Self> {
+        let mut storage = Storage::Text;
+        for meta in metas {
+            storage = match meta {
+                syn::Meta::Path(p) if p.is_ident("text") => Storage::Text,
+                syn::Meta::Path(p) if p.is_ident("integer") => Storage::Integer,
+                syn::Meta::NameValue(nv) if nv.path.is_ident("postgres_enum") => match &nv.lit {
+                    syn::Lit::Str(s) => Storage::PostgresEnum(s.value()),
+                    _ => return Err(FORMAT_ERR_STORAGE.to_owned()),
+                },
+                _ => return Err(FORMAT_ERR_STORAGE.to_owned()),
+            };
+        }
+        Ok(storage)
+    }
+}
+
+/// Reads the variants of the enum, numbering them the same way rust does
+pub(crate) fn read_variants(
+    data: &syn::DataEnum,
+    renames: &[Option<String>],
+) -> Result<Vec<EnumVariant>> {
+    let mut next: i32 = 0;
+    let mut variants = Vec::default();
+    for (v, rename) in data.variants.iter().zip(renames) {
+        if !matches!(v.fields, syn::Fields::Unit) {
+            return Err(format!(
+                "WeldsEnum only supports enums without fields: {} has fields",
+                v.ident
+            ));
+        }
+        let number = match &v.discriminant {
+            Some((_, expr)) => read_discriminant(expr)
+                .ok_or_else(|| format!("WeldsEnum: {} must be an integer literal", v.ident))?,
+            None => next,
+        };
+        next = number.wrapping_add(1);
+        variants.push(EnumVariant {
+            ident: v.ident.clone(),
+            text: rename.clone().unwrap_or_else(|| v.ident.to_string()),
+            number,
+        });
+    }
+    Ok(variants)
+}
+
+fn read_discriminant(expr: &syn::Expr) -> Option<i32> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(i),
+            ..
+        }) => i.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => read_discriminant(expr).map(|x| -x),
+        _ => None,
+    }
+}
+
+const FORMAT_ERR_STORAGE: &str = "Invalid Format For WeldsEnum:
+Expected one of
+[ welds(text) ]
+[ welds(integer) ]
+[ welds(postgres_enum = \"type_name\") ]";
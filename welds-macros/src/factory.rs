@@ -0,0 +1,97 @@
+use crate::errors::Result;
+use syn::Ident;
+use syn::MetaList;
+
+/// How a field is filled in when a factory builds a model
+#[derive(Debug)]
+pub(crate) enum FieldDefault {
+    /// [ factory(default = "expr") ]
+    Expr(Box<syn::Expr>),
+    /// [ factory(sequence) ] or [ factory(sequence = "user{n}@example.com") ]
+    Sequence(Option<String>),
+    /// The default value for the type of the field
+    ForType,
+}
+
+#[derive(Debug)]
+pub(crate) struct FactoryField {
+    pub(crate) field: Ident,
+    pub(crate) default: FieldDefault,
+}
+
+/// User has defined a named variant on the factory
+/// [ factory(variant(name, fn_to_call)) ]
+#[derive(Debug)]
+pub(crate) struct Variant {
+    pub(crate) name: Ident,
+    pub(crate) callback: syn::Path,
+}
+
+impl FactoryField {
+    pub(crate) fn new(field: Ident, metas: &[&syn::Meta]) -> Result<Self> {
+        let mut default = FieldDefault::ForType;
+        for meta in metas {
+            if !meta.path().is_ident("sequence") && !meta.path().is_ident("default") {
+                return Err(FORMAT_ERR_FIELD.to_owned());
+            }
+            default = match meta {
+                syn::Meta::Path(_) if meta.path().is_ident("sequence") => {
+                    FieldDefault::Sequence(None)
+                }
+                syn::Meta::NameValue(nv) => {
+                    let value = match &nv.lit {
+                        syn::Lit::Str(s) => s,
+                        _ => return Err(FORMAT_ERR_FIELD.to_owned()),
+                    };
+                    if nv.path.is_ident("sequence") {
+                        FieldDefault::Sequence(Some(value.value()))
+                    } else {
+                        let expr: syn::Expr = value
+                            .parse()
+                            .map_err(|e| format!("Invalid factory default for {field}: {e}"))?;
+                        FieldDefault::Expr(Box::new(expr))
+                    }
+                }
+                _ => return Err(FORMAT_ERR_FIELD.to_owned()),
+            };
+        }
+        Ok(Self { field, default })
+    }
+}
+
+impl Variant {
+    pub(crate) fn new(list: &MetaList) -> Result<Self> {
+        let badformat = || Err(FORMAT_ERR_VARIANT.to_owned());
+
+        let inner: Vec<_> = list.nested.iter().collect();
+        if inner.len() != 2 {
+            return badformat();
+        }
+
+        let paths: Vec<&syn::Path> = inner
+            .iter()
+            .filter_map(|x| match x {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => Some(path),
+                _ => None,
+            })
+            .collect();
+        if paths.len() != 2 || paths[0].segments.len() != 1 {
+            return badformat();
+        }
+
+        Ok(Self {
+            name: paths[0].segments[0].ident.clone(),
+            callback: paths[1].clone(),
+        })
+    }
+}
+
+const FORMAT_ERR_FIELD: &str = "Invalid Format For factory field:
+Expected one of
+[ factory(sequence) ]
+[ factory(sequence = \"text_{n}\") ]
+[ factory(default = \"expression\") ]";
+
+const FORMAT_ERR_VARIANT: &str = "Invalid Format For factory variant:
+variant should be in for format of
+[ factory(variant(name, fn_to_call)) ]";
@@ -2,6 +2,7 @@ use crate::attributes;
 use crate::column::Column;
 use crate::errors::Result;
 use crate::hook::Hook;
+use crate::inheritance::Discriminator;
 use crate::relation::Relation;
 use syn::Ident;
 
@@ -18,6 +19,7 @@ pub(crate) struct Info {
     pub schemaname: Option<String>,
     pub readonly: bool,
     pub welds_path: syn::Path,
+    pub discriminator: Option<Discriminator>,
 }
 
 impl Info {
@@ -37,6 +39,15 @@ impl Info {
         let pks = attributes::get_pks(ast);
         let readonly = attributes::get_readonly(ast);
         let welds_path = attributes::get_welds_path(ast);
+        let discriminator = attributes::get_discriminator(ast)?;
+        if let Some(d) = &discriminator {
+            if columns.iter().any(|c| !c.ignore && c.dbname == d.column) {
+                return Err(format!(
+                    "The discriminator column is written by welds, remove the field for it: {}",
+                    d.column
+                ));
+            }
+        }
 
         Ok(Self {
             columns,
@@ -51,6 +62,7 @@ impl Info {
             schemaname,
             readonly,
             welds_path,
+            discriminator,
         })
     }
 }
@@ -78,6 +90,7 @@ mod tests {
                 schemaname: Some("daschema".to_string()),
                 readonly: false,
                 welds_path: Ident::new("welds", Span::call_site()).into(),
+                discriminator: None,
             }
         }
 
@@ -97,6 +110,9 @@ mod tests {
                 dbname: name,
                 field_type,
                 is_option: null,
+                with: None,
+                flatten: None,
+                db_enum: false,
             };
             self.columns.push(col);
             self
@@ -114,6 +130,9 @@ mod tests {
                 dbname: name,
                 field_type,
                 is_option: false,
+                with: None,
+                flatten: None,
+                db_enum: false,
             };
             self.columns.push(col.clone());
             self.pks.push(col);
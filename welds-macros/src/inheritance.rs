@@ -0,0 +1,87 @@
+use crate::errors::Result;
+use syn::{Ident, MetaList, Type};
+
+/// The rows of a model in a table shared with other models (single-table inheritance)
+/// [ welds(discriminator(column = "type", value = "car")) ]
+#[derive(Debug, Clone)]
+pub(crate) struct Discriminator {
+    pub(crate) column: String,
+    pub(crate) value: String,
+}
+
+/// A variant of an enum deriving WeldsModel. Car(Car)
+#[derive(Debug)]
+pub(crate) struct ModelVariant {
+    pub(crate) ident: Ident,
+    /// The variant in snake_case. The field for this variant on the enum's schema
+    pub(crate) field: Ident,
+    pub(crate) model: Type,
+}
+
+impl Discriminator {
+    pub(crate) fn new(list: &MetaList) -> Result<Self> {
+        let mut column = None;
+        let mut value = None;
+        for inner in &list.nested {
+            let nv = match inner {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) => nv,
+                _ => return Err(FORMAT_ERR_DISCRIMINATOR.to_owned()),
+            };
+            let lit = match &nv.lit {
+                syn::Lit::Str(s) => s.value(),
+                _ => return Err(FORMAT_ERR_DISCRIMINATOR.to_owned()),
+            };
+            if nv.path.is_ident("column") {
+                column = Some(lit);
+            } else if nv.path.is_ident("value") {
+                value = Some(lit);
+            } else {
+                return Err(FORMAT_ERR_DISCRIMINATOR.to_owned());
+            }
+        }
+        match (column, value) {
+            (Some(column), Some(value)) => Ok(Self { column, value }),
+            _ => Err(FORMAT_ERR_DISCRIMINATOR.to_owned()),
+        }
+    }
+}
+
+/// Reads the variants of an enum deriving WeldsModel. Each variant wraps one model
+pub(crate) fn read_variants(data: &syn::DataEnum) -> Result<Vec<ModelVariant>> {
+    let mut variants = Vec::default();
+    for v in &data.variants {
+        let field = match &v.fields {
+            syn::Fields::Unnamed(f) if f.unnamed.len() == 1 => &f.unnamed[0],
+            _ => {
+                return Err(format!(
+                    "WeldsModel enums must wrap a single model in each variant: {}(Model)",
+                    v.ident
+                ))
+            }
+        };
+        variants.push(ModelVariant {
+            ident: v.ident.clone(),
+            field: Ident::new(&snake_case(&v.ident.to_string()), v.ident.span()),
+            model: field.ty.clone(),
+        });
+    }
+    if variants.is_empty() {
+        return Err("WeldsModel enums must have at least one variant".to_owned());
+    }
+    Ok(variants)
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::default();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+const FORMAT_ERR_DISCRIMINATOR: &str = "Invalid Format For discriminator:
+discriminator should be in the format of
+[ welds(discriminator(column = \"type\", value = \"car\")) ]";
@@ -4,9 +4,12 @@ use quote::quote;
 pub(crate) mod attributes;
 pub(crate) mod blocks;
 pub(crate) mod column;
+pub(crate) mod db_enum;
 pub(crate) mod errors;
+pub(crate) mod factory;
 pub(crate) mod hook;
 pub(crate) mod info;
+pub(crate) mod inheritance;
 pub(crate) mod relation;
 pub(crate) mod utils;
 
@@ -23,6 +26,9 @@ pub fn model_gen(input: TokenStream) -> TokenStream {
 fn model_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
     // Gather the Info needed to build all the code snipits
     let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    if let syn::Data::Enum(_) = &ast.data {
+        return model_enum_gen(&ast);
+    }
     let info = Info::new(&ast)?;
 
     // write all the code snipits
@@ -67,3 +73,75 @@ fn model_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
 
     Ok(q.into())
 }
+
+/// An enum of models that share a table (single-table inheritance)
+fn model_enum_gen(ast: &syn::DeriveInput) -> errors::Result<TokenStream> {
+    if !ast.generics.params.is_empty() {
+        return Err("WeldsModel does not support generic enums".to_owned());
+    }
+    let wp = attributes::get_welds_path(ast);
+    let readonly = attributes::get_readonly(ast);
+    let variants = attributes::get_model_variants(ast)?;
+
+    let q = blocks::inheritance(&ast.ident, &wp, &variants, readonly);
+    Ok(q.into())
+}
+
+#[proc_macro_derive(WeldsFactory, attributes(factory))]
+pub fn factory_gen(input: TokenStream) -> TokenStream {
+    match factory_gen_inner(input) {
+        Ok(q) => q,
+        Err(err) => quote! { std::compile_error!(#err); }.into(),
+    }
+}
+
+fn factory_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let info = Info::new(&ast)?;
+    let fields = attributes::get_factory_fields(&ast)?;
+    let variants = attributes::get_factory_variants(&ast)?;
+
+    let q = blocks::factory(&info, &fields, &variants)?;
+    Ok(q.into())
+}
+
+#[proc_macro_derive(WeldsEmbedded, attributes(welds, welds_path))]
+pub fn embedded_gen(input: TokenStream) -> TokenStream {
+    match embedded_gen_inner(input) {
+        Ok(q) => q,
+        Err(err) => quote! { std::compile_error!(#err); }.into(),
+    }
+}
+
+fn embedded_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    if !ast.generics.params.is_empty() {
+        return Err("WeldsEmbedded does not support generic structs".to_owned());
+    }
+    let wp = attributes::get_welds_path(&ast);
+    let columns = attributes::get_columns(&ast);
+
+    let q = blocks::embedded(&ast.ident, &wp, &columns);
+    Ok(q.into())
+}
+
+#[proc_macro_derive(WeldsEnum, attributes(welds, welds_path))]
+pub fn enum_gen(input: TokenStream) -> TokenStream {
+    match enum_gen_inner(input) {
+        Ok(q) => q,
+        Err(err) => quote! { std::compile_error!(#err); }.into(),
+    }
+}
+
+fn enum_gen_inner(input: TokenStream) -> errors::Result<TokenStream> {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    if !ast.generics.params.is_empty() {
+        return Err("WeldsEnum does not support generic enums".to_owned());
+    }
+    let wp = attributes::get_welds_path(&ast);
+    let storage = attributes::get_enum_storage(&ast)?;
+    let variants = attributes::get_enum_variants(&ast)?;
+
+    let q = blocks::db_enum(&ast.ident, &wp, &storage, &variants);
+    Ok(q.into())
+}
@@ -8,18 +8,17 @@ async fn main() -> Result<()> {
     // Connect and setup a DB for use to play with
     let client = welds::connections::connect("sqlite::memory:").await?;
 
+    let migrations: &[MigrationFn] = &[
+        create_peoples_table,
+        create_addresses_table,
+        add_address_to_people,
+        rename_and_make_nullable,
+        index_people_address,
+    ];
+
     // run all the migrations
     // This will skip over migrations that have already ran
-    up(
-        &client,
-        &[
-            create_peoples_table,
-            create_addresses_table,
-            add_address_to_people,
-            rename_and_make_nullable,
-        ],
-    )
-    .await?;
+    up(&client, migrations).await?;
     println!("Migrate Up Complete");
 
     // lets rollback the last change
@@ -27,6 +26,16 @@ async fn main() -> Result<()> {
     println!("Migrate Down Complete");
     println!("Rollback: {}", downed.unwrap());
 
+    // see what has ran, and the SQL that would run next, without running it
+    for step in status(&client, migrations).await? {
+        let name = step.name().unwrap_or_default();
+        match step.when_applied() {
+            Some(when) => println!("Applied: {name} at {when}"),
+            None => println!("Pending: {name}"),
+        }
+    }
+    println!("{}", dry_run(&client, migrations).await?);
+
     Ok(())
 }
 
@@ -61,3 +70,12 @@ fn rename_and_make_nullable(state: &TableState) -> Result<MigrationStep> {
     let m = alter.change("aaddress_id").null().rename("address_id");
     Ok(MigrationStep::new("rename_and_make_nullable", m))
 }
+
+// Index the column we just fixed up. Only the rows with an address are indexed
+fn index_people_address(state: &TableState) -> Result<MigrationStep> {
+    let alter = change_table(state, "people")?;
+    let m = alter
+        .create_index(&["address_id", "name"])
+        .filter("address_id IS NOT NULL");
+    Ok(MigrationStep::new("index_people_address", m))
+}
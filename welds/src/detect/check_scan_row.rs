@@ -0,0 +1,229 @@
+use super::table_def::CheckDef;
+use crate::model_traits::TableIdent;
+use crate::Row;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+pub struct CheckScanRow {
+    pub(super) schema: Option<String>,
+    pub(super) table_name: String,
+    pub(super) table_sql: String,
+}
+
+impl TryFrom<Row> for CheckScanRow {
+    type Error = crate::WeldsError;
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        Ok(CheckScanRow {
+            schema: row.get_by_position(0)?,
+            table_name: row.get_by_position(1)?,
+            table_sql: row.get_by_position(2)?,
+        })
+    }
+}
+
+impl CheckScanRow {
+    pub fn ident(&self) -> TableIdent {
+        TableIdent {
+            schema: self.schema.clone(),
+            name: self.table_name.clone(),
+        }
+    }
+
+    /// The CHECK constraints in the SQL that created the table
+    pub fn checks(&self) -> Vec<CheckDef> {
+        parse_checks(&self.table_sql)
+    }
+}
+
+/// Reads the CHECK constraints out of a CREATE TABLE statement.
+/// Both column and table constraints, named (CONSTRAINT name CHECK (..)) or not
+fn parse_checks(sql: &str) -> Vec<CheckDef> {
+    let mut checks = Vec::default();
+    // the words of the column or table constraint being read
+    let mut words: Vec<&str> = Vec::default();
+    let mut in_body = false;
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                let end = skip_quoted(&mut chars, close, sql.len());
+                words.push(&sql[start..end]);
+            }
+            // the ( around the columns of the table
+            '(' if !in_body => {
+                in_body = true;
+                words.clear();
+            }
+            '(' => {
+                let end = skip_parens(&mut chars, sql.len());
+                if words
+                    .last()
+                    .is_some_and(|w| w.eq_ignore_ascii_case("CHECK"))
+                {
+                    checks.push(read_check(&words, sql[start + 1..end].trim()));
+                }
+            }
+            ',' => words.clear(),
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || *c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                words.push(&sql[start..end]);
+            }
+            _ => {}
+        }
+    }
+    checks
+}
+
+/// Builds the check from the words before its (expr). [column ... CONSTRAINT name CHECK]
+fn read_check(words: &[&str], expr: &str) -> CheckDef {
+    let n = words.len();
+    let name = match n >= 3 && words[n - 3].eq_ignore_ascii_case("CONSTRAINT") {
+        true => Some(words[n - 2].to_string()),
+        false => None,
+    };
+    let table_keywords = ["CONSTRAINT", "CHECK", "PRIMARY", "UNIQUE", "FOREIGN"];
+    let column = words
+        .first()
+        .filter(|w| !table_keywords.iter().any(|k| w.eq_ignore_ascii_case(k)))
+        .map(|w| w.to_string());
+    CheckDef {
+        column,
+        name,
+        expr: expr.to_string(),
+    }
+}
+
+/// Renames a column in the SQL of a CHECK condition. Text in strings is left as is
+pub(crate) fn rename_identifier(expr: &str, from: &str, to: &str) -> String {
+    let mut renamed = String::with_capacity(expr.len());
+    let mut chars = expr.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\'' => {
+                let end = skip_quoted(&mut chars, c, expr.len());
+                renamed.push_str(&expr[start..end]);
+            }
+            '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                let end = skip_quoted(&mut chars, close, expr.len());
+                let inner = &expr[start + 1..end - 1];
+                match inner == from {
+                    true => renamed.push_str(&format!("{c}{to}{close}")),
+                    false => renamed.push_str(&expr[start..end]),
+                }
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || *c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = &expr[start..end];
+                match word.eq_ignore_ascii_case(from) {
+                    true => renamed.push_str(to),
+                    false => renamed.push_str(word),
+                }
+            }
+            c => renamed.push(c),
+        }
+    }
+    renamed
+}
+
+/// Moves past a quoted string or identifier. Returns the index after the closing quote
+fn skip_quoted(chars: &mut Peekable<CharIndices>, close: char, len: usize) -> usize {
+    while let Some((i, c)) = chars.next() {
+        if c != close {
+            continue;
+        }
+        // a doubled quote is an escaped quote
+        if close != ']' && chars.peek().map(|(_, c)| *c) == Some(close) {
+            chars.next();
+            continue;
+        }
+        return i + c.len_utf8();
+    }
+    len
+}
+
+/// Moves past the inside of (...). Returns the index of the closing )
+fn skip_parens(chars: &mut Peekable<CharIndices>, len: usize) -> usize {
+    let mut depth = 1;
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                skip_quoted(chars, c, len);
+            }
+            '[' => {
+                skip_quoted(chars, ']', len);
+            }
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(column: Option<&str>, name: Option<&str>, expr: &str) -> CheckDef {
+        CheckDef {
+            column: column.map(|c| c.to_string()),
+            name: name.map(|n| n.to_string()),
+            expr: expr.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_read_named_and_unnamed_checks() {
+        let sql = "CREATE TABLE dogs ( id INTEGER PRIMARY KEY AUTOINCREMENT, age INT NOT NULL DEFAULT (1) CONSTRAINT ck_dogs_age CHECK (age >= 0), name VARCHAR(20) CHECK(length(name) > 0), CONSTRAINT ck_dogs_old CHECK (age < 100) )";
+        let expected = vec![
+            check(Some("age"), Some("ck_dogs_age"), "age >= 0"),
+            check(Some("name"), None, "length(name) > 0"),
+            check(None, Some("ck_dogs_old"), "age < 100"),
+        ];
+        assert_eq!(parse_checks(sql), expected);
+    }
+
+    #[test]
+    fn should_not_read_checks_out_of_strings() {
+        let sql = "CREATE TABLE \"Dog Checks\" ( name TEXT DEFAULT 'CHECK (x)' CONSTRAINT \"ck name\" CHECK (name <> ')''(') )";
+        let expected = vec![check(Some("name"), Some("\"ck name\""), "name <> ')''('")];
+        assert_eq!(parse_checks(sql), expected);
+    }
+
+    #[test]
+    fn should_rename_a_column_in_a_check() {
+        let expr = "age >= 0 AND \"age\" < 100 AND page > 0 AND name <> 'age'";
+        let renamed = rename_identifier(expr, "age", "years");
+        let expected = "years >= 0 AND \"years\" < 100 AND page > 0 AND name <> 'age'";
+        assert_eq!(renamed, expected);
+    }
+
+    #[test]
+    fn tables_without_checks_have_none() {
+        let sql = "CREATE TABLE cars ( id INTEGER PRIMARY KEY, checked INT NOT NULL DEFAULT (1) )";
+        assert!(parse_checks(sql).is_empty());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::model_traits::TableIdent;
+use crate::Row;
+
+/// One column of a foreign key. A foreign key over many columns has a row for each
+pub struct ForeignKeyScanRow {
+    pub(super) schema: Option<String>,
+    pub(super) table_name: String,
+    pub(super) id: i64,
+    pub(super) column: String,
+    pub(super) foreign_table: String,
+    pub(super) foreign_column: Option<String>,
+    pub(super) on_delete: String,
+    pub(super) on_update: String,
+}
+
+impl TryFrom<Row> for ForeignKeyScanRow {
+    type Error = crate::WeldsError;
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        Ok(ForeignKeyScanRow {
+            schema: row.get_by_position(0)?,
+            table_name: row.get_by_position(1)?,
+            id: row.get_by_position(2)?,
+            column: row.get_by_position(3)?,
+            foreign_table: row.get_by_position(4)?,
+            foreign_column: row.get_by_position(5)?,
+            on_delete: row.get_by_position(6)?,
+            on_update: row.get_by_position(7)?,
+        })
+    }
+}
+
+impl ForeignKeyScanRow {
+    pub fn ident(&self) -> TableIdent {
+        TableIdent {
+            schema: self.schema.clone(),
+            name: self.table_name.clone(),
+        }
+    }
+}
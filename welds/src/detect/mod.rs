@@ -11,6 +11,12 @@ mod table_scan_row;
 use table_scan_row::TableScanRow;
 mod fk_scan_row;
 use fk_scan_row::{FkScanRow, FkScanTableCol};
+mod view_scan_row;
+use view_scan_row::ViewScanRow;
+mod check_scan_row;
+use check_scan_row::CheckScanRow;
+mod foreign_key_scan_row;
+use foreign_key_scan_row::ForeignKeyScanRow;
 
 #[cfg(feature = "mock")]
 pub use table_def::mock::MockColumnDef;
@@ -18,7 +24,9 @@ pub use table_def::mock::MockColumnDef;
 pub use table_def::mock::MockTableDef;
 
 pub(crate) mod table_def;
-pub use table_def::{ColumnDef, DataType, RelationDef, TableDef, TableDefSingle};
+pub use table_def::{
+    CheckDef, ColumnDef, DataType, ForeignKeyDef, RelationDef, TableDef, TableDefSingle, ViewDef,
+};
 
 /// Returns a list of all user defined tables in the database
 /// requires feature `detect`
@@ -50,6 +58,32 @@ pub async fn find_all_tables(client: &dyn Client) -> Result<Vec<TableDef>> {
 
     link_fks_into_tables(&fks, &mut tables);
 
+    // Add the SQL that creates each view
+    let sql = ts.view_scan_sql();
+    let args: ParamArgs = Vec::default();
+    let mut views_raw = client.fetch_rows(sql, &args).await?;
+    let views: Result<Vec<ViewScanRow>> = views_raw.drain(..).map(|r| r.try_into()).collect();
+    let views = views?;
+
+    link_views_into_tables(views, &mut tables);
+
+    // Add the CHECK constraints of each table, if they are needed for this database
+    if let Some(sql) = ts.check_scan_sql() {
+        let args: ParamArgs = Vec::default();
+        let mut checks_raw = client.fetch_rows(sql, &args).await?;
+        let checks: Result<Vec<CheckScanRow>> =
+            checks_raw.drain(..).map(|r| r.try_into()).collect();
+        link_checks_into_tables(checks?, &mut tables);
+    }
+
+    // Add the foreign keys of each table in full, if they are needed for this database
+    if let Some(sql) = ts.foreign_key_scan_sql() {
+        let args: ParamArgs = Vec::default();
+        let mut fks_raw = client.fetch_rows(sql, &args).await?;
+        let fks: Result<Vec<ForeignKeyScanRow>> = fks_raw.drain(..).map(|r| r.try_into()).collect();
+        link_foreign_keys_into_tables(fks?, &mut tables);
+    }
+
     Ok(tables)
 }
 
@@ -167,6 +201,56 @@ fn link_fks_into_tables(fks: &[FkScanRow], tables: &mut [TableDef]) {
     }
 }
 
+fn link_views_into_tables(views: Vec<ViewScanRow>, tables: &mut [TableDef]) {
+    for view in views {
+        let ident = view.ident();
+        if let Some(table) = tables.iter_mut().find(|t| t.ident == ident) {
+            table.view = Some(ViewDef {
+                sql: view.view_sql.trim().to_string(),
+                materialized: view.is_materialized > 0,
+            });
+        }
+    }
+}
+
+fn link_checks_into_tables(rows: Vec<CheckScanRow>, tables: &mut [TableDef]) {
+    for row in rows {
+        let ident = row.ident();
+        if let Some(table) = tables.iter_mut().find(|t| t.ident == ident) {
+            table.checks = row.checks();
+        }
+    }
+}
+
+/// Groups the rows of each foreign key (one per column) and adds them to their tables
+fn link_foreign_keys_into_tables(rows: Vec<ForeignKeyScanRow>, tables: &mut [TableDef]) {
+    let mut last: Option<(TableIdent, i64)> = None;
+    for row in rows {
+        let ident = row.ident();
+        let Some(table) = tables.iter_mut().find(|t| t.ident == ident) else {
+            continue;
+        };
+        let key = Some((ident, row.id));
+        // the next column of the same foreign key
+        if last == key {
+            if let Some(fk) = table.foreign_keys.last_mut() {
+                fk.columns.push(row.column);
+                fk.foreign_columns.extend(row.foreign_column);
+            }
+            continue;
+        }
+        last = key;
+        table.foreign_keys.push(ForeignKeyDef {
+            name: None,
+            columns: vec![row.column],
+            foreign_table: row.foreign_table,
+            foreign_columns: row.foreign_column.into_iter().collect(),
+            on_delete: row.on_delete,
+            on_update: row.on_update,
+        });
+    }
+}
+
 /// Groups the Table Scan Rows into TableDefs
 fn build_table_defs(syntax: Syntax, rows: Vec<TableScanRow>) -> Vec<TableDef> {
     //group the rows into vecs for each table
@@ -180,7 +264,7 @@ fn build_table_defs(syntax: Syntax, rows: Vec<TableScanRow>) -> Vec<TableDef> {
     let mut tables = Vec::default();
     for (ident, bucket) in buckets.drain() {
         let ty = bucket[0].kind();
-        let columns = build_cols(bucket);
+        let columns = build_cols(syntax, bucket);
         tables.push(TableDef {
             syntax,
             ident,
@@ -188,11 +272,35 @@ fn build_table_defs(syntax: Syntax, rows: Vec<TableScanRow>) -> Vec<TableDef> {
             columns,
             has_many: Vec::default(),
             belongs_to: Vec::default(),
+            view: None,
+            checks: Vec::default(),
+            foreign_keys: Vec::default(),
         });
     }
     tables
 }
 
+/// Sqlite gives back expression defaults without the () they need to be used again
+fn column_default(syntax: Syntax, default: String) -> String {
+    if syntax != Syntax::Sqlite || is_sqlite_literal(&default) {
+        return default;
+    }
+    format!("({default})")
+}
+
+fn is_sqlite_literal(sql: &str) -> bool {
+    let upper = sql.to_uppercase();
+    let keywords = ["NULL", "TRUE", "FALSE"];
+    let quoted = sql.len() > 1
+        && sql.starts_with('\'')
+        && sql.ends_with('\'')
+        && !sql[1..sql.len() - 1].replace("''", "").contains('\'');
+    sql.parse::<f64>().is_ok()
+        || quoted
+        || keywords.contains(&upper.as_str())
+        || upper.starts_with("CURRENT_")
+}
+
 fn build_lookup(
     fks: &[FkScanRow],
     src: impl Fn(&FkScanRow) -> &FkScanTableCol,
@@ -206,7 +314,7 @@ fn build_lookup(
     map
 }
 
-fn build_cols(mut rows: Vec<TableScanRow>) -> Vec<ColumnDef> {
+fn build_cols(syntax: Syntax, mut rows: Vec<TableScanRow>) -> Vec<ColumnDef> {
     rows.drain(..)
         .map(|r| {
             // NOTE: we get _TYPE back for types that are TYPE[]. doing this type swap back to normal here.
@@ -221,6 +329,7 @@ fn build_cols(mut rows: Vec<TableScanRow>) -> Vec<ColumnDef> {
                 null: r.is_nullable > 0,
                 primary_key: r.is_primary_key > 0,
                 updatable: r.is_updatable > 0,
+                default: r.column_default.map(|d| column_default(syntax, d)),
             }
         })
         .collect()
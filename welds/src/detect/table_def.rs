@@ -11,6 +11,9 @@ pub struct TableDef {
     pub(crate) has_many: Vec<RelationDef>,
     pub(crate) belongs_to: Vec<RelationDef>,
     pub(crate) syntax: Syntax,
+    pub(crate) view: Option<ViewDef>,
+    pub(crate) checks: Vec<CheckDef>,
+    pub(crate) foreign_keys: Vec<ForeignKeyDef>,
 }
 
 impl TableDef {
@@ -32,6 +35,20 @@ impl TableDef {
     pub fn syntax(&self) -> Syntax {
         self.syntax
     }
+    /// The definition of this view. None for tables
+    pub fn view(&self) -> Option<&ViewDef> {
+        self.view.as_ref()
+    }
+    /// The CHECK constraints on this table.
+    /// Only read for Sqlite, which needs them to keep them when it rebuilds a table
+    pub fn checks(&self) -> &[CheckDef] {
+        &self.checks
+    }
+    /// The foreign keys on this table, with their ON DELETE/UPDATE actions.
+    /// Only read for Sqlite, which needs them to keep them when it rebuilds a table
+    pub fn foreign_keys(&self) -> &[ForeignKeyDef] {
+        &self.foreign_keys
+    }
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -76,6 +93,7 @@ pub struct ColumnDef {
     pub(crate) null: bool,
     pub(crate) primary_key: bool,
     pub(crate) updatable: bool,
+    pub(crate) default: Option<String>,
 }
 
 impl ColumnDef {
@@ -103,6 +121,11 @@ impl ColumnDef {
         self.updatable
     }
 
+    /// returns the SQL of the default value of this column, if it has one
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
     /// returns a model_traits::Column. This can be used for queries.
     pub fn as_query_column(&self, syntax: Syntax) -> Option<crate::model_traits::Column> {
         let db_type = self.ty();
@@ -116,6 +139,148 @@ impl ColumnDef {
     }
 }
 
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+/// Describes how a view is made
+pub struct ViewDef {
+    pub(crate) sql: String,
+    pub(crate) materialized: bool,
+}
+
+impl ViewDef {
+    /// returns the SQL that creates this view
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// returns true if this is a materialized view (postgres)
+    pub fn materialized(&self) -> bool {
+        self.materialized
+    }
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+/// A CHECK constraint on a table
+pub struct CheckDef {
+    pub(crate) column: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) expr: String,
+}
+
+impl CheckDef {
+    /// returns the column the constraint was written on. None if it is a table constraint
+    pub fn column(&self) -> Option<&str> {
+        self.column.as_deref()
+    }
+
+    /// returns the name of the constraint, if it was given one
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// returns the SQL condition that is checked
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// returns the SQL of this constraint, to be used in a CREATE TABLE after its column or the columns
+    pub fn sql(&self) -> String {
+        match &self.name {
+            Some(name) => format!("CONSTRAINT {} CHECK ({})", name, self.expr),
+            None => format!("CHECK ({})", self.expr),
+        }
+    }
+
+    /// returns this constraint with a column renamed, in the column it is on and in its condition
+    pub(crate) fn rename_column(&self, from: &str, to: &str) -> CheckDef {
+        let column = match self.column.as_deref() {
+            Some(column) if column == from => Some(to.to_string()),
+            column => column.map(|c| c.to_string()),
+        };
+        CheckDef {
+            column,
+            name: self.name.clone(),
+            expr: super::check_scan_row::rename_identifier(&self.expr, from, to),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+/// A FOREIGN KEY constraint on a table
+pub struct ForeignKeyDef {
+    pub(crate) name: Option<String>,
+    pub(crate) columns: Vec<String>,
+    pub(crate) foreign_table: String,
+    pub(crate) foreign_columns: Vec<String>,
+    pub(crate) on_delete: String,
+    pub(crate) on_update: String,
+}
+
+impl ForeignKeyDef {
+    /// returns the name of the constraint, if it was given one
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// returns the columns on this table that point to the other table
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// returns the table the foreign key points to
+    pub fn foreign_table(&self) -> &str {
+        &self.foreign_table
+    }
+
+    /// returns the columns the foreign key points to. Empty if it points to the primary key
+    pub fn foreign_columns(&self) -> &[String] {
+        &self.foreign_columns
+    }
+
+    /// returns the SQL action taken when the row pointed to is deleted. (CASCADE, SET NULL, ...)
+    pub fn on_delete(&self) -> &str {
+        &self.on_delete
+    }
+
+    /// returns the SQL action taken when the key pointed to is updated. (CASCADE, SET NULL, ...)
+    pub fn on_update(&self) -> &str {
+        &self.on_update
+    }
+
+    /// returns the SQL of this constraint, to be used in a CREATE TABLE after the columns
+    pub fn sql(&self) -> String {
+        let mut sql = match &self.name {
+            Some(name) => format!("CONSTRAINT {name} "),
+            None => String::default(),
+        };
+        let columns = self.columns.join(", ");
+        sql.push_str(&format!(
+            "FOREIGN KEY ({columns}) REFERENCES {}",
+            self.foreign_table
+        ));
+        if !self.foreign_columns.is_empty() {
+            sql.push_str(&format!(" ({})", self.foreign_columns.join(", ")));
+        }
+        sql.push_str(&format!(" ON DELETE {}", self.on_delete));
+        if self.on_update != "NO ACTION" {
+            sql.push_str(&format!(" ON UPDATE {}", self.on_update));
+        }
+        sql
+    }
+
+    /// returns this constraint with a column of this table renamed
+    pub(crate) fn rename_column(&self, from: &str, to: &str) -> ForeignKeyDef {
+        let columns = self
+            .columns
+            .iter()
+            .map(|c| if c == from { to } else { c }.to_string())
+            .collect();
+        ForeignKeyDef {
+            columns,
+            ..self.clone()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 pub enum DataType {
     Table,
@@ -169,6 +334,7 @@ pub mod mock {
                 null: false,
                 primary_key: false,
                 updatable: true,
+                default: None,
             })
         }
 
@@ -186,6 +352,11 @@ pub mod mock {
             self
         }
 
+        pub fn default(mut self, sql: impl Into<String>) -> Self {
+            self.0.default = Some(sql.into());
+            self
+        }
+
         pub fn build(self) -> ColumnDef {
             self.0
         }
@@ -204,6 +375,9 @@ pub mod mock {
                 columns: Vec::default(),
                 has_many: Vec::default(),
                 belongs_to: Vec::default(),
+                view: None,
+                checks: Vec::default(),
+                foreign_keys: Vec::default(),
             })
         }
 
@@ -214,6 +388,16 @@ pub mod mock {
 
         pub fn as_table(mut self) -> Self {
             self.0.ty = DataType::Table;
+            self.0.view = None;
+            self
+        }
+
+        pub fn as_view_of(mut self, sql: impl Into<String>, materialized: bool) -> Self {
+            self.0.ty = DataType::View;
+            self.0.view = Some(ViewDef {
+                sql: sql.into(),
+                materialized,
+            });
             self
         }
 
@@ -224,6 +408,7 @@ pub mod mock {
                 null: false,
                 primary_key: true,
                 updatable: true,
+                default: None,
             });
             self
         }
@@ -235,6 +420,7 @@ pub mod mock {
                 null: false,
                 primary_key: false,
                 updatable: true,
+                default: None,
             });
             self
         }
@@ -250,10 +436,48 @@ pub mod mock {
                 null: true,
                 primary_key: false,
                 updatable: true,
+                default: None,
+            });
+            self
+        }
+
+        pub fn with_check(
+            mut self,
+            column: Option<&str>,
+            name: Option<&str>,
+            expr: impl Into<String>,
+        ) -> Self {
+            self.0.checks.push(CheckDef {
+                column: column.map(|c| c.to_string()),
+                name: name.map(|n| n.to_string()),
+                expr: expr.into(),
             });
             self
         }
 
+        pub fn with_foreign_key(
+            mut self,
+            column: &str,
+            foreign_table: &str,
+            foreign_column: &str,
+            on_delete: &str,
+        ) -> Self {
+            self.0.foreign_keys.push(ForeignKeyDef {
+                name: None,
+                columns: vec![column.to_string()],
+                foreign_table: foreign_table.to_string(),
+                foreign_columns: vec![foreign_column.to_string()],
+                on_delete: on_delete.to_string(),
+                on_update: "NO ACTION".to_string(),
+            });
+            self
+        }
+
+        pub fn with_column_def(mut self, column: MockColumnDef) -> Self {
+            self.0.columns.push(column.build());
+            self
+        }
+
         pub fn build(self) -> TableDef {
             self.0
         }
@@ -4,6 +4,9 @@ pub(crate) struct TableScan {
     table_scan_sql: fn() -> &'static str,
     single_table_scan_sql: fn() -> &'static str,
     fk_scan_sql: fn() -> &'static str,
+    view_scan_sql: fn() -> &'static str,
+    check_scan_sql: Option<fn() -> &'static str>,
+    foreign_key_scan_sql: Option<fn() -> &'static str>,
 }
 
 impl TableScan {
@@ -13,21 +16,33 @@ impl TableScan {
                 table_scan_sql: MySql::table_scan_sql,
                 single_table_scan_sql: MySql::single_table_scan_sql,
                 fk_scan_sql: MySql::fk_scan_sql,
+                view_scan_sql: MySql::view_scan_sql,
+                check_scan_sql: None,
+                foreign_key_scan_sql: None,
             },
             Syntax::Postgres => TableScan {
                 table_scan_sql: Postgres::table_scan_sql,
                 single_table_scan_sql: Postgres::single_table_scan_sql,
                 fk_scan_sql: Postgres::fk_scan_sql,
+                view_scan_sql: Postgres::view_scan_sql,
+                check_scan_sql: None,
+                foreign_key_scan_sql: None,
             },
             Syntax::Sqlite => TableScan {
                 table_scan_sql: Sqlite::table_scan_sql,
                 single_table_scan_sql: Sqlite::single_table_scan_sql,
                 fk_scan_sql: Sqlite::fk_scan_sql,
+                view_scan_sql: Sqlite::view_scan_sql,
+                check_scan_sql: Some(Sqlite::check_scan_sql),
+                foreign_key_scan_sql: Some(Sqlite::foreign_key_scan_sql),
             },
             Syntax::Mssql => TableScan {
                 table_scan_sql: Mssql::table_scan_sql,
                 single_table_scan_sql: Mssql::single_table_scan_sql,
                 fk_scan_sql: Mssql::fk_scan_sql,
+                view_scan_sql: Mssql::view_scan_sql,
+                check_scan_sql: None,
+                foreign_key_scan_sql: None,
             },
         }
     }
@@ -43,6 +58,22 @@ impl TableScan {
     pub(crate) fn fk_scan_sql(&self) -> &'static str {
         (self.fk_scan_sql)()
     }
+
+    pub(crate) fn view_scan_sql(&self) -> &'static str {
+        (self.view_scan_sql)()
+    }
+
+    /// The SQL to read the CHECK constraints of each table.
+    /// Only Sqlite needs them, to keep them when a table is rebuilt
+    pub(crate) fn check_scan_sql(&self) -> Option<&'static str> {
+        self.check_scan_sql.map(|f| f())
+    }
+
+    /// The SQL to read the foreign keys of each table in full.
+    /// Only Sqlite needs them, to keep them when a table is rebuilt
+    pub(crate) fn foreign_key_scan_sql(&self) -> Option<&'static str> {
+        self.foreign_key_scan_sql.map(|f| f())
+    }
 }
 
 struct Postgres;
@@ -56,6 +87,9 @@ impl Postgres {
     fn fk_scan_sql() -> &'static str {
         include_str!("./postgres_fk.sql")
     }
+    fn view_scan_sql() -> &'static str {
+        include_str!("./postgres_views.sql")
+    }
 }
 
 struct MySql;
@@ -69,6 +103,9 @@ impl MySql {
     fn fk_scan_sql() -> &'static str {
         include_str!("./mysql_fk.sql")
     }
+    fn view_scan_sql() -> &'static str {
+        include_str!("./mysql_views.sql")
+    }
 }
 
 struct Mssql;
@@ -82,6 +119,9 @@ impl Mssql {
     fn fk_scan_sql() -> &'static str {
         include_str!("./mssql_fk.sql")
     }
+    fn view_scan_sql() -> &'static str {
+        include_str!("./mssql_views.sql")
+    }
 }
 
 struct Sqlite;
@@ -95,4 +135,13 @@ impl Sqlite {
     fn fk_scan_sql() -> &'static str {
         include_str!("./sqlite_fk.sql")
     }
+    fn view_scan_sql() -> &'static str {
+        include_str!("./sqlite_views.sql")
+    }
+    fn check_scan_sql() -> &'static str {
+        include_str!("./sqlite_checks.sql")
+    }
+    fn foreign_key_scan_sql() -> &'static str {
+        include_str!("./sqlite_foreign_keys.sql")
+    }
 }
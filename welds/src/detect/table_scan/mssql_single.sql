@@ -5,16 +5,17 @@ SELECT
     c.name column_name,
     t.Name column_type,
     CAST(c.is_nullable as INT) as is_nullable,
-    CAST(ISNULL(i.is_primary_key, 0) as INT) as is_primary_key,
-    CAST(1 as INT) as is_updatable
+    CAST(CASE WHEN EXISTS (
+        SELECT 1 FROM sys.index_columns ic
+        JOIN sys.indexes i ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+        WHERE i.is_primary_key = 1 AND ic.object_id = c.object_id AND ic.column_id = c.column_id
+    ) THEN 1 ELSE 0 END as INT) as is_primary_key,
+    CAST(1 as INT) as is_updatable,
+    OBJECT_DEFINITION(c.default_object_id) as column_default
 FROM
     sys.columns c
 INNER JOIN
     sys.types t ON c.user_type_id = t.user_type_id
-LEFT OUTER JOIN
-    sys.index_columns ic ON ic.object_id = c.object_id AND ic.column_id = c.column_id
-LEFT OUTER JOIN
-    sys.indexes i ON ic.object_id = i.object_id AND ic.index_id = i.index_id
 JOIN (
 SELECT table_schema, table_name, 'table' as ty FROM INFORMATION_SCHEMA.TABLES WHERE TABLE_NAME NOT in (select name from sys.objects where type = 'U' and is_ms_shipped = 1) AND TABLE_TYPE='BASE TABLE'
 UNION
@@ -0,0 +1,9 @@
+SELECT
+    s.name as table_schema,
+    v.name as table_name,
+    m.definition as view_sql,
+    CAST(0 as INT) as is_materialized
+FROM sys.views v
+JOIN sys.schemas s ON s.schema_id = v.schema_id
+JOIN sys.sql_modules m ON m.object_id = v.object_id
+WHERE v.is_ms_shipped = 0
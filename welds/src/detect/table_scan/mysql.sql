@@ -6,7 +6,12 @@ cast(col.column_name as CHAR ) as column_name,
 cast(col.data_type as CHAR ) as data_type,
 cast(col.is_nullable='YES' AS SIGNED INTEGER) as is_nullable,
 case when column_key= 'PRI' then 1 else 0 end as is_primary_key,
-1 as is_updatable
+1 as is_updatable,
+cast(case
+  when col.column_default is null then null
+  when col.extra like '%DEFAULT_GENERATED%' then concat('(', col.column_default, ')')
+  else quote(col.column_default)
+end as CHAR ) as column_default
 FROM (
 select 
 table_name, table_schema, 'table' as ty 
@@ -0,0 +1,7 @@
+SELECT
+cast(table_schema as CHAR ) as table_schema,
+cast(table_name as CHAR ) as table_name,
+cast(concat('CREATE VIEW ', table_schema, '.', table_name, ' AS ', view_definition) as CHAR ) as view_sql,
+0 as is_materialized
+FROM information_schema.views
+WHERE table_schema != 'sys' AND table_schema != 'performance_schema' AND table_schema != 'information_schema' AND table_schema != 'mysql'
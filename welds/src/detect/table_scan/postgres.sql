@@ -1,4 +1,4 @@
-
+SELECT * FROM (
 SELECT 
     things.schemaname as schema,
     things.tablename as table_name,
@@ -21,7 +21,8 @@ WHERE
     AND ccu.column_name = col.column_name
 
 ) is not null then 1 else 0 end as is_primary_key,
-    case when col.is_updatable = 'YES' then 1 else 0 end as is_updatable
+    case when col.is_updatable = 'YES' then 1 else 0 end as is_updatable,
+    col.column_default
 FROM (
     SELECT schemaname, tablename, 'table' as ty FROM pg_catalog.pg_tables 
       WHERE schemaname != 'pg_catalog' 
@@ -41,4 +42,22 @@ FROM (
       AND table_schema != 'timescaledb_experimental'
 ) things
 join information_schema.columns col on col.table_schema = things.schemaname AND col.table_name = things.tablename
-ORDER BY things.schemaname, things.tablename, is_primary_key desc, col.column_name
+UNION ALL
+-- materialized views are not in information_schema
+SELECT
+    n.nspname as schema,
+    c.relname as table_name,
+    'view' as ty,
+    a.attname as column_name,
+    t.typname as column_type,
+    case when a.attnotnull then 0 else 1 end as is_nullable,
+    0 as is_primary_key,
+    0 as is_updatable,
+    NULL as column_default
+FROM pg_catalog.pg_class c
+JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+JOIN pg_catalog.pg_attribute a ON a.attrelid = c.oid AND a.attnum > 0 AND NOT a.attisdropped
+JOIN pg_catalog.pg_type t ON t.oid = a.atttypid
+WHERE c.relkind = 'm'
+) cols
+ORDER BY schema, table_name, is_primary_key desc, column_name
@@ -0,0 +1,14 @@
+SELECT
+    schemaname::text as schema,
+    viewname::text as view_name,
+    'CREATE VIEW ' || schemaname || '.' || viewname || ' AS ' || rtrim(definition, E'; \n') as view_sql,
+    0 as is_materialized
+FROM pg_catalog.pg_views
+WHERE schemaname != 'pg_catalog' AND schemaname != 'information_schema'
+UNION ALL
+SELECT
+    schemaname::text as schema,
+    matviewname::text as view_name,
+    'CREATE MATERIALIZED VIEW ' || schemaname || '.' || matviewname || ' AS ' || rtrim(definition, E'; \n') as view_sql,
+    1 as is_materialized
+FROM pg_catalog.pg_matviews
@@ -6,7 +6,8 @@ select
   table_info.type as column_type,
   NOT table_info."notnull" as is_nullable,
   table_info.pk as is_primary_key,
-  1 as is_updatable
+  1 as is_updatable,
+  table_info.dflt_value as column_default
 from
   sqlite_master
   join pragma_table_info(sqlite_master.name) as table_info
@@ -0,0 +1,6 @@
+SELECT
+    null as schemaname,
+    name,
+    sql as table_sql
+FROM sqlite_master
+WHERE type = 'table'
@@ -0,0 +1,14 @@
+SELECT
+    null as schemaname,
+    m.name,
+    p.id,
+    p."from" as from_column,
+    p."table" as to_table,
+    p."to" as to_column,
+    p.on_delete,
+    p.on_update
+FROM
+    sqlite_master m
+    JOIN pragma_foreign_key_list(m.name) p
+WHERE m.type = 'table'
+ORDER BY m.name, p.id, p.seq
@@ -0,0 +1,7 @@
+SELECT
+    null as schemaname,
+    name,
+    sql as view_sql,
+    0 as is_materialized
+FROM sqlite_master
+WHERE type = 'view'
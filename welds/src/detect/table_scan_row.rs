@@ -11,6 +11,7 @@ pub struct TableScanRow {
     pub(super) is_nullable: i32,
     pub(super) is_primary_key: i32,
     pub(super) is_updatable: i32,
+    pub(super) column_default: Option<String>,
 }
 
 impl TryFrom<Row> for TableScanRow {
@@ -25,6 +26,7 @@ impl TryFrom<Row> for TableScanRow {
             is_nullable: row.get_by_position(5)?,
             is_primary_key: row.get_by_position(6)?,
             is_updatable: row.get_by_position(7)?,
+            column_default: row.get_by_position(8)?,
         })
     }
 }
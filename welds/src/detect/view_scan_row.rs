@@ -0,0 +1,30 @@
+use crate::model_traits::TableIdent;
+use crate::Row;
+
+pub struct ViewScanRow {
+    pub(super) schema: Option<String>,
+    pub(super) view_name: String,
+    pub(super) view_sql: String,
+    pub(super) is_materialized: i32,
+}
+
+impl TryFrom<Row> for ViewScanRow {
+    type Error = crate::WeldsError;
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        Ok(ViewScanRow {
+            schema: row.get_by_position(0)?,
+            view_name: row.get_by_position(1)?,
+            view_sql: row.get_by_position(2)?,
+            is_materialized: row.get_by_position(3)?,
+        })
+    }
+}
+
+impl ViewScanRow {
+    pub fn ident(&self) -> TableIdent {
+        TableIdent {
+            schema: self.schema.clone(),
+            name: self.view_name.clone(),
+        }
+    }
+}
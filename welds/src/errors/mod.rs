@@ -10,6 +10,8 @@ pub enum WeldsError {
     Database(ConnError),
     #[error("Could not find tablebase table {0}")]
     MissingTable(TableIdent),
+    #[error("Not a valid table or schema name: {0}")]
+    InvalidTableName(String),
     #[error("The Database column is not present: {0}")]
     MigrationError(String),
     #[error("Migration Error: {0}")]
@@ -28,6 +30,10 @@ pub enum WeldsError {
         "Multiple tables exist with this table. Use `table_search` to search return all results"
     )]
     AmbiguousTable,
+    #[error("The row doesn't match the discriminator of any variant of {0}")]
+    UnknownDiscriminator(String),
+    #[error("Fixture Error: {0}")]
+    Fixture(String),
     #[error("Anyhow Error")]
     Other(#[from] anyhow::Error),
 }
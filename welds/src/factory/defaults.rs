@@ -0,0 +1,66 @@
+/// The value a factory uses for a field when it isn't given one.
+///
+/// Implement this for your own types to use them in a `WeldsFactory`
+pub trait FactoryDefault {
+    fn factory_default(field: &str, sequence: u64) -> Self;
+}
+
+/// A value made from the sequence number of a factory. Used by `#[factory(sequence)]`
+pub trait FromSequence {
+    fn from_sequence(sequence: u64) -> Self;
+}
+
+macro_rules! number_defaults {
+    ($($t:ty),*) => {
+        $(
+            impl FactoryDefault for $t {
+                fn factory_default(_field: &str, _sequence: u64) -> Self {
+                    0 as $t
+                }
+            }
+            impl FromSequence for $t {
+                fn from_sequence(sequence: u64) -> Self {
+                    sequence as $t
+                }
+            }
+        )*
+    };
+}
+
+number_defaults!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl FactoryDefault for bool {
+    fn factory_default(_field: &str, _sequence: u64) -> Self {
+        false
+    }
+}
+
+impl FactoryDefault for String {
+    fn factory_default(field: &str, sequence: u64) -> Self {
+        format!("{field} {sequence}")
+    }
+}
+
+impl FromSequence for String {
+    fn from_sequence(sequence: u64) -> Self {
+        sequence.to_string()
+    }
+}
+
+impl<T> FactoryDefault for Option<T> {
+    fn factory_default(_field: &str, _sequence: u64) -> Self {
+        None
+    }
+}
+
+impl<T: FromSequence> FromSequence for Option<T> {
+    fn from_sequence(sequence: u64) -> Self {
+        Some(T::from_sequence(sequence))
+    }
+}
+
+impl<T> FactoryDefault for Vec<T> {
+    fn factory_default(_field: &str, _sequence: u64) -> Self {
+        Vec::default()
+    }
+}
@@ -0,0 +1,170 @@
+use crate::errors::Result;
+use crate::model_traits::hooks::{AfterCreate, AfterUpdate, BeforeCreate, BeforeUpdate};
+use crate::model_traits::{
+    ColumnDefaultCheck, HasSchema, TableColumns, TableInfo, UpdateFromRow, WriteToArgs,
+};
+use crate::state::DbState;
+use crate::Client;
+use std::future::Future;
+use std::pin::Pin;
+
+mod defaults;
+pub use defaults::{FactoryDefault, FromSequence};
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Implemented by `#[derive(WeldsFactory)]`.
+///
+/// Builds models with test data, filling in every field so you only need to set the ones you care about.
+///
+/// ```rust,ignore
+/// #[derive(Debug, WeldsModel, WeldsFactory)]
+/// #[welds(table = "users")]
+/// #[welds(BelongsTo(team, Team, "team_id"))]
+/// #[factory(variant(admin, make_admin))]
+/// pub struct User {
+///     #[welds(primary_key)]
+///     pub id: i32,
+///     pub team_id: i32,
+///     #[factory(sequence = "user{n}@example.com")]
+///     pub email: String,
+///     #[factory(default = "\"Bob\".to_string()")]
+///     pub name: String,
+///     pub admin: bool,
+/// }
+///
+/// fn make_admin(user: &mut User) {
+///     user.admin = true;
+/// }
+///
+/// // creates a Team, then a User that belongs to it
+/// let user = User::factory().variant("admin").create(&client).await?;
+/// let users = User::factory().with(|u| u.team_id = user.team_id).create_many(&client, 3).await?;
+/// ```
+///
+/// Fields are filled in with:
+/// - `#[factory(default = "expr")]` the expression given
+/// - `#[factory(sequence)]` a number that goes up each time a model is built
+/// - `#[factory(sequence = "text{n}")]` the text with `{n}` replaced with the sequence number
+/// - `None` for `Option` fields
+/// - otherwise the `FactoryDefault` for the type of the field
+///
+/// When created, every required `BelongsTo` whose foreign key is still the default value
+/// will have its parent created first, using the parent's factory.
+pub trait WeldsFactory: HasSchema + Sized + 'static {
+    /// The type of the primary key, used to fill in the foreign keys of children
+    type Key;
+
+    /// The next number in the sequence for this model
+    fn next_sequence() -> u64;
+
+    /// Builds a new model, filling in every field
+    fn factory_build(sequence: u64) -> Self;
+
+    /// Applies a named variant. returns false if there isn't a variant with this name
+    fn factory_variant(&mut self, name: &str) -> bool;
+
+    /// The value of the primary key
+    fn factory_key(&self) -> Self::Key;
+
+    /// Creates the required BelongsTo parents of this model that have not been set
+    fn factory_create_parents<'a>(
+        &'a mut self,
+        client: &'a dyn Client,
+    ) -> BoxFuture<'a, Result<()>>;
+
+    /// Returns a factory to build this model
+    fn factory() -> Factory<Self> {
+        Factory::new()
+    }
+}
+
+type Change<T> = Box<dyn Fn(&mut T) + Send + Sync>;
+
+/// Builds (or creates in the database) models using a `WeldsFactory`.
+pub struct Factory<T> {
+    changes: Vec<Change<T>>,
+}
+
+impl<T> Default for Factory<T>
+where
+    T: WeldsFactory,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Factory<T>
+where
+    T: WeldsFactory,
+{
+    pub fn new() -> Self {
+        Self {
+            changes: Vec::default(),
+        }
+    }
+
+    /// Changes the model after it is built
+    pub fn with(mut self, change: impl Fn(&mut T) + Send + Sync + 'static) -> Self {
+        self.changes.push(Box::new(change));
+        self
+    }
+
+    /// Applies a variant defined with `#[factory(variant(name, fn_to_call))]`
+    ///
+    /// NOTE: panics when the model is built if there is no variant with this name
+    pub fn variant(self, name: &'static str) -> Self {
+        self.with(move |model: &mut T| {
+            if !model.factory_variant(name) {
+                let model = std::any::type_name::<T>();
+                panic!("{model} does not have a factory variant named: {name}");
+            }
+        })
+    }
+
+    /// Builds a model without saving it
+    pub fn build(&self) -> T {
+        let mut model = T::factory_build(T::next_sequence());
+        for change in &self.changes {
+            change(&mut model);
+        }
+        model
+    }
+
+    /// Builds a list of models without saving them
+    pub fn build_many(&self, count: usize) -> Vec<T> {
+        (0..count).map(|_| self.build()).collect()
+    }
+
+    /// Builds a model, creates its parents, and saves it to the database
+    pub async fn create(&self, client: &dyn Client) -> Result<DbState<T>>
+    where
+        T: WriteToArgs + ColumnDefaultCheck + UpdateFromRow,
+        T: BeforeCreate + AfterCreate + BeforeUpdate + AfterUpdate,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let mut model = self.build();
+        model.factory_create_parents(client).await?;
+        let mut state = DbState::new_uncreated(model);
+        state.save(client).await?;
+        Ok(state)
+    }
+
+    /// Creates a list of models in the database
+    pub async fn create_many(&self, client: &dyn Client, count: usize) -> Result<Vec<DbState<T>>>
+    where
+        T: WriteToArgs + ColumnDefaultCheck + UpdateFromRow,
+        T: BeforeCreate + AfterCreate + BeforeUpdate + AfterUpdate,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let mut list = Vec::with_capacity(count);
+        for _ in 0..count {
+            list.push(self.create(client).await?);
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod tests;
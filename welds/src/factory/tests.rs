@@ -0,0 +1,133 @@
+use super::*;
+use crate::prelude::*;
+use crate::Syntax;
+use welds_connections::noop::{MockClient, MockRow, ParamValue};
+
+#[derive(Debug, WeldsModel, WeldsFactory)]
+#[welds(table = "teams")]
+#[welds(HasMany(users, User, "team_id"))]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Team {
+    #[welds(primary_key)]
+    pub id: i32,
+    #[factory(default = "\"Tigers\".to_string()")]
+    pub name: String,
+}
+
+#[derive(Debug, WeldsModel, WeldsFactory)]
+#[welds(table = "users")]
+#[welds(BelongsTo(team, Team, "team_id"))]
+#[welds_path(crate)] // needed only within the welds crate.
+#[factory(variant(admin, make_admin))]
+struct User {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub team_id: i32,
+    #[factory(sequence = "user{n}@example.com")]
+    pub email: String,
+    pub nickname: Option<String>,
+    pub admin: bool,
+}
+
+fn make_admin(user: &mut User) {
+    user.admin = true;
+}
+
+fn team_row(id: i32) -> MockRow {
+    MockRow::new()
+        .column("id", id)
+        .column("name", "Tigers".to_string())
+}
+
+fn user_row(id: i32, team_id: i32) -> MockRow {
+    MockRow::new()
+        .column("id", id)
+        .column("team_id", team_id)
+        .column("email", "user@example.com".to_string())
+        .column("nickname", None::<String>)
+        .column("admin", false)
+}
+
+#[test]
+fn should_fill_in_every_field_when_built() {
+    let user = User::factory().build();
+    assert_eq!(user.id, 0);
+    assert_eq!(user.team_id, 0);
+    assert!(user.email.starts_with("user"));
+    assert!(user.email.ends_with("@example.com"));
+    assert_eq!(user.nickname, None);
+    assert!(!user.admin);
+    assert_eq!(Team::factory().build().name, "Tigers");
+}
+
+#[test]
+fn should_use_a_new_sequence_number_for_each_model() {
+    let users = User::factory().build_many(2);
+    assert_ne!(users[0].email, users[1].email);
+}
+
+#[test]
+fn should_apply_variants_and_changes() {
+    let user = User::factory()
+        .variant("admin")
+        .with(|u| u.nickname = Some("bob".to_string()))
+        .build();
+    assert!(user.admin);
+    assert_eq!(user.nickname.as_deref(), Some("bob"));
+    let mut user = User::factory().build();
+    assert!(!user.factory_variant("superuser"));
+}
+
+#[test]
+fn should_create_the_parent_before_the_model() {
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Postgres);
+        client.expect_rows("INSERT INTO teams", vec![team_row(7)]);
+        client.expect_rows("INSERT INTO users", vec![user_row(3, 7)]);
+
+        let user = User::factory().variant("admin").create(&client).await;
+        let user = user.unwrap();
+        client.assert_all_consumed();
+        assert_eq!(user.id, 3);
+        assert_eq!(user.team_id, 7);
+
+        let history = client.history();
+        assert_eq!(history[0].params, vec![ParamValue::from("Tigers")]);
+        // team_id, email, nickname, admin
+        let params = &history[1].params;
+        assert_eq!(params[0], ParamValue::Int(7));
+        assert_eq!(params[2], ParamValue::Null);
+        // bools are read using the sqlite encoding
+        assert_eq!(params[3], ParamValue::Int(1));
+    });
+}
+
+#[test]
+fn should_not_create_a_parent_that_was_set() {
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Postgres);
+        client.expect_rows("INSERT INTO users", vec![user_row(3, 9)]);
+        client.expect_rows("INSERT INTO users", vec![user_row(4, 9)]);
+
+        let users = User::factory()
+            .with(|u| u.team_id = 9)
+            .create_many(&client, 2)
+            .await
+            .unwrap();
+        client.assert_all_consumed();
+        assert_eq!(users.len(), 2);
+        let history = client.history();
+        assert!(history.iter().all(|s| s.params[0] == ParamValue::Int(9)));
+    });
+}
+
+#[test]
+fn should_return_the_error_when_the_parent_fails_to_create() {
+    futures::executor::block_on(async move {
+        let client = MockClient::new(Syntax::Postgres);
+        client.expect_error("INSERT INTO teams", "no teams today");
+        let result = User::factory().create(&client).await;
+        assert!(result.is_err());
+        assert_eq!(client.history().len(), 1);
+    });
+}
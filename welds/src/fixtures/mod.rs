@@ -0,0 +1,388 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::{Column, HasSchema, TableColumns, TableInfo};
+use crate::relations::{BelongsToInfo, ListRelations};
+use crate::writers::{ColumnWriter, NextParam};
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use welds_connections::{Client, Param, Syntax};
+
+mod parse;
+mod value;
+pub use value::FixtureValue;
+
+/// Test data loaded from YAML or JSON files, that can be inserted into a database.
+///
+/// Fixture files are keyed by table, then by a label for each row:
+/// ```yaml
+/// sellers:
+///   alice:
+///     name: Alice
+/// products:
+///   widget:
+///     name: Widget
+///     seller: alice   # a BelongsTo relationship, set to the id of the `alice` fixture
+/// ```
+///
+/// Register the models the fixtures are for, then insert them.
+/// Parents are inserted before children.
+/// ```rust,ignore
+/// let fixtures = Fixtures::load("tests/fixtures/shop.yml")?
+///     .model::<Seller>()
+///     .model::<Product>();
+/// fixtures.wipe(&client).await?;
+/// fixtures.insert(&client).await?;
+/// let alice_id = fixtures.id::<Seller>("alice");
+/// ```
+///
+/// If a fixture doesn't give a value for an integer primary key,
+/// an id is generated from its label. The same label always gets the same id.
+///
+/// NOTE: Values are sent to the database as the rust type of the column when it is a number or bool.
+/// All other values are sent as text. On Postgres the text is cast to the type of the column in the database.
+#[derive(Default)]
+pub struct Fixtures {
+    tables: Vec<(String, Vec<Fixture>)>,
+    models: Vec<ModelInfo>,
+}
+
+/// A single row in a fixture file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixture {
+    label: String,
+    values: Vec<(String, FixtureValue)>,
+}
+
+impl Fixture {
+    pub(crate) fn new(label: &str, values: Vec<(String, FixtureValue)>) -> Self {
+        Self {
+            label: label.to_string(),
+            values,
+        }
+    }
+
+    /// The label this fixture was given in the file
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The value given for a column (or relationship)
+    pub fn get(&self, key: &str) -> Option<&FixtureValue> {
+        self.values.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+}
+
+type TruncateFn =
+    for<'c> fn(&'c dyn Client) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'c>>;
+
+struct ModelInfo {
+    identifier: &'static [&'static str],
+    columns: Vec<Column>,
+    primary_keys: Vec<Column>,
+    belongs_to: Vec<BelongsToInfo>,
+    truncate: TruncateFn,
+}
+
+fn truncate<T>(client: &dyn Client) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>>
+where
+    T: HasSchema + 'static,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    Box::pin(crate::query::truncate::truncate::<T>(client))
+}
+
+impl Fixtures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads fixtures from a `.json`, `.yml`, or `.yaml` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| WeldsError::Fixture(format!("unable to read {}: {e}", path.display())))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&text),
+            Some("yml") | Some("yaml") => Self::from_yaml(&text),
+            _ => Err(WeldsError::Fixture(format!(
+                "unknown fixture file type: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(Self::new().with_tables(parse::json(json)?))
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        Ok(Self::new().with_tables(parse::yaml(yaml)?))
+    }
+
+    /// Adds the fixtures (and models) from another set of fixtures to this one
+    pub fn merge(mut self, other: Fixtures) -> Self {
+        self.models.extend(other.models);
+        self.with_tables(other.tables)
+    }
+
+    /// Registers a model, so its fixtures will be inserted/wiped
+    pub fn model<T>(mut self) -> Self
+    where
+        T: HasSchema + ListRelations + 'static,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.models.push(ModelInfo {
+            identifier: <T as HasSchema>::Schema::identifier(),
+            columns: <T as HasSchema>::Schema::columns(),
+            primary_keys: <T as HasSchema>::Schema::primary_keys(),
+            belongs_to: T::belongs_to(),
+            truncate: truncate::<T>,
+        });
+        self
+    }
+
+    /// Returns the fixtures for the table of a model
+    pub fn fixtures<T>(&self) -> &[Fixture]
+    where
+        T: HasSchema,
+        <T as HasSchema>::Schema: TableInfo,
+    {
+        self.table(<T as HasSchema>::Schema::identifier())
+    }
+
+    /// Returns the id of the fixture with this label.
+    /// Either the id given in the file, or the one generated for it
+    pub fn id<T>(&self, label: &str) -> Option<FixtureValue>
+    where
+        T: HasSchema,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let identifier = <T as HasSchema>::Schema::identifier();
+        let pks = <T as HasSchema>::Schema::primary_keys();
+        self.id_of(identifier, &pks, label).ok()
+    }
+
+    /// Inserts the fixtures of all the registered models.
+    /// Models are inserted in an order so that parents are inserted before their children.
+    pub async fn insert(&self, client: &dyn Client) -> Result<()> {
+        let syntax = client.syntax();
+        for model in self.ordered() {
+            let fixtures = self.table(model.identifier);
+            if fixtures.is_empty() {
+                continue;
+            }
+            let identifier = model.identifier.join(".");
+            let casts = text_casts(model, client).await?;
+            for fixture in fixtures {
+                let values = self.resolve(model, fixture)?;
+
+                let col_writer = ColumnWriter::new(syntax);
+                let next_params = NextParam::new(syntax);
+                let mut columns = Vec::default();
+                let mut placeholders = Vec::default();
+                let mut params: Vec<value::BoxedParam> = Vec::default();
+                for (column, value) in &values {
+                    columns.push(col_writer.excape(column.name()));
+                    let placeholder = next_params.next();
+                    let cast = casts.iter().find(|(c, _)| c == column.name());
+                    let placeholder = match cast {
+                        Some((_, ty)) if FixtureValue::sent_as_text(column) => {
+                            format!("CAST({placeholder} AS {ty})")
+                        }
+                        _ => placeholder,
+                    };
+                    placeholders.push(placeholder);
+                    params.push(value.to_param(column)?);
+                }
+
+                let mut sql = format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    identifier,
+                    columns.join(", "),
+                    placeholders.join(", ")
+                );
+
+                // Mssql only allows a value for an identity column with IDENTITY_INSERT on
+                if let (Syntax::Mssql, [pk]) = (syntax, model.primary_keys.as_slice()) {
+                    if values.iter().any(|(c, _)| c.name() == pk.name()) {
+                        sql = mssql_identity_insert(&identifier, pk.name(), &sql);
+                    }
+                }
+
+                let args: Vec<&(dyn Param + Sync)> = params
+                    .iter()
+                    .map(|p| p.as_ref() as &(dyn Param + Sync))
+                    .collect();
+                client.execute(&sql, &args).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes all the rows from the tables of the registered models.
+    /// Children are removed before their parents.
+    pub async fn wipe(&self, client: &dyn Client) -> Result<()> {
+        for model in self.ordered().iter().rev() {
+            (model.truncate)(client).await?;
+        }
+        Ok(())
+    }
+
+    fn with_tables(mut self, tables: parse::Parsed) -> Self {
+        for (table, mut fixtures) in tables {
+            match self.tables.iter_mut().find(|(t, _)| t == &table) {
+                Some((_, existing)) => existing.append(&mut fixtures),
+                None => self.tables.push((table, fixtures)),
+            }
+        }
+        self
+    }
+
+    /// The fixtures for a table. The table can be keyed in the file with or without its schema
+    fn table(&self, identifier: &[&str]) -> &[Fixture] {
+        let full = identifier.join(".");
+        let name = identifier.last().copied().unwrap_or_default();
+        self.tables
+            .iter()
+            .find(|(t, _)| t == &full)
+            .or_else(|| self.tables.iter().find(|(t, _)| t == name))
+            .map(|(_, fixtures)| fixtures.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The registered models, with parents before their children
+    fn ordered(&self) -> Vec<&ModelInfo> {
+        let mut ordered: Vec<&ModelInfo> = Vec::default();
+        let mut remaining: Vec<&ModelInfo> = self.models.iter().collect();
+        while !remaining.is_empty() {
+            let ready = remaining.iter().position(|model| {
+                model.belongs_to.iter().all(|rel| {
+                    rel.table == model.identifier
+                        || ordered.iter().any(|m| m.identifier == rel.table)
+                        || !remaining.iter().any(|m| m.identifier == rel.table)
+                })
+            });
+            // a loop in the relationships, fall back to the order they were registered
+            let next = ready.unwrap_or(0);
+            ordered.push(remaining.remove(next));
+        }
+        ordered
+    }
+
+    /// Returns the columns and values to insert for a fixture,
+    /// resolving references to other fixtures and generating ids.
+    fn resolve(&self, model: &ModelInfo, fixture: &Fixture) -> Result<Vec<(Column, FixtureValue)>> {
+        let mut values = Vec::default();
+        for (key, value) in &fixture.values {
+            if let Some(column) = model.columns.iter().find(|c| c.name() == key) {
+                values.push((column.clone(), value.clone()));
+                continue;
+            }
+            let relation = model.belongs_to.iter().find(|r| r.field == key);
+            let relation = relation.ok_or_else(|| {
+                WeldsError::Fixture(format!(
+                    "{} is not a column or relationship of {}",
+                    key,
+                    model.identifier.join(".")
+                ))
+            })?;
+            let column = model
+                .columns
+                .iter()
+                .find(|c| c.name() == relation.foreign_key)
+                .ok_or_else(|| {
+                    WeldsError::Fixture(format!("missing column {}", relation.foreign_key))
+                })?;
+            let id = match value {
+                FixtureValue::Null => FixtureValue::Null,
+                FixtureValue::Text(label) => {
+                    self.id_of(relation.table, &relation.primary_keys, label)?
+                }
+                other => other.clone(),
+            };
+            values.push((column.clone(), id));
+        }
+
+        // Generate an id if one wasn't given
+        if let [pk] = model.primary_keys.as_slice() {
+            if !values.iter().any(|(c, _)| c.name() == pk.name()) && is_integer(pk) {
+                values.push((pk.clone(), FixtureValue::Int(label_id(&fixture.label))));
+            }
+        }
+        Ok(values)
+    }
+
+    fn id_of(&self, table: &[&str], pks: &[Column], label: &str) -> Result<FixtureValue> {
+        let missing = || {
+            WeldsError::Fixture(format!(
+                "there is no fixture {} for {}",
+                label,
+                table.join(".")
+            ))
+        };
+        let fixture = self.table(table).iter().find(|f| f.label == label);
+        let fixture = fixture.ok_or_else(missing)?;
+        let pk = match pks {
+            [pk] => pk,
+            _ => return Err(WeldsError::NoPrimaryKey),
+        };
+        if let Some(id) = fixture.get(pk.name()) {
+            return Ok(id.clone());
+        }
+        if is_integer(pk) {
+            return Ok(FixtureValue::Int(label_id(label)));
+        }
+        Err(WeldsError::Fixture(format!(
+            "the fixture {} for {} needs a value for {}",
+            label,
+            table.join("."),
+            pk.name()
+        )))
+    }
+}
+
+/// The database types of the columns of a model that text values need to be cast to.
+/// Postgres won't convert a text param to a uuid, date, json, enum ... column on its own.
+async fn text_casts(model: &ModelInfo, client: &dyn Client) -> Result<Vec<(String, String)>> {
+    if client.syntax() != Syntax::Postgres {
+        return Ok(Vec::default());
+    }
+    let (schema, name) = match model.identifier {
+        [schema, name] => (Some(*schema), *name),
+        [name] => (None, *name),
+        _ => return Ok(Vec::default()),
+    };
+    let table = crate::detect::find_table(schema, name, client).await?;
+    let columns = table.iter().flat_map(|t| t.columns());
+    let casts = columns
+        .filter(|c| !matches!(c.ty().to_lowercase().as_str(), "text" | "varchar"))
+        .map(|c| (c.name().to_string(), c.ty().to_string()))
+        .collect();
+    Ok(casts)
+}
+
+/// Wraps an insert so it can give a value for the identity column of a table.
+/// The insert is left as is if the column isn't an identity column
+fn mssql_identity_insert(table: &str, column: &str, insert: &str) -> String {
+    let check = format!("IF COLUMNPROPERTY(OBJECT_ID('{table}'), '{column}', 'IsIdentity') = 1");
+    format!(
+        "{check} SET IDENTITY_INSERT {table} ON; {insert}; {check} SET IDENTITY_INSERT {table} OFF"
+    )
+}
+
+fn is_integer(column: &Column) -> bool {
+    matches!(column.rust_type(), "i32" | "i64")
+}
+
+/// A stable id for a fixture label (FNV-1a), that fits in an i32
+fn label_id(label: &str) -> i64 {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in label.bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    (hash % 0x3fff_ffff) as i64 + 1
+}
+
+#[cfg(test)]
+mod tests;
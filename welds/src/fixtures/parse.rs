@@ -0,0 +1,136 @@
+use super::{Fixture, FixtureValue};
+use crate::errors::{Result, WeldsError};
+
+/// tables in the order they were found in the file
+pub(crate) type Parsed = Vec<(String, Vec<Fixture>)>;
+
+/// Reads fixtures from JSON in the format
+/// `{ "table": { "label": { "column": value } } }`
+pub(crate) fn json(input: &str) -> Result<Parsed> {
+    use serde_json::Value;
+    let err = |msg: &str| WeldsError::Fixture(format!("invalid fixture json: {msg}"));
+
+    let root: Value = serde_json::from_str(input).map_err(|e| err(&e.to_string()))?;
+    let tables = root
+        .as_object()
+        .ok_or_else(|| err("expected an object of tables"))?;
+
+    let mut parsed = Vec::default();
+    for (table, fixtures) in tables {
+        let fixtures = fixtures
+            .as_object()
+            .ok_or_else(|| err(&format!("expected an object of fixtures for {table}")))?;
+        let mut list = Vec::default();
+        for (label, columns) in fixtures {
+            let columns = columns
+                .as_object()
+                .ok_or_else(|| err(&format!("expected an object of columns for {label}")))?;
+            let values = columns
+                .iter()
+                .map(|(column, value)| {
+                    let value = match value {
+                        Value::Null => FixtureValue::Null,
+                        Value::Bool(x) => FixtureValue::Bool(*x),
+                        Value::Number(x) => match x.as_i64() {
+                            Some(x) => FixtureValue::Int(x),
+                            None => FixtureValue::Float(x.as_f64().unwrap_or_default()),
+                        },
+                        Value::String(x) => FixtureValue::Text(x.clone()),
+                        // arrays and objects are stored as json text
+                        other => FixtureValue::Text(other.to_string()),
+                    };
+                    (column.clone(), value)
+                })
+                .collect();
+            list.push(Fixture::new(label, values));
+        }
+        parsed.push((table.clone(), list));
+    }
+    Ok(parsed)
+}
+
+/// Reads fixtures from YAML in the format
+/// ```yaml
+/// table:
+///   label:
+///     column: value
+/// ```
+/// Anchors, aliases and merge keys (`<<`) are resolved before the fixtures are read.
+pub(crate) fn yaml(input: &str) -> Result<Parsed> {
+    use serde_yaml::Value;
+    let err = |msg: &str| WeldsError::Fixture(format!("invalid fixture yaml: {msg}"));
+
+    let mut root: Value = serde_yaml::from_str(input).map_err(|e| err(&e.to_string()))?;
+    root.apply_merge().map_err(|e| err(&e.to_string()))?;
+
+    // an empty file has no fixtures
+    let empty = serde_yaml::Mapping::default();
+    let tables = match &root {
+        Value::Null => &empty,
+        other => other
+            .as_mapping()
+            .ok_or_else(|| err("expected a mapping of tables"))?,
+    };
+
+    let mut parsed = Vec::default();
+    for (table, fixtures) in tables {
+        let table = yaml_key(table).ok_or_else(|| err("expected a table name"))?;
+        let fixtures = match fixtures {
+            Value::Null => &empty,
+            other => other
+                .as_mapping()
+                .ok_or_else(|| err(&format!("expected a mapping of fixtures for {table}")))?,
+        };
+        let mut list = Vec::default();
+        for (label, columns) in fixtures {
+            let label = yaml_key(label).ok_or_else(|| err("expected a fixture label"))?;
+            // a fixture with no columns
+            let columns = match columns {
+                Value::Null => &empty,
+                other => other
+                    .as_mapping()
+                    .ok_or_else(|| err(&format!("expected a mapping of columns for {label}")))?,
+            };
+            let mut values = Vec::default();
+            for (column, value) in columns {
+                let column = yaml_key(column).ok_or_else(|| err("expected a column name"))?;
+                values.push((column, yaml_value(value)?));
+            }
+            list.push(Fixture::new(&label, values));
+        }
+        parsed.push((table, list));
+    }
+    Ok(parsed)
+}
+
+/// The text of a scalar used as a key
+fn yaml_key(key: &serde_yaml::Value) -> Option<String> {
+    use serde_yaml::Value;
+    match key {
+        Value::String(x) => Some(x.clone()),
+        Value::Number(x) => Some(x.to_string()),
+        Value::Bool(x) => Some(x.to_string()),
+        Value::Tagged(x) => yaml_key(&x.value),
+        _ => None,
+    }
+}
+
+fn yaml_value(value: &serde_yaml::Value) -> Result<FixtureValue> {
+    use serde_yaml::Value;
+    Ok(match value {
+        Value::Null => FixtureValue::Null,
+        Value::Bool(x) => FixtureValue::Bool(*x),
+        Value::Number(x) => match x.as_i64() {
+            Some(x) => FixtureValue::Int(x),
+            None => FixtureValue::Float(x.as_f64().unwrap_or_default()),
+        },
+        Value::String(x) => FixtureValue::Text(x.clone()),
+        Value::Tagged(x) => yaml_value(&x.value)?,
+        // sequences and mappings are stored as json text
+        other => {
+            let json = serde_json::to_string(other)
+                .map_err(|e| WeldsError::Fixture(format!("invalid fixture yaml: {e}")))?;
+            FixtureValue::Text(json)
+        }
+    })
+}
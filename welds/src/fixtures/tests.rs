@@ -0,0 +1,290 @@
+use super::*;
+
+const YAML: &str = r#"
+# a comment
+sellers:
+  alice:
+    name: "Alice # not a comment"
+    active: true
+  bob:
+    id: 7
+    name: Bob   # a comment
+
+products:
+  widget:
+    name: Widget
+    price: 1.5
+    seller: alice
+    notes: ~
+"#;
+
+#[test]
+fn should_parse_yaml_fixtures() {
+    let fixtures = Fixtures::from_yaml(YAML).unwrap();
+    let sellers = fixtures.table(&["sellers"]);
+    assert_eq!(sellers.len(), 2);
+    assert_eq!(sellers[0].label(), "alice");
+    assert_eq!(
+        sellers[0].get("name"),
+        Some(&FixtureValue::Text("Alice # not a comment".to_owned()))
+    );
+    assert_eq!(sellers[0].get("active"), Some(&FixtureValue::Bool(true)));
+    assert_eq!(sellers[1].get("id"), Some(&FixtureValue::Int(7)));
+    assert_eq!(
+        sellers[1].get("name"),
+        Some(&FixtureValue::Text("Bob".to_owned()))
+    );
+
+    let products = fixtures.table(&["products"]);
+    assert_eq!(products[0].get("price"), Some(&FixtureValue::Float(1.5)));
+    assert_eq!(products[0].get("notes"), Some(&FixtureValue::Null));
+}
+
+#[test]
+fn should_parse_json_fixtures() {
+    let json = r#"{ "sellers": { "alice": { "name": "Alice", "id": 3, "rating": null } } }"#;
+    let fixtures = Fixtures::from_json(json).unwrap();
+    let sellers = fixtures.table(&["public", "sellers"]);
+    assert_eq!(sellers.len(), 1);
+    assert_eq!(sellers[0].get("id"), Some(&FixtureValue::Int(3)));
+    assert_eq!(sellers[0].get("rating"), Some(&FixtureValue::Null));
+}
+
+#[test]
+fn should_error_on_bad_yaml_indentation() {
+    let yaml = "sellers:\n    alice:\n  name: Alice\n";
+    assert!(Fixtures::from_yaml(yaml).is_err());
+}
+
+#[test]
+fn should_merge_fixtures_for_the_same_table() {
+    let a = Fixtures::from_yaml("sellers:\n  alice:\n    name: Alice\n").unwrap();
+    let b = Fixtures::from_yaml("sellers:\n  bob:\n    name: Bob\n").unwrap();
+    let fixtures = a.merge(b);
+    assert_eq!(fixtures.table(&["sellers"]).len(), 2);
+}
+
+#[test]
+fn generated_ids_should_be_stable() {
+    assert_eq!(label_id("alice"), label_id("alice"));
+    assert_ne!(label_id("alice"), label_id("bob"));
+    assert!(label_id("alice") > 0);
+    assert!(label_id("alice") < i32::MAX as i64);
+}
+
+#[test]
+fn should_parse_yaml_flow_styles_anchors_and_quoted_scalars() {
+    let yaml = r#"
+sellers:
+  alice: &seller { name: 'Alice O''Neil', active: true }
+  bob:
+    <<: *seller
+    name: "Bob\tSmith"
+    tags: [a, b]
+    bio: |
+      line one
+      line two
+    motto: >
+      folded
+      text
+"#;
+    let fixtures = Fixtures::from_yaml(yaml).unwrap();
+    let sellers = fixtures.table(&["sellers"]);
+    let text = |x: &str| Some(FixtureValue::Text(x.to_owned()));
+    assert_eq!(sellers[0].get("name"), text("Alice O'Neil").as_ref());
+    assert_eq!(sellers[0].get("active"), Some(&FixtureValue::Bool(true)));
+    assert_eq!(sellers[1].get("name"), text("Bob\tSmith").as_ref());
+    assert_eq!(sellers[1].get("active"), Some(&FixtureValue::Bool(true)));
+    assert_eq!(sellers[1].get("tags"), text(r#"["a","b"]"#).as_ref());
+    assert_eq!(sellers[1].get("bio"), text("line one\nline two\n").as_ref());
+    assert_eq!(sellers[1].get("motto"), text("folded text\n").as_ref());
+}
+
+#[test]
+fn should_error_on_values_out_of_range_for_the_column() {
+    use crate::model_traits::Column;
+    let column = Column::new("quantity", "i16", false);
+    let err = FixtureValue::Int(70000).to_param(&column).err().unwrap();
+    let message = err.to_string();
+    assert!(message.contains("70000"), "{message}");
+    assert!(message.contains("quantity"), "{message}");
+    assert!(FixtureValue::Int(-40000).to_param(&column).is_err());
+    assert!(FixtureValue::Int(32767).to_param(&column).is_ok());
+
+    let column = Column::new("count", "Option<i32>", true);
+    assert!(FixtureValue::Int(i64::MAX).to_param(&column).is_err());
+    assert!(FixtureValue::Null.to_param(&column).is_ok());
+
+    let column = Column::new("weight", "f32", false);
+    assert!(FixtureValue::Float(1e300).to_param(&column).is_err());
+    assert!(FixtureValue::Float(1.5).to_param(&column).is_ok());
+}
+
+mod inserts {
+    use super::*;
+    use crate::WeldsModel;
+    use welds_connections::noop::{MockClient, MockRow, ParamValue};
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "sellers")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Seller {
+        #[welds(primary_key)]
+        pub id: i32,
+        pub name: String,
+    }
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "products")]
+    #[welds(BelongsTo(seller, Seller, "seller_id"))]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Product {
+        #[welds(primary_key)]
+        pub id: i32,
+        pub name: String,
+        pub seller_id: i32,
+    }
+
+    const SHOP: &str = r#"
+products:
+  widget:
+    name: Widget
+    seller: alice
+sellers:
+  alice:
+    name: Alice
+  bob:
+    id: 7
+    name: Bob
+"#;
+
+    fn shop() -> Fixtures {
+        // children registered before their parents
+        Fixtures::from_yaml(SHOP)
+            .unwrap()
+            .model::<Product>()
+            .model::<Seller>()
+    }
+
+    #[test]
+    fn should_insert_parents_before_children_with_resolved_ids() {
+        let client = MockClient::new(Syntax::Sqlite);
+        client.expect_execute("INSERT INTO sellers", 1);
+        client.expect_execute("INSERT INTO sellers", 1);
+        client.expect_execute("INSERT INTO products", 1);
+        let fixtures = shop();
+        futures::executor::block_on(fixtures.insert(&client)).unwrap();
+        client.assert_all_consumed();
+
+        let alice_id = label_id("alice");
+        let history = client.history();
+        assert_eq!(
+            history[0].sql,
+            r#"INSERT INTO sellers ("name", "id") VALUES (?, ?)"#
+        );
+        assert_eq!(
+            history[0].params,
+            vec![ParamValue::from("Alice"), ParamValue::from(alice_id)]
+        );
+        assert_eq!(
+            history[1].params,
+            vec![ParamValue::from(7), ParamValue::from("Bob")]
+        );
+        assert_eq!(
+            history[2].sql,
+            r#"INSERT INTO products ("name", "seller_id", "id") VALUES (?, ?, ?)"#
+        );
+        assert_eq!(
+            history[2].params,
+            vec![
+                ParamValue::from("Widget"),
+                ParamValue::from(alice_id),
+                ParamValue::from(label_id("widget")),
+            ]
+        );
+        assert_eq!(
+            fixtures.id::<Seller>("alice"),
+            Some(FixtureValue::Int(alice_id))
+        );
+        assert_eq!(fixtures.id::<Seller>("bob"), Some(FixtureValue::Int(7)));
+    }
+
+    #[test]
+    fn should_error_on_a_missing_label() {
+        let yaml = "products:\n  widget:\n    name: Widget\n    seller: nobody\n";
+        let fixtures = Fixtures::from_yaml(yaml)
+            .unwrap()
+            .model::<Product>()
+            .model::<Seller>();
+        let client = MockClient::new(Syntax::Sqlite);
+        let err = futures::executor::block_on(fixtures.insert(&client));
+        assert!(err.is_err());
+        assert!(client.history().is_empty());
+    }
+
+    #[test]
+    fn should_wipe_children_before_parents() {
+        let client = MockClient::new(Syntax::Sqlite);
+        client.expect_execute("DELETE FROM products", 1);
+        client.expect_execute("DELETE FROM sellers", 2);
+        futures::executor::block_on(shop().wipe(&client)).unwrap();
+        client.assert_all_consumed();
+    }
+
+    #[derive(Debug, WeldsModel)]
+    #[welds(table = "tickets")]
+    #[welds_path(crate)] // needed only within the welds crate.
+    struct Ticket {
+        #[welds(primary_key)]
+        pub id: i32,
+        pub code: String,
+        pub title: String,
+    }
+
+    fn scan_row(column: &str, ty: &str, pk: i32) -> MockRow {
+        MockRow::new()
+            .column("schema", Some("public".to_string()))
+            .column("table_name", "tickets".to_string())
+            .column("ty", "table".to_string())
+            .column("column_name", column.to_string())
+            .column("column_type", ty.to_string())
+            .column("is_nullable", 0_i32)
+            .column("is_primary_key", pk)
+            .column("is_updatable", 1_i32)
+            .column("column_default", None::<String>)
+    }
+
+    #[test]
+    fn should_cast_text_to_the_column_type_on_postgres() {
+        let client = MockClient::new(Syntax::Postgres);
+        let columns = vec![
+            scan_row("id", "int4", 1),
+            scan_row("code", "uuid", 0),
+            scan_row("title", "varchar", 0),
+        ];
+        client.expect_rows("information_schema", columns);
+        client.expect_execute("INSERT INTO tickets", 1);
+        let yaml = "tickets:\n  first:\n    id: 1\n    code: 4a1f2c9e-8d7b-4c1a-9f3e-2b6d8e0c5a71\n    title: First\n";
+        let fixtures = Fixtures::from_yaml(yaml).unwrap().model::<Ticket>();
+        futures::executor::block_on(fixtures.insert(&client)).unwrap();
+        client.assert_all_consumed();
+        assert_eq!(
+            client.history()[1].sql,
+            r#"INSERT INTO tickets ("id", "code", "title") VALUES ($1, CAST($2 AS UUID), $3)"#
+        );
+    }
+
+    #[test]
+    fn should_allow_identity_inserts_on_mssql() {
+        let client = MockClient::new(Syntax::Mssql);
+        client.expect_execute("INSERT INTO tickets", 1);
+        let yaml = "tickets:\n  first:\n    code: abc\n    title: First\n";
+        let fixtures = Fixtures::from_yaml(yaml).unwrap().model::<Ticket>();
+        futures::executor::block_on(fixtures.insert(&client)).unwrap();
+        let check = "IF COLUMNPROPERTY(OBJECT_ID('tickets'), 'id', 'IsIdentity') = 1";
+        let expected = format!(
+            r#"{check} SET IDENTITY_INSERT tickets ON; INSERT INTO tickets ("code", "title", "id") VALUES (@p1, @p2, @p3); {check} SET IDENTITY_INSERT tickets OFF"#
+        );
+        assert_eq!(client.history()[0].sql, expected);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::Column;
+use welds_connections::Param;
+
+/// A single value in a fixture file
+#[derive(Debug, Clone, PartialEq)]
+pub enum FixtureValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+pub(crate) type BoxedParam = Box<dyn Param + Sync + Send>;
+
+impl FixtureValue {
+    /// Converts the value into a param, using the rust type of the column
+    /// so the database is sent the type it is expecting
+    pub(crate) fn to_param(&self, column: &Column) -> Result<BoxedParam> {
+        let (inner, nullable) = inner_type(column);
+        if self == &FixtureValue::Null && !nullable && !column.nullable() {
+            return Err(self.wrong_type(column));
+        }
+
+        let param: BoxedParam = match inner {
+            "i16" => Box::new(self.as_small_int::<i16>(column)?),
+            "i32" => Box::new(self.as_small_int::<i32>(column)?),
+            "i64" => Box::new(self.as_int(column)?),
+            "f32" => Box::new(self.as_f32(column)?),
+            "f64" => Box::new(self.as_float(column)?),
+            "bool" => Box::new(self.as_bool(column)?),
+            // Everything else is sent as text and left to the database to convert
+            _ => Box::new(self.as_text()),
+        };
+        Ok(param)
+    }
+
+    /// True if the value for this column is sent to the database as text
+    pub(crate) fn sent_as_text(column: &Column) -> bool {
+        let (inner, _) = inner_type(column);
+        !matches!(inner, "i16" | "i32" | "i64" | "f32" | "f64" | "bool")
+    }
+
+    fn as_int(&self, column: &Column) -> Result<Option<i64>> {
+        match self {
+            FixtureValue::Null => Ok(None),
+            FixtureValue::Int(x) => Ok(Some(*x)),
+            FixtureValue::Bool(x) => Ok(Some(*x as i64)),
+            FixtureValue::Text(x) => x.parse().map(Some).map_err(|_| self.wrong_type(column)),
+            FixtureValue::Float(_) => Err(self.wrong_type(column)),
+        }
+    }
+
+    /// reads an int that must fit in a column smaller than an i64
+    fn as_small_int<T: TryFrom<i64>>(&self, column: &Column) -> Result<Option<T>> {
+        self.as_int(column)?
+            .map(|x| T::try_from(x).map_err(|_| self.out_of_range(column)))
+            .transpose()
+    }
+
+    fn as_f32(&self, column: &Column) -> Result<Option<f32>> {
+        match self.as_float(column)? {
+            Some(x) if x.is_finite() && !(x as f32).is_finite() => Err(self.out_of_range(column)),
+            x => Ok(x.map(|x| x as f32)),
+        }
+    }
+
+    fn as_float(&self, column: &Column) -> Result<Option<f64>> {
+        match self {
+            FixtureValue::Null => Ok(None),
+            FixtureValue::Int(x) => Ok(Some(*x as f64)),
+            FixtureValue::Float(x) => Ok(Some(*x)),
+            FixtureValue::Text(x) => x.parse().map(Some).map_err(|_| self.wrong_type(column)),
+            FixtureValue::Bool(_) => Err(self.wrong_type(column)),
+        }
+    }
+
+    fn as_bool(&self, column: &Column) -> Result<Option<bool>> {
+        match self {
+            FixtureValue::Null => Ok(None),
+            FixtureValue::Bool(x) => Ok(Some(*x)),
+            FixtureValue::Int(x) => Ok(Some(*x != 0)),
+            _ => Err(self.wrong_type(column)),
+        }
+    }
+
+    fn as_text(&self) -> Option<String> {
+        match self {
+            FixtureValue::Null => None,
+            FixtureValue::Bool(x) => Some(x.to_string()),
+            FixtureValue::Int(x) => Some(x.to_string()),
+            FixtureValue::Float(x) => Some(x.to_string()),
+            FixtureValue::Text(x) => Some(x.clone()),
+        }
+    }
+
+    fn wrong_type(&self, column: &Column) -> WeldsError {
+        WeldsError::Fixture(format!(
+            "the value {:?} can not be used for the column {} ({})",
+            self,
+            column.name(),
+            column.rust_type()
+        ))
+    }
+
+    fn out_of_range(&self, column: &Column) -> WeldsError {
+        WeldsError::Fixture(format!(
+            "the value {:?} is out of range for the column {} ({})",
+            self,
+            column.name(),
+            column.rust_type()
+        ))
+    }
+}
+
+/// returns the rust type of a column without its path or Option, and if it was an Option
+fn inner_type(column: &Column) -> (&str, bool) {
+    let (inner, nullable) = strip_option(column.rust_type());
+    (inner.rsplit("::").next().unwrap_or(inner), nullable)
+}
+
+/// returns the type inside of an Option, and if it was an Option
+fn strip_option(rust_type: &str) -> (&str, bool) {
+    let rust_type = rust_type.trim_start_matches("std::option::");
+    match rust_type
+        .strip_prefix("Option<")
+        .and_then(|x| x.strip_suffix('>'))
+    {
+        Some(inner) => (inner, true),
+        None => (rust_type, false),
+    }
+}
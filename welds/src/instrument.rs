@@ -0,0 +1,33 @@
+use crate::model_traits::HasSchema;
+use std::future::Future;
+
+/// Runs the database work for a model inside a `tracing` span.
+/// The span names the model, table and operation (select, insert, ...)
+/// the SQL statements that are run become children of this span.
+///
+/// When the `tracing` feature is NOT enabled this is a passthrough.
+#[cfg(feature = "tracing")]
+pub(crate) async fn operation<T, F>(operation: &'static str, fut: F) -> F::Output
+where
+    T: HasSchema,
+    F: Future,
+{
+    use crate::model_traits::TableInfo;
+    use tracing::Instrument;
+    let span = tracing::info_span!(
+        "welds.model",
+        model = std::any::type_name::<T>(),
+        table = <T as HasSchema>::Schema::identifier().join("."),
+        operation,
+    );
+    fut.instrument(span).await
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn operation<T, F>(_operation: &'static str, fut: F) -> F::Output
+where
+    T: HasSchema,
+    F: Future,
+{
+    fut.await
+}
@@ -143,6 +143,10 @@
 //! - migrations - adds all the migration structs and traits
 //! - full - all the features excluding (mock)
 //! - mock - Use for testing ONLY. Enables mocking out database schemas
+//! - serde - deserialize rows into any type that implements `serde::Deserialize`
+//! - factory - `#[derive(WeldsFactory)]` to build and create models with test data
+//! - fixtures - load test data from YAML/JSON files into the database
+//! - tracing - wraps each query in a `tracing` span (model, table, operation, SQL, timing, rows)
 //!
 //!
 //! # Important Notes:
@@ -153,6 +157,7 @@
 pub mod errors;
 pub use errors::WeldsError;
 pub mod exts;
+mod instrument;
 pub mod model_traits;
 pub mod query;
 pub mod relations;
@@ -170,6 +175,12 @@ pub mod check;
 #[cfg(feature = "migrations")]
 pub mod migrations;
 
+#[cfg(feature = "factory")]
+pub mod factory;
+
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+
 pub use welds_connections as connections;
 
 /// Re-export welds_connections
@@ -177,3 +188,13 @@ pub use welds_connections::{Client, Row, Syntax, TransactStart};
 
 /// Re-export the Macro used to make models
 pub use welds_macros::WeldsModel;
+
+/// Re-export the Macro used to map enums to columns
+pub use welds_macros::WeldsEnum;
+
+/// Re-export the Macro used to make groups of columns that can be flattened into a model
+pub use welds_macros::WeldsEmbedded;
+
+/// Re-export the Macro used to make factories for models
+#[cfg(feature = "factory")]
+pub use welds_macros::WeldsFactory;
@@ -1,4 +1,5 @@
-use super::types::{Index, OnDelete, Type};
+use super::types::{DefaultValue, Index, OnDelete, Type};
+use crate::errors::{Result, WeldsError};
 use crate::migrations::writers;
 use crate::migrations::MigrationWriter;
 use crate::model_traits::TableIdent;
@@ -13,6 +14,20 @@ impl MigrationWriter for TableBuilder {
     fn up_sql(&self, syntax: Syntax) -> Vec<String> {
         writers::create_table::from_builder(syntax, self)
     }
+
+    fn validate(&self, _syntax: Syntax) -> Result<()> {
+        if let PrimaryKey::Columns(pks) = &self.pk {
+            for pk in pks {
+                if !self.columns.iter().any(|c| &c.name == pk) {
+                    return Err(WeldsError::MigrationError(format!(
+                        "The primary key column '{}' is not a column of the table {}",
+                        pk, self.ident
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Builds a new migration used to create a table
@@ -36,17 +51,25 @@ pub fn create_table(name: impl Into<String>) -> TableBuilder {
     let ident = TableIdent::parse(&name);
     TableBuilder {
         ident,
-        pk: IdBuilder::default(),
+        pk: PrimaryKey::Id(IdBuilder::default()),
         columns: Vec::default(),
     }
 }
 
 pub struct TableBuilder {
     pub(crate) ident: TableIdent,
-    pub(crate) pk: IdBuilder,
+    pub(crate) pk: PrimaryKey,
     pub(crate) columns: Vec<ColumnBuilder>,
 }
 
+/// How the primary key of a new table is made
+pub(crate) enum PrimaryKey {
+    /// An id column, auto-incremented if it is an int
+    Id(IdBuilder),
+    /// A primary key on columns added with `.column(...)`
+    Columns(Vec<String>),
+}
+
 type ColumnLambda = fn(&str, Type) -> ColumnBuilder;
 type TableLambda = fn(&str, Type) -> IdBuilder;
 
@@ -60,7 +83,24 @@ impl TableBuilder {
             }
         };
         let col = lam(builder);
-        self.pk = col;
+        self.pk = PrimaryKey::Id(col);
+        self
+    }
+
+    /// Use a primary key made from columns of the table instead of an id column.
+    /// The columns are added with `.column(...)`. None of them are auto-incremented.
+    /// The migration fails with a `MigrationError` if a column isn't added.
+    /// ```
+    /// use welds::migrations::{create_table, types::Type};
+    ///
+    /// let m = create_table("user_groups")
+    ///     .primary_key(&["user_id", "group_id"])
+    ///     .column(|c| c("user_id", Type::Int))
+    ///     .column(|c| c("group_id", Type::Int));
+    /// ```
+    pub fn primary_key(mut self, columns: &[&str]) -> Self {
+        let columns = columns.iter().map(|c| c.to_string()).collect();
+        self.pk = PrimaryKey::Columns(columns);
         self
     }
 
@@ -73,6 +113,8 @@ impl TableBuilder {
                 nullable: false,
                 index: None,
                 index_name: None,
+                default: None,
+                check: None,
             }
         };
         let col = lam(builder);
@@ -87,6 +129,8 @@ pub struct ColumnBuilder {
     pub(crate) nullable: bool,
     pub(crate) index: Option<Index>,
     pub(crate) index_name: Option<String>,
+    pub(crate) default: Option<DefaultValue>,
+    pub(crate) check: Option<(String, String)>,
 }
 
 impl ColumnBuilder {
@@ -128,6 +172,31 @@ impl ColumnBuilder {
         self.index = Some(Index::ForeignKey((table.into(), column.into(), on_delete)));
         self
     }
+
+    /// The value the database writes to this column when a row is inserted without it
+    /// ```
+    /// use welds::migrations::{create_table, types::{DefaultValue, Type}};
+    ///
+    /// let m = create_table("dogs")
+    ///     .column(|c| c("age", Type::Int).default(0))
+    ///     .column(|c| c("created_at", Type::Datetime).default(DefaultValue::CurrentTimestamp));
+    /// ```
+    pub fn default(mut self, value: impl Into<DefaultValue>) -> Self {
+        self.default = Some(value.into());
+        self
+    }
+
+    /// Add a named CHECK constraint to this column. `expr` is the SQL condition to check
+    /// ```
+    /// use welds::migrations::{create_table, types::Type};
+    ///
+    /// let m = create_table("dogs")
+    ///     .column(|c| c("age", Type::Int).check("ck_dogs_age", "age >= 0"));
+    /// ```
+    pub fn check(mut self, name: impl Into<String>, expr: impl Into<String>) -> Self {
+        self.check = Some((name.into(), expr.into()));
+        self
+    }
 }
 
 pub struct IdBuilder {
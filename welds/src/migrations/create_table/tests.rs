@@ -62,6 +62,64 @@ fn should_be_able_to_make_a_fk() {
     assert_eq!(sql2, expected.trim());
 }
 
+#[test]
+fn should_create_a_table_with_a_composite_primary_key() {
+    let m = create_table("user_groups")
+        .primary_key(&["user_id", "group_id"])
+        .column(|c| c("user_id", Type::Int))
+        .column(|c| c("group_id", Type::Int))
+        .column(|c| c("role", Type::String).is_null());
+
+    //mysql
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mysql).join("; ");
+    let expected = r#"
+    CREATE TABLE user_groups ( user_id INT NOT NULL, group_id INT NOT NULL, role VARCHAR(255) NULL, PRIMARY KEY ( user_id, group_id ) )"#;
+    assert_eq!(sql, expected.trim());
+
+    //postgres
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    let expected = r#"
+    CREATE TABLE user_groups ( user_id INT NOT NULL, group_id INT NOT NULL, role TEXT NULL, PRIMARY KEY ( user_id, group_id ) )"#;
+    assert_eq!(sql, expected.trim());
+
+    //mssql
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    let expected = r#"
+    CREATE TABLE user_groups ( user_id INT NOT NULL, group_id INT NOT NULL, role NVARCHAR(MAX) NULL, PRIMARY KEY ( user_id, group_id ) )"#;
+    assert_eq!(sql, expected.trim());
+
+    //sqlite
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite).join("; ");
+    let expected = r#"
+    CREATE TABLE user_groups ( user_id INTEGER NOT NULL, group_id INTEGER NOT NULL, role TEXT NULL, PRIMARY KEY ( user_id, group_id ) )"#;
+    assert_eq!(sql, expected.trim());
+}
+
+#[test]
+fn primary_key_columns_should_never_be_null() {
+    let m = create_table("codes")
+        .primary_key(&["code"])
+        .column(|c| c("code", Type::String).is_null());
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    let expected = r#"CREATE TABLE codes ( code TEXT NOT NULL, PRIMARY KEY ( code ) )"#;
+    assert_eq!(sql, expected);
+}
+
+#[test]
+fn should_error_when_a_primary_key_column_is_missing() {
+    let m = create_table("user_groups")
+        .primary_key(&["user_id", "group_id"])
+        .column(|c| c("user_id", Type::Int));
+    let err = MigrationWriter::validate(&m, Syntax::Postgres).unwrap_err();
+    assert!(
+        matches!(&err, crate::WeldsError::MigrationError(msg) if msg.contains("'group_id'")),
+        "{err}"
+    );
+
+    let m = m.column(|c| c("group_id", Type::Int));
+    assert!(MigrationWriter::validate(&m, Syntax::Postgres).is_ok());
+}
+
 #[test]
 fn should_drop_basic_table() {
     let m = create_table("s1.MyTable")
@@ -77,3 +135,39 @@ fn should_drop_basic_table() {
     let sql2 = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
     assert_eq!(sql2, expected.trim());
 }
+
+#[test]
+fn should_create_columns_with_defaults_and_checks() {
+    let m = create_table("s1.dogs")
+        .id(|c| c("id", Type::Int))
+        .column(|c| {
+            c("age", Type::Int)
+                .default(0)
+                .check("ck_dogs_age", "age >= 0")
+        })
+        .column(|c| c("good", Type::Bool).default(true))
+        .column(|c| c("name", Type::String).default("it's"))
+        .column(|c| c("born", Type::Datetime).default(DefaultValue::CurrentTimestamp));
+
+    //postgres
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    let expected = r#"
+    CREATE TABLE s1.dogs ( id SERIAL PRIMARY KEY, age INT NOT NULL DEFAULT 0 CONSTRAINT ck_dogs_age CHECK (age >= 0), good BOOL NOT NULL DEFAULT TRUE, name TEXT NOT NULL DEFAULT 'it''s', born TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP )"#;
+    assert_eq!(sql, expected.trim());
+
+    //mssql names the defaults so they can be dropped
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    let expected = r#"
+    CREATE TABLE s1.dogs ( id INT IDENTITY(1,1) PRIMARY KEY, age INT NOT NULL CONSTRAINT df_dogs_age DEFAULT 0 CONSTRAINT ck_dogs_age CHECK (age >= 0), good BIT NOT NULL CONSTRAINT df_dogs_good DEFAULT 1, name NVARCHAR(MAX) NOT NULL CONSTRAINT df_dogs_name DEFAULT 'it''s', born DATETIME2 NOT NULL CONSTRAINT df_dogs_born DEFAULT CURRENT_TIMESTAMP )"#;
+    assert_eq!(sql, expected.trim());
+}
+
+#[test]
+fn should_generate_uuids_by_default_on_every_syntax() {
+    let m = create_table("dogs").column(|c| c("key", Type::Uuid).default(DefaultValue::Uuid));
+    let column = |syntax| MigrationWriter::up_sql(&m, syntax).join("; ");
+    assert!(column(Syntax::Postgres).contains("key UUID NOT NULL DEFAULT gen_random_uuid()"));
+    assert!(column(Syntax::Mysql).contains("NOT NULL DEFAULT (UUID())"));
+    assert!(column(Syntax::Mssql).contains("CONSTRAINT df_dogs_key DEFAULT NEWID()"));
+    assert!(column(Syntax::Sqlite).contains("NOT NULL DEFAULT (lower(hex(randomblob(4)))"));
+}
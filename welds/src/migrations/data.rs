@@ -0,0 +1,182 @@
+use crate::errors::Result;
+use crate::Client;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The future returned by the code of a data migration
+pub type DataFuture<'c> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'c>>;
+
+type DataFn = Box<dyn for<'c> Fn(&'c dyn Client) -> DataFuture<'c> + Send + Sync>;
+
+/// Written to `_welds_migrations` in place of rollback SQL for a data migration.
+pub(crate) const DATA_MIGRATION: &str = "-- welds: data migration";
+/// Written in place of rollback SQL for a data migration that has a down.
+pub(crate) const DATA_MIGRATION_WITH_DOWN: &str = "-- welds: data migration with down";
+
+/// A migration that runs rust code against the database. Useful for backfilling data.
+/// The code runs in the same transaction as the rest of the migrations.
+/// ```
+/// use welds::errors::Result;
+/// use welds::migrations::{DataMigration, MigrationStep, TableState};
+///
+/// fn backfill_nicknames(_state: &TableState) -> Result<MigrationStep> {
+///     let m = DataMigration::up(|client| {
+///         Box::pin(async move {
+///             let rows = client.fetch_rows("SELECT id, name FROM dogs", &[]).await?;
+///             for row in rows {
+///                 let id: i32 = row.get("id")?;
+///                 let name: String = row.get("name")?;
+///                 let nickname = name.split(' ').next().unwrap_or_default().to_string();
+///                 let sql = "UPDATE dogs SET nickname = ? WHERE id = ?";
+///                 client.execute(sql, &[&nickname, &id]).await?;
+///             }
+///             Ok(())
+///         })
+///     });
+///     Ok(MigrationStep::data("m20250301120000_backfill_nicknames", m))
+/// }
+/// ```
+pub struct DataMigration {
+    pub(crate) up: DataFn,
+    pub(crate) down: Option<DataFn>,
+}
+
+impl DataMigration {
+    /// The code to run when migrating up
+    pub fn up<F>(f: F) -> DataMigration
+    where
+        F: for<'c> Fn(&'c dyn Client) -> DataFuture<'c> + Send + Sync + 'static,
+    {
+        DataMigration {
+            up: Box::new(f),
+            down: None,
+        }
+    }
+
+    /// The code to run when rolling back this migration.
+    /// Without it rolling back does nothing to the data.
+    ///
+    /// NOTE: roll back with `down_with` or `down_last_with`, they can find this code in your list of migrations
+    pub fn down<F>(mut self, f: F) -> DataMigration
+    where
+        F: for<'c> Fn(&'c dyn Client) -> DataFuture<'c> + Send + Sync + 'static,
+    {
+        self.down = Some(Box::new(f));
+        self
+    }
+
+    /// What is written to the migration log in place of rollback SQL
+    pub(crate) fn rollback_marker(&self) -> &'static str {
+        match self.down {
+            Some(_) => DATA_MIGRATION_WITH_DOWN,
+            None => DATA_MIGRATION,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use crate::errors::{Result, WeldsError};
+    use crate::migrations::prelude::*;
+    use crate::migrations::{down, down_last_with, down_with, up, DataMigration, MigrationFn};
+    use crate::Client;
+
+    fn create_cars(_: &TableState) -> Result<MigrationStep> {
+        let m = create_table("cars")
+            .id(|c| c("id", Type::Int))
+            .column(|c| c("name", Type::String));
+        Ok(MigrationStep::new("create_cars", m))
+    }
+
+    fn seed_cars(_: &TableState) -> Result<MigrationStep> {
+        let m = DataMigration::up(|client| {
+            Box::pin(async move {
+                let sql = "INSERT INTO cars (name) VALUES ('civic')";
+                client.execute(sql, &[]).await?;
+                Ok(())
+            })
+        })
+        .down(|client| {
+            Box::pin(async move {
+                client.execute("DELETE FROM cars", &[]).await?;
+                Ok(())
+            })
+        });
+        Ok(MigrationStep::data("seed_cars", m))
+    }
+
+    fn broken(_: &TableState) -> Result<MigrationStep> {
+        let m = DataMigration::up(|_client| {
+            Box::pin(async move { Err(WeldsError::MigrationError("broken".to_string())) })
+        });
+        Ok(MigrationStep::data("broken", m))
+    }
+
+    const MIGRATIONS: &[MigrationFn] = &[create_cars, seed_cars];
+
+    async fn count(client: &dyn Client, sql: &str) -> usize {
+        client.fetch_rows(sql, &[]).await.unwrap().len()
+    }
+
+    #[test]
+    fn should_roll_back_the_data_with_the_migration_on_error() {
+        futures::executor::block_on(async move {
+            let client = crate::connections::connect("sqlite::memory:")
+                .await
+                .unwrap();
+            up(&client, &[create_cars]).await.unwrap();
+
+            let err = up(&client, &[create_cars, seed_cars, broken]).await;
+            assert!(matches!(err, Err(WeldsError::MigrationError(_))));
+            assert_eq!(count(&client, "SELECT id FROM cars").await, 0);
+            let logs = "SELECT id FROM _welds_migrations";
+            assert_eq!(count(&client, logs).await, 1);
+        });
+    }
+
+    #[test]
+    fn down_last_with_should_run_the_rust_down() {
+        futures::executor::block_on(async move {
+            let client = crate::connections::connect("sqlite::memory:")
+                .await
+                .unwrap();
+            up(&client, MIGRATIONS).await.unwrap();
+            assert_eq!(count(&client, "SELECT id FROM cars").await, 1);
+
+            let name = down_last_with(&client, MIGRATIONS).await.unwrap();
+            assert_eq!(name.as_deref(), Some("seed_cars"));
+            assert_eq!(count(&client, "SELECT id FROM cars").await, 0);
+        });
+    }
+
+    #[test]
+    fn down_with_should_run_the_rust_down() {
+        futures::executor::block_on(async move {
+            let client = crate::connections::connect("sqlite::memory:")
+                .await
+                .unwrap();
+            up(&client, MIGRATIONS).await.unwrap();
+
+            let name = down_with(&client, "seed_cars", MIGRATIONS).await.unwrap();
+            assert_eq!(name.as_deref(), Some("seed_cars"));
+            assert_eq!(count(&client, "SELECT id FROM cars").await, 0);
+        });
+    }
+
+    #[test]
+    fn down_without_the_migrations_should_error_for_a_rust_down() {
+        futures::executor::block_on(async move {
+            let client = crate::connections::connect("sqlite::memory:")
+                .await
+                .unwrap();
+            up(&client, MIGRATIONS).await.unwrap();
+
+            let err = down(&client, "seed_cars").await;
+            assert!(matches!(err, Err(WeldsError::MigrationError(_))));
+            // nothing was rolled back
+            assert_eq!(count(&client, "SELECT id FROM cars").await, 1);
+            let logs = "SELECT id FROM _welds_migrations WHERE name = 'seed_cars'";
+            assert_eq!(count(&client, logs).await, 1);
+        });
+    }
+}
@@ -0,0 +1,75 @@
+use crate::migrations::MigrationWriter;
+use crate::Syntax;
+use welds_connections::enums::{DbEnum, EnumStorage};
+
+pub struct CreateEnum {
+    name: Option<&'static str>,
+    values: &'static [&'static str],
+}
+
+/// Creates the native Postgres ENUM type for an enum with `#[welds(postgres_enum = "name")]`
+///
+/// ```rust,ignore
+/// fn create_mood(_state: &TableState) -> Result<MigrationStep> {
+///     Ok(MigrationStep::new("create_mood", create_enum::<Mood>()))
+/// }
+/// ```
+///
+/// NOTE: Nothing is run for other databases or enums stored as text/integer,
+/// their columns don't need a type created first.
+pub fn create_enum<E: DbEnum>() -> CreateEnum {
+    let name = match E::STORAGE {
+        EnumStorage::PostgresEnum(name) => Some(name),
+        _ => None,
+    };
+    CreateEnum {
+        name,
+        values: E::TEXT_VALUES,
+    }
+}
+
+impl MigrationWriter for CreateEnum {
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        let name = match self.name {
+            Some(name) if syntax == Syntax::Postgres => name,
+            _ => return Vec::default(),
+        };
+        let values: Vec<String> = self
+            .values
+            .iter()
+            .map(|v| format!("'{}'", v.replace('\'', "''")))
+            .collect();
+        vec![format!(
+            "CREATE TYPE {} AS ENUM ({})",
+            name,
+            values.join(", ")
+        )]
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        match self.name {
+            Some(name) if syntax == Syntax::Postgres => vec![format!("DROP TYPE {}", name)],
+            _ => Vec::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_create_the_type_on_postgres() {
+        let m = CreateEnum {
+            name: Some("mood"),
+            values: &["happy", "it's ok"],
+        };
+        assert_eq!(
+            m.up_sql(Syntax::Postgres),
+            vec!["CREATE TYPE mood AS ENUM ('happy', 'it''s ok')"]
+        );
+        assert_eq!(m.down_sql(Syntax::Postgres), vec!["DROP TYPE mood"]);
+        assert!(m.up_sql(Syntax::Sqlite).is_empty());
+        assert!(m.down_sql(Syntax::Mysql).is_empty());
+    }
+}
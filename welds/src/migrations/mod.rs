@@ -1,6 +1,7 @@
 use crate::detect::{self, TableDef};
 use crate::errors::Result;
 use crate::errors::WeldsError;
+use crate::model_traits::TableIdent;
 use crate::state::DbState;
 use crate::Client;
 use crate::Syntax;
@@ -16,12 +17,22 @@ pub mod writers;
 mod create_table;
 pub mod types;
 pub use create_table::create_table;
+mod enums;
+pub use enums::{create_enum, CreateEnum};
+mod view;
+pub use view::{create_view, ViewBuilder};
 mod tablemod;
 pub use tablemod::change_table;
 mod utils;
 use crate::connections::Transaction;
 mod manual;
 pub use manual::Manual;
+mod data;
+pub use data::{DataFuture, DataMigration};
+mod status;
+pub use status::{dry_run, pending, status, MigrationState, MigrationStatus};
+mod test_database;
+pub use test_database::TestDatabase;
 
 pub type MigrationFn = fn(state: &TableState) -> Result<MigrationStep>;
 
@@ -53,15 +64,25 @@ pub async fn up(client: &(dyn TransactStart), migrations: &[MigrationFn]) -> Res
         let found = found.pop();
         if found.is_none() {
             // run the migration step
-            for part in step.writer.up_sql(trans.syntax()) {
-                trans.execute(&part, &[]).await?;
-            }
+            let rollback_sql = match &step.kind {
+                StepKind::Sql(writer) => {
+                    writer.validate(trans.syntax())?;
+                    for part in writer.up_sql(trans.syntax()) {
+                        trans.execute(&part, &[]).await?;
+                    }
+                    writer.down_sql(trans.syntax()).join("; ")
+                }
+                StepKind::Data(data) => {
+                    (data.up)(&trans).await?;
+                    data.rollback_marker().to_string()
+                }
+            };
 
             let mut mlog = DbState::new_uncreated(MigrationLog {
                 id: 0,
                 name: step.name.to_string(),
                 when_applied: unixtime() as i64,
-                rollback_sql: step.writer.down_sql(trans.syntax()).join("; "),
+                rollback_sql,
             });
             mlog.save(&trans).await?;
         }
@@ -93,7 +114,27 @@ fn unixtime() -> u128 {
 /// Rolls back the last migration that ran.
 /// return the name of the migration that rolled back
 /// None, there were not more migrations to rollback
+///
+/// NOTE: a `DataMigration` with a down needs your list of migrations. use `down_last_with`
 pub async fn down_last(client: &(dyn TransactStart)) -> Result<Option<String>> {
+    down_last_inner(client, None).await
+}
+
+/// Rolls back the last migration that ran.
+/// The list of migrations is used to find the down of a `DataMigration`
+/// return the name of the migration that rolled back
+/// None, there were not more migrations to rollback
+pub async fn down_last_with(
+    client: &dyn TransactStart,
+    migrations: &[MigrationFn],
+) -> Result<Option<String>> {
+    down_last_inner(client, Some(migrations)).await
+}
+
+async fn down_last_inner(
+    client: &dyn TransactStart,
+    migrations: Option<&[MigrationFn]>,
+) -> Result<Option<String>> {
     //make the migration table if needed
     {
         let setup_trans = client.begin().await?;
@@ -114,12 +155,7 @@ pub async fn down_last(client: &(dyn TransactStart)) -> Result<Option<String>> {
         None => return Ok(None),
     };
 
-    let down = last.rollback_sql.as_str();
-    let parts = utils::split_sql_commands(down);
-
-    for sql in parts {
-        trans.execute(&sql, &[]).await?;
-    }
+    rollback(&trans, &last, migrations).await?;
     last.delete(&trans).await?;
     trans.commit().await?;
 
@@ -129,7 +165,29 @@ pub async fn down_last(client: &(dyn TransactStart)) -> Result<Option<String>> {
 /// Rolls back the given migration.
 /// return the name of the migration that rolled back
 /// None, there were no matching migrations to rollback
+///
+/// NOTE: a `DataMigration` with a down needs your list of migrations. use `down_with`
 pub async fn down(client: &(dyn TransactStart), name: impl Into<String>) -> Result<Option<String>> {
+    down_inner(client, name.into(), None).await
+}
+
+/// Rolls back the given migration.
+/// The list of migrations is used to find the down of a `DataMigration`
+/// return the name of the migration that rolled back
+/// None, there were no matching migrations to rollback
+pub async fn down_with(
+    client: &dyn TransactStart,
+    name: impl Into<String>,
+    migrations: &[MigrationFn],
+) -> Result<Option<String>> {
+    down_inner(client, name.into(), Some(migrations)).await
+}
+
+async fn down_inner(
+    client: &dyn TransactStart,
+    name: String,
+    migrations: Option<&[MigrationFn]>,
+) -> Result<Option<String>> {
     //make the migration table if needed
     {
         let setup_trans = client.begin().await?;
@@ -137,7 +195,6 @@ pub async fn down(client: &(dyn TransactStart), name: impl Into<String>) -> Resu
     }
 
     let trans = client.begin().await?;
-    let name: String = name.into();
     let name = name.as_str();
 
     let mut mlog = MigrationLog::all()
@@ -153,15 +210,54 @@ pub async fn down(client: &(dyn TransactStart), name: impl Into<String>) -> Resu
         None => return Ok(None),
     };
 
+    rollback(&trans, &mlog, migrations).await?;
+    mlog.delete(&trans).await?;
+    trans.commit().await?;
+    Ok(Some(mlog.name.as_str().to_string()))
+}
+
+/// Undoes a migration that was logged as ran
+async fn rollback(
+    trans: &Transaction<'_>,
+    mlog: &MigrationLog,
+    migrations: Option<&[MigrationFn]>,
+) -> Result<()> {
     let down = mlog.rollback_sql.as_str();
-    let parts = utils::split_sql_commands(down);
 
-    for sql in parts {
+    // data migrations without a down have nothing to undo
+    if down == data::DATA_MIGRATION {
+        return Ok(());
+    }
+
+    // The down of a data migration is rust code, find it in the list of migrations
+    if down == data::DATA_MIGRATION_WITH_DOWN {
+        let name = mlog.name.as_str();
+        let missing = || {
+            let err = format!("The migration {name} rolls back with rust code. Include your list of migrations to roll it back (down_with/down_last_with)");
+            WeldsError::MigrationError(err)
+        };
+        let migrations = migrations.ok_or_else(missing)?;
+        let state = get_state(trans).await?;
+        // steps that can't be built from the current state can't be the one we are looking for
+        let step = migrations
+            .iter()
+            .filter_map(|lambda| lambda(&state).ok())
+            .find(|step| step.name == name)
+            .ok_or_else(missing)?;
+        if let StepKind::Data(DataMigration {
+            down: Some(down), ..
+        }) = &step.kind
+        {
+            down(trans).await?;
+            return Ok(());
+        }
+        return Err(missing());
+    }
+
+    for sql in utils::split_sql_commands(down) {
         trans.execute(&sql, &[]).await?;
     }
-    mlog.delete(&trans).await?;
-    trans.commit().await?;
-    Ok(Some(mlog.name.as_str().to_string()))
+    Ok(())
 }
 
 fn migration_table() -> Box<dyn MigrationWriter>
@@ -202,16 +298,34 @@ pub struct TableState(pub(crate) Vec<TableDef>);
 pub trait MigrationWriter {
     fn up_sql(&self, syntax: Syntax) -> Vec<String>;
     fn down_sql(&self, syntax: Syntax) -> Vec<String>;
+
+    /// Returns a `MigrationError` if this migration can't be written for the syntax.
+    /// `up` checks this before running any of the migration's SQL
+    fn validate(&self, _syntax: Syntax) -> Result<()> {
+        Ok(())
+    }
+
+    /// The existing table/view this migration was written from.
+    /// `status` can't verify the SQL of a migration written from a table an earlier pending migration changes
+    fn table(&self) -> Option<&TableIdent> {
+        None
+    }
 }
 
 /// Once migration step.
 /// This is what is used by up/down
 /// to make the changes to the database
 pub struct MigrationStep {
-    pub(crate) writer: Box<dyn MigrationWriter>,
+    pub(crate) kind: StepKind,
     pub(crate) name: &'static str,
 }
 
+/// What a migration step does to the database
+pub(crate) enum StepKind {
+    Sql(Box<dyn MigrationWriter>),
+    Data(DataMigration),
+}
+
 impl MigrationStep {
     pub fn new<T>(name: &'static str, writer: T) -> Self
     where
@@ -220,7 +334,15 @@ impl MigrationStep {
     {
         MigrationStep {
             name,
-            writer: Box::new(writer),
+            kind: StepKind::Sql(Box::new(writer)),
+        }
+    }
+
+    /// A step that runs rust code against the database
+    pub fn data(name: &'static str, data: DataMigration) -> Self {
+        MigrationStep {
+            name,
+            kind: StepKind::Data(data),
         }
     }
 }
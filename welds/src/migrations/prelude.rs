@@ -1,10 +1,20 @@
+pub use crate::migrations::create_enum;
 pub use crate::migrations::create_table;
+pub use crate::migrations::create_view;
 pub use crate::migrations::down;
 pub use crate::migrations::down_last;
+pub use crate::migrations::down_last_with;
+pub use crate::migrations::down_with;
+pub use crate::migrations::dry_run;
+pub use crate::migrations::pending;
+pub use crate::migrations::status;
 pub use crate::migrations::tablemod::change_table;
-pub use crate::migrations::types::{Index, OnDelete, Type};
+pub use crate::migrations::types::{DefaultValue, Index, OnDelete, Type};
 pub use crate::migrations::up;
+pub use crate::migrations::DataMigration;
 pub use crate::migrations::MigrationFn;
+pub use crate::migrations::MigrationState;
+pub use crate::migrations::MigrationStatus;
 pub use crate::migrations::MigrationStep;
 pub use crate::migrations::MigrationWriter;
 pub use crate::migrations::TableState;
@@ -0,0 +1,347 @@
+use super::{get_state, MigrationFn, MigrationLog, StepKind};
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::TableIdent;
+use crate::Client;
+use std::collections::HashSet;
+
+/// Where a migration in your list of migrations is at in the database
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationStatus {
+    pub(crate) position: usize,
+    pub(crate) name: Option<String>,
+    pub(crate) state: MigrationState,
+}
+
+/// If a migration has ran, and if not what it would do
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationState {
+    /// The migration ran at this time (unix time in milliseconds)
+    Applied { when_applied: i64 },
+    /// The migration hasn't ran. This is the SQL it would run
+    Pending { sql: Vec<String> },
+    /// The migration hasn't ran. It is a `DataMigration`, it runs rust code not SQL
+    PendingData,
+    /// The migration could not be written from the current state of the database.
+    /// Usually because it changes something an earlier pending migration makes,
+    /// or it changes a table an earlier pending migration also changes.
+    Blocked { error: String },
+    /// The migration can't be written for this database. `up` will fail with this error
+    Invalid { error: String },
+}
+
+impl MigrationStatus {
+    /// The index of this migration in your list of migrations
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The name of the migration. None if the migration could not be written
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn state(&self) -> &MigrationState {
+        &self.state
+    }
+
+    /// When the migration ran (unix time in milliseconds). None if it hasn't ran
+    pub fn when_applied(&self) -> Option<i64> {
+        match self.state {
+            MigrationState::Applied { when_applied } => Some(when_applied),
+            _ => None,
+        }
+    }
+
+    /// The SQL a pending migration would run. Empty for all other migrations
+    pub fn sql(&self) -> &[String] {
+        match &self.state {
+            MigrationState::Pending { sql } => sql,
+            _ => &[],
+        }
+    }
+
+    pub fn is_applied(&self) -> bool {
+        matches!(self.state, MigrationState::Applied { .. })
+    }
+}
+
+/// Returns the status of each migration in your list of migrations, without running any of them.
+///
+/// The SQL of a pending migration is written for the current state of the database.
+/// `up` writes each migration after the ones before it have ran, so a pending migration that
+/// changes a table made or changed by an earlier pending migration is returned as `Blocked`
+pub async fn status(
+    client: &dyn Client,
+    migrations: &[MigrationFn],
+) -> Result<Vec<MigrationStatus>> {
+    let syntax = client.syntax();
+    let state = get_state(client).await?;
+
+    // The migration table doesn't exist until the first migration runs
+    let has_log = state
+        .0
+        .iter()
+        .any(|t| t.ident().name() == "_welds_migrations");
+    let logs = match has_log {
+        true => MigrationLog::all().run(client).await?,
+        false => Vec::default(),
+    };
+
+    let mut seen = HashSet::new();
+    let mut list = Vec::default();
+    // The tables changed by pending migrations, and the first migration to change them
+    let mut changed: Vec<(TableIdent, &'static str)> = Vec::default();
+
+    for (position, lambda) in migrations.iter().enumerate() {
+        let step = match lambda(&state) {
+            Ok(step) => step,
+            Err(err) => {
+                let error = err.to_string();
+                let state = MigrationState::Blocked { error };
+                list.push(MigrationStatus {
+                    position,
+                    name: None,
+                    state,
+                });
+                continue;
+            }
+        };
+
+        if seen.contains(step.name) {
+            Err(WeldsError::DuplicateMigration)?;
+        }
+        seen.insert(step.name.to_string());
+
+        let log = logs.iter().find(|l| l.name == step.name);
+        // The SQL is written from the table as it is now, an earlier pending migration changes it first
+        let blocked_by =
+            match (log, &step.kind) {
+                (None, StepKind::Sql(writer)) => writer.table().and_then(|table| {
+                    match changed.iter().find(|(t, _)| t == table) {
+                        Some((_, earlier)) => Some(format!(
+                        "It changes {table}, which the pending migration {earlier} changes first"
+                    )),
+                        None => {
+                            changed.push((table.clone(), step.name));
+                            None
+                        }
+                    }
+                }),
+                _ => None,
+            };
+
+        let state = match (log, &step.kind, blocked_by) {
+            (Some(log), _, _) => MigrationState::Applied {
+                when_applied: log.when_applied,
+            },
+            (None, _, Some(error)) => MigrationState::Blocked { error },
+            (None, StepKind::Sql(writer), None) => match writer.validate(syntax) {
+                Ok(_) => MigrationState::Pending {
+                    sql: writer.up_sql(syntax),
+                },
+                Err(err) => MigrationState::Invalid {
+                    error: err.to_string(),
+                },
+            },
+            (None, StepKind::Data(_), None) => MigrationState::PendingData,
+        };
+
+        list.push(MigrationStatus {
+            position,
+            name: Some(step.name.to_string()),
+            state,
+        });
+    }
+
+    Ok(list)
+}
+
+/// Returns the status of only the migrations that have not ran.
+/// See `status`
+pub async fn pending(
+    client: &dyn Client,
+    migrations: &[MigrationFn],
+) -> Result<Vec<MigrationStatus>> {
+    let list = status(client, migrations).await?;
+    Ok(list.into_iter().filter(|s| !s.is_applied()).collect())
+}
+
+/// Returns the SQL `up` would run as a single script, without running it.
+/// Each pending migration starts with a comment of its name.
+/// See `status`
+pub async fn dry_run(client: &dyn Client, migrations: &[MigrationFn]) -> Result<String> {
+    let list = pending(client, migrations).await?;
+    Ok(dry_run_script(&list))
+}
+
+fn dry_run_script(list: &[MigrationStatus]) -> String {
+    let mut script = String::default();
+    for status in list {
+        let name = status.name().unwrap_or("?");
+        let position = status.position;
+        match &status.state {
+            MigrationState::Applied { .. } => continue,
+            MigrationState::Pending { sql } => {
+                script.push_str(&format!("-- {position}: {name}\n"));
+                for part in sql {
+                    script.push_str(&format!("{part};\n"));
+                }
+            }
+            MigrationState::PendingData => {
+                script.push_str(&format!(
+                    "-- {position}: {name} (data migration, runs rust code)\n"
+                ));
+            }
+            MigrationState::Invalid { error } => {
+                script.push_str(&format!("-- {position}: {name} is invalid: {error}\n"));
+            }
+            MigrationState::Blocked { error } => {
+                script.push_str(&format!(
+                    "-- {position}: can't be written until the migrations before it have ran: {error}\n"
+                ));
+            }
+        }
+        script.push('\n');
+    }
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(position: usize, name: &str, sql: &[&str]) -> MigrationStatus {
+        MigrationStatus {
+            position,
+            name: Some(name.to_string()),
+            state: MigrationState::Pending {
+                sql: sql.iter().map(|s| s.to_string()).collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn should_write_a_script_of_the_pending_sql() {
+        let list = vec![
+            MigrationStatus {
+                position: 0,
+                name: Some("create_cars".to_string()),
+                state: MigrationState::Applied { when_applied: 7 },
+            },
+            pending(1, "add_color", &["ALTER TABLE cars ADD COLUMN color TEXT"]),
+            MigrationStatus {
+                position: 2,
+                name: Some("fill_color".to_string()),
+                state: MigrationState::PendingData,
+            },
+            MigrationStatus {
+                position: 3,
+                name: None,
+                state: MigrationState::Blocked {
+                    error: "Missing".to_string(),
+                },
+            },
+            MigrationStatus {
+                position: 4,
+                name: Some("create_groups".to_string()),
+                state: MigrationState::Invalid {
+                    error: "Bad".to_string(),
+                },
+            },
+        ];
+        let script = dry_run_script(&list);
+        let expected = "-- 1: add_color\nALTER TABLE cars ADD COLUMN color TEXT;\n\n-- 2: fill_color (data migration, runs rust code)\n\n-- 3: can't be written until the migrations before it have ran: Missing\n\n-- 4: create_groups is invalid: Bad\n\n";
+        assert_eq!(script, expected);
+    }
+
+    #[test]
+    fn only_pending_migrations_have_sql() {
+        let applied = MigrationStatus {
+            position: 0,
+            name: Some("create_cars".to_string()),
+            state: MigrationState::Applied { when_applied: 7 },
+        };
+        assert!(applied.is_applied());
+        assert_eq!(applied.when_applied(), Some(7));
+        assert!(applied.sql().is_empty());
+        let pending = pending(1, "add_color", &["SELECT 1"]);
+        assert!(!pending.is_applied());
+        assert_eq!(pending.when_applied(), None);
+        assert_eq!(pending.sql(), &["SELECT 1".to_string()]);
+    }
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use super::super::*;
+        use crate::migrations::prelude::*;
+        use crate::migrations::{change_table, DataMigration, TestDatabase};
+
+        fn create_cars(_: &TableState) -> Result<MigrationStep> {
+            let m = create_table("cars")
+                .id(|c| c("id", Type::Int))
+                .column(|c| c("name", Type::String));
+            Ok(MigrationStep::new("create_cars", m))
+        }
+
+        fn add_color(state: &TableState) -> Result<MigrationStep> {
+            let m = change_table(state, "cars")?.add_column("color", Type::String);
+            Ok(MigrationStep::new("add_color", m))
+        }
+
+        fn add_wheels(state: &TableState) -> Result<MigrationStep> {
+            let m = change_table(state, "cars")?.add_column("wheels", Type::Int);
+            Ok(MigrationStep::new("add_wheels", m))
+        }
+
+        fn fill_color(_: &TableState) -> Result<MigrationStep> {
+            let m = DataMigration::up(|_client| Box::pin(async move { Ok(()) }));
+            Ok(MigrationStep::data("fill_color", m))
+        }
+
+        const MIGRATIONS: &[MigrationFn] = &[create_cars, add_color, add_wheels, fill_color];
+
+        #[test]
+        fn should_return_the_status_of_applied_and_pending_migrations() {
+            futures::executor::block_on(async move {
+                let db = TestDatabase::sqlite(&[create_cars]).await.unwrap();
+                let client = db.client();
+
+                let list = status(client, MIGRATIONS).await.unwrap();
+                assert_eq!(list.len(), 4);
+                assert_eq!(list[0].name(), Some("create_cars"));
+                assert!(list[0].is_applied());
+                assert!(list[0].when_applied().is_some());
+
+                assert_eq!(list[1].name(), Some("add_color"));
+                assert!(matches!(list[1].state(), MigrationState::Pending { .. }));
+                assert!(list[1].sql()[0].contains("color"));
+
+                // written from cars before add_color ran, the SQL can't be trusted
+                assert_eq!(list[2].name(), Some("add_wheels"));
+                match list[2].state() {
+                    MigrationState::Blocked { error } => assert!(error.contains("add_color")),
+                    state => panic!("expected add_wheels to be blocked: {:?}", state),
+                }
+
+                assert_eq!(list[3].state(), &MigrationState::PendingData);
+
+                let pending = pending(client, MIGRATIONS).await.unwrap();
+                let names: Vec<_> = pending.iter().map(|s| s.name()).collect();
+                let expected = vec![Some("add_color"), Some("add_wheels"), Some("fill_color")];
+                assert_eq!(names, expected);
+
+                let script = dry_run(client, MIGRATIONS).await.unwrap();
+                assert!(!script.contains("create_cars"));
+                assert!(script.starts_with(&format!("-- 1: add_color\n{};\n", list[1].sql()[0])));
+                assert!(script
+                    .contains("-- 2: can't be written until the migrations before it have ran"));
+                assert!(script.contains("-- 3: fill_color (data migration, runs rust code)"));
+
+                // nothing was ran
+                let rows = client.fetch_rows("SELECT name FROM _welds_migrations", &[]);
+                assert_eq!(rows.await.unwrap().len(), 1);
+                db.teardown().await.unwrap();
+            });
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use crate::detect::TableDef;
-use crate::migrations::types::Type;
+use crate::errors::{Result, WeldsError};
+use crate::migrations::types::{DefaultValue, Type};
 use crate::migrations::writers::add_column;
+use crate::migrations::writers::constraints::drop_mssql_default;
 use crate::migrations::writers::drop_column;
 use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
 use crate::Syntax;
 
 pub struct AddColumn {
@@ -10,6 +13,8 @@ pub struct AddColumn {
     name: String,
     ty: Type,
     null: bool,
+    default: Option<DefaultValue>,
+    check: Option<(String, String)>,
 }
 
 impl AddColumn {
@@ -19,6 +24,8 @@ impl AddColumn {
             name,
             null: false,
             ty,
+            default: None,
+            check: None,
         }
     }
 
@@ -31,6 +38,22 @@ impl AddColumn {
         self.null = false;
         self
     }
+
+    /// The value the database writes to this column when a row is inserted without it.
+    /// Existing rows are given this value, allowing a NOT NULL column to be added to a table with rows
+    ///
+    /// NOTE: Sqlite can only add a column with a constant default.
+    /// `CurrentTimestamp` and `Uuid` fail with a `MigrationError`
+    pub fn default(mut self, value: impl Into<DefaultValue>) -> Self {
+        self.default = Some(value.into());
+        self
+    }
+
+    /// Add a named CHECK constraint to this column. `expr` is the SQL condition to check
+    pub fn check(mut self, name: impl Into<String>, expr: impl Into<String>) -> Self {
+        self.check = Some((name.into(), expr.into()));
+        self
+    }
 }
 
 impl MigrationWriter for AddColumn {
@@ -38,10 +61,51 @@ impl MigrationWriter for AddColumn {
         let col = self.name.as_str();
         let ty = self.ty.db_type(syntax);
         let nullable = self.null;
-        vec![add_column(syntax, &self.tabledef, col, ty, nullable)]
+        let default = self.default.as_ref().map(|d| d.db_default(syntax));
+        let check = self.check.as_ref().map(|(n, e)| (n.as_str(), e.as_str()));
+        vec![add_column(
+            syntax,
+            &self.tabledef,
+            col,
+            ty,
+            nullable,
+            default.as_deref(),
+            check,
+        )]
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        let not_constant = matches!(
+            self.default,
+            Some(DefaultValue::CurrentTimestamp | DefaultValue::Uuid)
+        );
+        if syntax == Syntax::Sqlite && not_constant {
+            let err = format!(
+                "Sqlite can't add the column {} with a default that isn't constant",
+                self.name
+            );
+            return Err(WeldsError::MigrationError(err));
+        }
+        Ok(())
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        let mut commands = Vec::default();
+        // Mssql won't drop a column that has constraints on it
+        if syntax == Syntax::Mssql {
+            let ident = self.tabledef.ident();
+            if self.default.is_some() {
+                commands.push(drop_mssql_default(ident, &self.name));
+            }
+            if let Some((name, _)) = &self.check {
+                commands.push(format!("ALTER TABLE {ident} DROP CONSTRAINT {name}"));
+            }
+        }
+        commands.push(drop_column(&self.tabledef, &self.name));
+        commands
     }
 
-    fn down_sql(&self, _syntax: Syntax) -> Vec<String> {
-        vec![drop_column(&self.tabledef, &self.name)]
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
     }
 }
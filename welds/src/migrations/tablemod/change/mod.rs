@@ -4,9 +4,11 @@ use crate::migrations::utils::find_column_or_unwrap;
 use crate::migrations::writers::add_column;
 use crate::migrations::writers::alter_column_type_down;
 use crate::migrations::writers::alter_column_type_up;
+use crate::migrations::writers::constraints::drop_mssql_default;
 use crate::migrations::writers::drop_column;
 use crate::migrations::writers::rename_column;
 use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
 use crate::Syntax;
 
 pub struct Change {
@@ -143,6 +145,10 @@ impl MigrationWriter for Change {
 
         commands
     }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
 }
 
 pub struct DropColumn {
@@ -151,8 +157,15 @@ pub struct DropColumn {
 }
 
 impl MigrationWriter for DropColumn {
-    fn up_sql(&self, _syntax: Syntax) -> Vec<String> {
-        vec![drop_column(&self.tabledef, &self.column_name)]
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        let col = find_column_or_unwrap(&self.tabledef, &self.column_name);
+        let mut commands = Vec::default();
+        // Mssql won't drop a column that still has a default
+        if syntax == Syntax::Mssql && col.default().is_some() {
+            commands.push(drop_mssql_default(self.tabledef.ident(), col.name()));
+        }
+        commands.push(drop_column(&self.tabledef, &self.column_name));
+        commands
     }
 
     fn down_sql(&self, syntax: Syntax) -> Vec<String> {
@@ -169,6 +182,12 @@ impl MigrationWriter for DropColumn {
             &self.column_name,
             ty,
             nullable,
+            col.default(),
+            None,
         )]
     }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
 }
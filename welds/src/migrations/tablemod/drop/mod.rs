@@ -1,16 +1,34 @@
-use crate::detect::TableDef;
+use crate::detect::{DataType, TableDef, ViewDef};
+use crate::errors::{Result, WeldsError};
 use crate::migrations::writers;
 use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
 use crate::Syntax;
 
 impl MigrationWriter for Drop {
+    // The errors writing the SQL are returned by validate
     fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        if self.tabledef.ty() == DataType::View {
+            let view = self.view().map(|v| v.sql().to_string());
+            return view.into_iter().collect();
+        }
         writers::create_table::from_def(syntax, &self.tabledef)
     }
 
-    fn up_sql(&self, _syntax: Syntax) -> Vec<String> {
-        let tablename = self.tabledef.ident();
-        vec![writers::drop_table(tablename)]
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        self.up(syntax).into_iter().collect()
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        self.up(syntax)?;
+        if self.tabledef.ty() == DataType::View {
+            self.view()?;
+        }
+        Ok(())
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
     }
 }
 
@@ -24,4 +42,24 @@ impl Drop {
     pub(crate) fn new(tabledef: TableDef) -> Drop {
         Drop { tabledef }
     }
+
+    fn up(&self, syntax: Syntax) -> Result<String> {
+        let tablename = self.tabledef.ident();
+        if self.tabledef.ty() == DataType::View {
+            let view = self.tabledef.view();
+            let materialized = view.map(|v| v.materialized()).unwrap_or_default();
+            return writers::view::drop_view_sql(syntax, tablename, materialized);
+        }
+        Ok(writers::drop_table(tablename))
+    }
+
+    /// The definition of the view being dropped, needed to re-create it on down
+    fn view(&self) -> Result<&ViewDef> {
+        self.tabledef.view().ok_or_else(|| {
+            WeldsError::MigrationError(format!(
+                "Could not find the definition of the view '{}' in the database",
+                self.tabledef.ident()
+            ))
+        })
+    }
 }
@@ -0,0 +1,198 @@
+use crate::detect::{ForeignKeyDef, TableDef};
+use crate::errors::{Result, WeldsError};
+use crate::migrations::types::OnDelete;
+use crate::migrations::writers::constraints::{add_foreign_key, drop_foreign_key, on_delete_sql};
+use crate::migrations::writers::sqlite_rebuild_with_foreign_keys;
+use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+/// The parts of a foreign key needed to create it
+struct ForeignKey {
+    name: String,
+    column: String,
+    foreign_table: String,
+    foreign_column: String,
+    on_delete: OnDelete,
+}
+
+impl ForeignKey {
+    fn create_sql(&self, syntax: Syntax, tabledef: &TableDef) -> Vec<String> {
+        // Sqlite can't add a constraint to a table. The table is rebuilt with it
+        if syntax == Syntax::Sqlite {
+            let mut fks = tabledef.foreign_keys().to_vec();
+            fks.push(self.as_def());
+            return sqlite_rebuild_with_foreign_keys(tabledef, &fks);
+        }
+        let sql = add_foreign_key(
+            syntax,
+            tabledef.ident(),
+            &self.name,
+            &self.column,
+            &self.foreign_table,
+            &self.foreign_column,
+            self.on_delete,
+        );
+        sql.into_iter().collect()
+    }
+
+    fn drop_sql(&self, syntax: Syntax, tabledef: &TableDef) -> Vec<String> {
+        // Sqlite can't drop a constraint from a table. The table is rebuilt without it
+        if syntax == Syntax::Sqlite {
+            let fks: Vec<ForeignKeyDef> = tabledef
+                .foreign_keys()
+                .iter()
+                .filter(|fk| !self.matches(fk))
+                .cloned()
+                .collect();
+            return sqlite_rebuild_with_foreign_keys(tabledef, &fks);
+        }
+        let sql = drop_foreign_key(syntax, tabledef.ident(), &self.name);
+        sql.into_iter().collect()
+    }
+
+    /// The SQL to put a Sqlite table back how it was found, with all of its foreign keys
+    fn restore_sql(&self, tabledef: &TableDef) -> Vec<String> {
+        sqlite_rebuild_with_foreign_keys(tabledef, tabledef.foreign_keys())
+    }
+
+    /// This foreign key, written in a rebuilt Sqlite table
+    fn as_def(&self) -> ForeignKeyDef {
+        ForeignKeyDef {
+            name: Some(self.name.clone()),
+            columns: vec![self.column.clone()],
+            foreign_table: self.foreign_table.clone(),
+            foreign_columns: vec![self.foreign_column.clone()],
+            on_delete: on_delete_sql(self.on_delete).to_string(),
+            on_update: "NO ACTION".to_string(),
+        }
+    }
+
+    /// True if a foreign key found on the table is this foreign key.
+    /// Sqlite doesn't keep the names of foreign keys, they are matched on their columns
+    fn matches(&self, fk: &ForeignKeyDef) -> bool {
+        let same = |a: &str, b: &str| a.eq_ignore_ascii_case(b);
+        let foreign_column = match fk.foreign_columns() {
+            [] => true,
+            [c] => same(c, &self.foreign_column),
+            _ => false,
+        };
+        matches!(fk.columns(), [c] if same(c, &self.column))
+            && same(fk.foreign_table(), &self.foreign_table)
+            && foreign_column
+    }
+}
+
+/// Adds a foreign key to a table.
+///
+/// NOTE: Sqlite can't add a foreign key to an existing table, the table is rebuilt with it
+pub struct AddForeignKey {
+    tabledef: TableDef,
+    fk: ForeignKey,
+}
+
+impl AddForeignKey {
+    pub(crate) fn new(
+        tabledef: TableDef,
+        column: String,
+        foreign_table: String,
+        foreign_column: String,
+        on_delete: OnDelete,
+    ) -> Self {
+        let name = format!("fk_{}_{}", tabledef.ident().name(), column);
+        let fk = ForeignKey {
+            name,
+            column,
+            foreign_table,
+            foreign_column,
+            on_delete,
+        };
+        Self { tabledef, fk }
+    }
+
+    /// Override the name of the foreign key. Defaults to fk_{table}_{column}
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.fk.name = name.into();
+        self
+    }
+}
+
+impl MigrationWriter for AddForeignKey {
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        self.fk.create_sql(syntax, &self.tabledef)
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        if syntax == Syntax::Sqlite {
+            return self.fk.restore_sql(&self.tabledef);
+        }
+        self.fk.drop_sql(syntax, &self.tabledef)
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
+
+/// Drops a foreign key. The key is described in full so it can be re-created on down
+///
+/// NOTE: Sqlite can't drop a foreign key from an existing table, the table is rebuilt without it
+pub struct DropForeignKey {
+    tabledef: TableDef,
+    fk: ForeignKey,
+}
+
+impl DropForeignKey {
+    pub(crate) fn new(
+        tabledef: TableDef,
+        name: String,
+        column: String,
+        foreign_table: String,
+        foreign_column: String,
+        on_delete: OnDelete,
+    ) -> Self {
+        let fk = ForeignKey {
+            name,
+            column,
+            foreign_table,
+            foreign_column,
+            on_delete,
+        };
+        Self { tabledef, fk }
+    }
+}
+
+impl MigrationWriter for DropForeignKey {
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        self.fk.drop_sql(syntax, &self.tabledef)
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        if syntax == Syntax::Sqlite {
+            return self.fk.restore_sql(&self.tabledef);
+        }
+        self.fk.create_sql(syntax, &self.tabledef)
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        // Sqlite rebuilds the table without the key, it must be there to be left out
+        let found = self
+            .tabledef
+            .foreign_keys()
+            .iter()
+            .any(|fk| self.fk.matches(fk));
+        if syntax == Syntax::Sqlite && !found {
+            let err = format!(
+                "The foreign key {} was not found on {}",
+                self.fk.name,
+                self.tabledef.ident()
+            );
+            return Err(WeldsError::MigrationError(err));
+        }
+        Ok(())
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
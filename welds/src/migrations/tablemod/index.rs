@@ -0,0 +1,139 @@
+use crate::detect::TableDef;
+use crate::errors::{Result, WeldsError};
+use crate::migrations::writers::constraints::{create_index, drop_index};
+use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+/// The parts of an index needed to create it
+struct IndexDef {
+    name: String,
+    columns: Vec<String>,
+    unique: bool,
+    filter: Option<String>,
+}
+
+impl IndexDef {
+    fn new(tabledef: &TableDef, name: Option<String>, columns: &[&str]) -> Self {
+        let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+        let name =
+            name.unwrap_or_else(|| format!("ix_{}_{}", tabledef.ident().name(), columns.join("_")));
+        Self {
+            name,
+            columns,
+            unique: false,
+            filter: None,
+        }
+    }
+
+    fn create_sql(&self, syntax: Syntax, tabledef: &TableDef) -> String {
+        let filter = self.filter.as_deref();
+        let (name, cols, unique) = (&self.name, &self.columns, self.unique);
+        create_index(syntax, tabledef.ident(), name, cols, unique, filter)
+    }
+
+    fn drop_sql(&self, syntax: Syntax, tabledef: &TableDef) -> String {
+        drop_index(syntax, tabledef.ident(), &self.name)
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        if syntax == Syntax::Mysql && self.filter.is_some() {
+            let err = format!("MySql does not support partial indexes: {}", self.name);
+            return Err(WeldsError::MigrationError(err));
+        }
+        Ok(())
+    }
+}
+
+pub struct CreateIndex {
+    tabledef: TableDef,
+    index: IndexDef,
+}
+
+impl CreateIndex {
+    pub(crate) fn new(tabledef: TableDef, columns: &[&str]) -> Self {
+        let index = IndexDef::new(&tabledef, None, columns);
+        Self { tabledef, index }
+    }
+
+    /// Only allow unique values in the indexed columns
+    pub fn unique(mut self) -> Self {
+        self.index.unique = true;
+        self
+    }
+
+    /// Override the name of the index. Defaults to ix_{table}_{columns}
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.index.name = name.into();
+        self
+    }
+
+    /// Only index the rows matching this SQL condition. (partial/filtered index)
+    ///
+    /// NOTE: MySql does not support partial indexes, the migration fails with a `MigrationError`
+    pub fn filter(mut self, condition: impl Into<String>) -> Self {
+        self.index.filter = Some(condition.into());
+        self
+    }
+}
+
+impl MigrationWriter for CreateIndex {
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        vec![self.index.create_sql(syntax, &self.tabledef)]
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        vec![self.index.drop_sql(syntax, &self.tabledef)]
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        self.index.validate(syntax)
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
+
+/// Drops an index. The index is described in full so it can be re-created on down
+pub struct DropIndex {
+    tabledef: TableDef,
+    index: IndexDef,
+}
+
+impl DropIndex {
+    pub(crate) fn new(tabledef: TableDef, name: String, columns: &[&str]) -> Self {
+        let index = IndexDef::new(&tabledef, Some(name), columns);
+        Self { tabledef, index }
+    }
+
+    /// The index being dropped is unique
+    pub fn unique(mut self) -> Self {
+        self.index.unique = true;
+        self
+    }
+
+    /// The index being dropped is a partial index on this SQL condition
+    pub fn filter(mut self, condition: impl Into<String>) -> Self {
+        self.index.filter = Some(condition.into());
+        self
+    }
+}
+
+impl MigrationWriter for DropIndex {
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        vec![self.index.drop_sql(syntax, &self.tabledef)]
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        vec![self.index.create_sql(syntax, &self.tabledef)]
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        self.index.validate(syntax)
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
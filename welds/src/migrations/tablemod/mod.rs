@@ -1,12 +1,17 @@
 use super::TableState;
 use crate::detect::TableDef;
 use crate::errors::{Result, WeldsError};
-use crate::migrations::types::Type;
+use crate::migrations::types::{OnDelete, Type};
 use crate::model_traits::TableIdent;
 
 pub mod add_column;
 pub mod change;
 pub mod drop;
+pub mod foreign_key;
+pub mod index;
+pub mod refresh;
+pub mod rename;
+pub mod unique;
 
 pub struct Table(TableDef);
 
@@ -64,10 +69,94 @@ impl Table {
         drop::Drop::new(self.0)
     }
 
+    /// Refresh the stored results of this materialized view
+    ///
+    /// NOTE: Only Postgres has materialized views, other databases fail with a `MigrationError`
+    pub fn refresh(self) -> refresh::Refresh {
+        refresh::Refresh::new(self.0)
+    }
+
+    /// Rename this table. The table stays in the same schema
+    pub fn rename(self, new_name: impl Into<String>) -> rename::RenameTable {
+        rename::RenameTable::new(self.0, new_name.into())
+    }
+
+    /// Move this table into another schema. The table keeps its name
+    pub fn set_schema(self, schema: impl Into<String>) -> rename::SetSchema {
+        rename::SetSchema::new(self.0, schema.into())
+    }
+
     /// Add a new column onto this table
     pub fn add_column(self, column_name: impl Into<String>, ty: Type) -> add_column::AddColumn {
         add_column::AddColumn::new(self.0, column_name.into(), ty)
     }
+
+    /// Create an index on one or more columns of this table
+    pub fn create_index(self, columns: &[&str]) -> index::CreateIndex {
+        index::CreateIndex::new(self.0, columns)
+    }
+
+    /// Drop an index from this table.
+    /// The columns of the index are needed to re-create it when migrating down
+    pub fn drop_index(self, name: impl Into<String>, columns: &[&str]) -> index::DropIndex {
+        index::DropIndex::new(self.0, name.into(), columns)
+    }
+
+    /// Add a unique constraint over one or more columns of this table
+    pub fn add_unique_constraint(self, columns: &[&str]) -> unique::AddUnique {
+        unique::AddUnique::new(self.0, columns)
+    }
+
+    /// Drop a unique constraint from this table.
+    /// The columns of the constraint are needed to re-create it when migrating down
+    pub fn drop_unique_constraint(
+        self,
+        name: impl Into<String>,
+        columns: &[&str],
+    ) -> unique::DropUnique {
+        unique::DropUnique::new(self.0, name.into(), columns)
+    }
+
+    /// Add a foreign key from a column on this table to a column on another table
+    ///
+    /// NOTE: Sqlite rebuilds the table to add the foreign key
+    pub fn add_foreign_key(
+        self,
+        column: impl Into<String>,
+        foreign_table: impl Into<String>,
+        foreign_column: impl Into<String>,
+        on_delete: OnDelete,
+    ) -> foreign_key::AddForeignKey {
+        foreign_key::AddForeignKey::new(
+            self.0,
+            column.into(),
+            foreign_table.into(),
+            foreign_column.into(),
+            on_delete,
+        )
+    }
+
+    /// Drop a foreign key from this table.
+    /// The foreign key is needed in full to re-create it when migrating down
+    ///
+    /// NOTE: Sqlite rebuilds the table to drop the foreign key. It is found by its columns
+    pub fn drop_foreign_key(
+        self,
+        name: impl Into<String>,
+        column: impl Into<String>,
+        foreign_table: impl Into<String>,
+        foreign_column: impl Into<String>,
+        on_delete: OnDelete,
+    ) -> foreign_key::DropForeignKey {
+        foreign_key::DropForeignKey::new(
+            self.0,
+            name.into(),
+            column.into(),
+            foreign_table.into(),
+            foreign_column.into(),
+            on_delete,
+        )
+    }
 }
 
 /// This module allows you to mock Table
@@ -0,0 +1,40 @@
+use crate::detect::TableDef;
+use crate::errors::Result;
+use crate::migrations::writers::view::refresh_view_sql;
+use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+/// Refreshes the stored results of a materialized view.
+/// Only Postgres has materialized views, other databases fail with a `MigrationError`
+pub struct Refresh {
+    tabledef: TableDef,
+}
+
+impl Refresh {
+    pub(crate) fn new(tabledef: TableDef) -> Self {
+        Self { tabledef }
+    }
+}
+
+impl MigrationWriter for Refresh {
+    // The error writing the SQL is returned by validate
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        let sql = refresh_view_sql(syntax, self.tabledef.ident());
+        sql.into_iter().collect()
+    }
+
+    fn down_sql(&self, _syntax: Syntax) -> Vec<String> {
+        // The results from before the refresh are gone, nothing to undo
+        Vec::default()
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        refresh_view_sql(syntax, self.tabledef.ident())?;
+        Ok(())
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
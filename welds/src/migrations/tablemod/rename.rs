@@ -0,0 +1,80 @@
+use crate::detect::TableDef;
+use crate::errors::Result;
+use crate::migrations::writers::{rename_table, set_schema};
+use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+/// Renames a table. Foreign keys referencing the table follow it to its new name
+pub struct RenameTable {
+    tabledef: TableDef,
+    new_name: String,
+}
+
+impl RenameTable {
+    pub(crate) fn new(tabledef: TableDef, new_name: String) -> Self {
+        Self { tabledef, new_name }
+    }
+}
+
+impl MigrationWriter for RenameTable {
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        vec![rename_table(syntax, self.tabledef.ident(), &self.new_name)]
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        let ident = self.tabledef.ident();
+        let renamed = TableIdent::new(&self.new_name, ident.schema());
+        vec![rename_table(syntax, &renamed, ident.name())]
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
+
+/// Moves a table to another schema. Foreign keys referencing the table follow it to its new schema
+///
+/// NOTE: Sqlite does not have schemas to move a table between, the migration fails with a `MigrationError`
+pub struct SetSchema {
+    tabledef: TableDef,
+    schema: String,
+}
+
+impl SetSchema {
+    pub(crate) fn new(tabledef: TableDef, schema: String) -> Self {
+        Self { tabledef, schema }
+    }
+
+    fn up(&self, syntax: Syntax) -> Result<Vec<String>> {
+        let ident = self.tabledef.ident();
+        Ok(vec![set_schema(syntax, ident, Some(&self.schema))?])
+    }
+
+    fn down(&self, syntax: Syntax) -> Result<Vec<String>> {
+        let ident = self.tabledef.ident();
+        let moved = TableIdent::new(ident.name(), Some(&self.schema));
+        Ok(vec![set_schema(syntax, &moved, ident.schema())?])
+    }
+}
+
+impl MigrationWriter for SetSchema {
+    // The errors of up/down are returned by validate
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        self.up(syntax).unwrap_or_default()
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        self.down(syntax).unwrap_or_default()
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        self.up(syntax)?;
+        self.down(syntax)?;
+        Ok(())
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
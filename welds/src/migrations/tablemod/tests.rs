@@ -49,6 +49,50 @@ fn down_should_recreate_the_table() {
     assert_eq!(sql, expected.trim());
 }
 
+#[test]
+fn sqlite_down_should_recreate_the_foreign_keys_of_a_dropped_table() {
+    let table = Table::mock(
+        MockTableDef::new(Syntax::Sqlite, "cars")
+            .with_pk("id", "INTEGER")
+            .with_column("make_id", "INTEGER")
+            .with_foreign_key("make_id", "makes", "id", "CASCADE"),
+    );
+    let m = table.drop();
+    let expected = "CREATE TABLE cars ( id INTEGER PRIMARY KEY, make_id INTEGER NOT NULL, FOREIGN KEY (make_id) REFERENCES makes (id) ON DELETE CASCADE )";
+    let sql = MigrationWriter::down_sql(&m, Syntax::Sqlite).join("; ");
+    assert_eq!(sql, expected);
+}
+
+#[test]
+fn down_should_recreate_a_table_with_a_composite_primary_key() {
+    let table = Table::mock(
+        MockTableDef::new(Syntax::Postgres, "s2.user_groups")
+            .with_pk("user_id", "INT4")
+            .with_pk("group_id", "INT4")
+            .with_nullable_column("role", "TEXT"),
+    );
+    let m = table.drop();
+    let expected = r#"
+    CREATE TABLE s2.user_groups ( user_id INT4 NOT NULL, group_id INT4 NOT NULL, role TEXT NULL, PRIMARY KEY ( user_id, group_id ) )"#;
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, expected.trim());
+}
+
+#[test]
+fn sqlite_should_keep_a_composite_primary_key_when_changing_a_column() {
+    let table = Table::mock(
+        MockTableDef::new(Syntax::Sqlite, "user_groups")
+            .with_pk("user_id", "INTEGER")
+            .with_pk("group_id", "INTEGER")
+            .with_column("role", "TEXT"),
+    );
+    let m = table.change("role").null();
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite);
+    let expected = r#"
+    CREATE TABLE user_groups_weldstmp ( user_id INTEGER NOT NULL, group_id INTEGER NOT NULL, role TEXT NULL, PRIMARY KEY ( user_id, group_id ) )"#;
+    assert_eq!(sql[0], expected.trim());
+}
+
 #[test]
 fn should_be_able_to_rename_column() {
     let table = mock_table(Syntax::Postgres);
@@ -97,3 +141,581 @@ fn should_should_recreate_a_dropped_column() {
     let expected_down = r#"ALTER TABLE s2.cars ADD COLUMN name VARCHAR(255) NOT NULL"#;
     assert_eq!(sql, expected_down);
 }
+
+#[test]
+fn should_be_able_to_create_a_multi_column_unique_index() {
+    let m = mock_table(Syntax::Postgres)
+        .create_index(&["id", "name"])
+        .unique();
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(
+        sql,
+        "CREATE UNIQUE INDEX ix_cars_id_name ON s2.cars ( id, name )"
+    );
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, "DROP INDEX s2.ix_cars_id_name");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, "DROP INDEX ix_cars_id_name ON s2.cars");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, "DROP INDEX ix_cars_id_name ON s2.cars");
+}
+
+#[test]
+fn should_be_able_to_create_a_partial_index() {
+    let m = mock_table(Syntax::Sqlite)
+        .create_index(&["name"])
+        .name("ix_named")
+        .filter("name IS NOT NULL");
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite).join("; ");
+    let expected = "CREATE INDEX ix_named ON s2.cars ( name ) WHERE name IS NOT NULL";
+    assert_eq!(sql, expected);
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, expected);
+}
+
+#[test]
+fn mysql_should_not_create_a_partial_index() {
+    let m = mock_table(Syntax::Mysql)
+        .create_index(&["name"])
+        .filter("name IS NOT NULL");
+    let err = MigrationWriter::validate(&m, Syntax::Mysql).unwrap_err();
+    assert!(matches!(err, WeldsError::MigrationError(_)), "{err}");
+    assert!(MigrationWriter::validate(&m, Syntax::Postgres).is_ok());
+
+    let m = mock_table(Syntax::Mysql)
+        .drop_index("ix_old", &["name"])
+        .filter("name IS NOT NULL");
+    assert!(MigrationWriter::validate(&m, Syntax::Mysql).is_err());
+}
+
+#[test]
+fn down_should_recreate_a_dropped_index() {
+    let m = mock_table(Syntax::Mssql)
+        .drop_index("ix_old", &["name"])
+        .unique()
+        .filter("name IS NOT NULL");
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, "DROP INDEX ix_old ON s2.cars");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mssql).join("; ");
+    let expected = "CREATE UNIQUE INDEX ix_old ON s2.cars ( name ) WHERE name IS NOT NULL";
+    assert_eq!(sql, expected);
+}
+
+#[test]
+fn should_be_able_to_add_a_unique_constraint() {
+    let m = mock_table(Syntax::Postgres).add_unique_constraint(&["id", "name"]);
+    let up = "ALTER TABLE s2.cars ADD CONSTRAINT uq_cars_id_name UNIQUE ( id, name )";
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, up);
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, up);
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite).join("; ");
+    assert_eq!(
+        sql,
+        "CREATE UNIQUE INDEX uq_cars_id_name ON s2.cars ( id, name )"
+    );
+
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars DROP CONSTRAINT uq_cars_id_name");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars DROP CONSTRAINT uq_cars_id_name");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars DROP INDEX uq_cars_id_name");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Sqlite).join("; ");
+    assert_eq!(sql, "DROP INDEX s2.uq_cars_id_name");
+}
+
+#[test]
+fn down_should_recreate_a_dropped_unique_constraint() {
+    let m = mock_table(Syntax::Mssql).drop_unique_constraint("uq_old", &["name"]);
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars DROP CONSTRAINT uq_old");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(
+        sql,
+        "ALTER TABLE s2.cars ADD CONSTRAINT uq_old UNIQUE ( name )"
+    );
+}
+
+#[test]
+fn should_be_able_to_add_a_foreign_key() {
+    let m = mock_table(Syntax::Postgres).add_foreign_key("id", "s2.makes", "id", OnDelete::Cascade);
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    let expected = "ALTER TABLE s2.cars ADD CONSTRAINT fk_cars_id FOREIGN KEY (id) REFERENCES s2.makes (id) ON DELETE CASCADE";
+    assert_eq!(sql, expected);
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars DROP CONSTRAINT fk_cars_id");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars DROP FOREIGN KEY fk_cars_id");
+    assert!(MigrationWriter::validate(&m, Syntax::Postgres).is_ok());
+}
+
+#[test]
+fn sqlite_should_rebuild_the_table_to_add_a_foreign_key() {
+    let table = Table::mock(
+        MockTableDef::new(Syntax::Sqlite, "cars")
+            .with_pk("id", "INT")
+            .with_column("owner_id", "INT")
+            .with_column("make_id", "INT")
+            .with_foreign_key("owner_id", "people", "id", "SET NULL"),
+    );
+    let m = table.add_foreign_key("make_id", "makes", "id", OnDelete::Cascade);
+    assert!(MigrationWriter::validate(&m, Syntax::Sqlite).is_ok());
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite);
+    let expected = "CREATE TABLE cars_weldstmp ( id INTEGER PRIMARY KEY AUTOINCREMENT, owner_id INT NOT NULL, make_id INT NOT NULL, FOREIGN KEY (owner_id) REFERENCES people (id) ON DELETE SET NULL, CONSTRAINT fk_cars_make_id FOREIGN KEY (make_id) REFERENCES makes (id) ON DELETE CASCADE )";
+    assert_eq!(sql[0], expected);
+    let expected = "INSERT INTO cars_weldstmp ( id, owner_id, make_id ) SELECT id, owner_id, make_id FROM cars";
+    assert_eq!(sql[1], expected);
+    assert_eq!(sql[2], "DROP TABLE cars");
+    assert_eq!(sql[3], "ALTER TABLE cars_weldstmp RENAME TO cars");
+
+    let sql = MigrationWriter::down_sql(&m, Syntax::Sqlite);
+    let expected = "CREATE TABLE cars_weldstmp ( id INTEGER PRIMARY KEY AUTOINCREMENT, owner_id INT NOT NULL, make_id INT NOT NULL, FOREIGN KEY (owner_id) REFERENCES people (id) ON DELETE SET NULL )";
+    assert_eq!(sql[0], expected);
+}
+
+#[test]
+fn sqlite_should_rebuild_the_table_to_drop_a_foreign_key() {
+    let table = || {
+        Table::mock(
+            MockTableDef::new(Syntax::Sqlite, "cars")
+                .with_pk("id", "INT")
+                .with_column("owner_id", "INT")
+                .with_column("make_id", "INT")
+                .with_foreign_key("owner_id", "people", "id", "SET NULL")
+                .with_foreign_key("make_id", "makes", "id", "CASCADE"),
+        )
+    };
+    let m = table().drop_foreign_key(
+        "fk_cars_make_id",
+        "make_id",
+        "makes",
+        "id",
+        OnDelete::Cascade,
+    );
+    assert!(MigrationWriter::validate(&m, Syntax::Sqlite).is_ok());
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite);
+    let expected = "CREATE TABLE cars_weldstmp ( id INTEGER PRIMARY KEY AUTOINCREMENT, owner_id INT NOT NULL, make_id INT NOT NULL, FOREIGN KEY (owner_id) REFERENCES people (id) ON DELETE SET NULL )";
+    assert_eq!(sql[0], expected);
+
+    let sql = MigrationWriter::down_sql(&m, Syntax::Sqlite);
+    let expected = "CREATE TABLE cars_weldstmp ( id INTEGER PRIMARY KEY AUTOINCREMENT, owner_id INT NOT NULL, make_id INT NOT NULL, FOREIGN KEY (owner_id) REFERENCES people (id) ON DELETE SET NULL, FOREIGN KEY (make_id) REFERENCES makes (id) ON DELETE CASCADE )";
+    assert_eq!(sql[0], expected);
+
+    // a foreign key that isn't on the table can't be dropped
+    let m = table().drop_foreign_key("fk_cars_id", "id", "makes", "id", OnDelete::Cascade);
+    let err = MigrationWriter::validate(&m, Syntax::Sqlite).unwrap_err();
+    assert!(matches!(err, WeldsError::MigrationError(_)), "{err}");
+}
+
+#[test]
+fn sqlite_should_keep_the_foreign_keys_of_a_rebuilt_table() {
+    let table = Table::mock(
+        MockTableDef::new(Syntax::Sqlite, "cars")
+            .with_pk("id", "INT")
+            .with_column("make_id", "INT")
+            .with_foreign_key("make_id", "makes", "id", "CASCADE"),
+    );
+    let m = table.change("make_id").rename("maker_id").null();
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite);
+    let expected = "CREATE TABLE cars_weldstmp ( id INTEGER PRIMARY KEY AUTOINCREMENT, maker_id INT NULL, FOREIGN KEY (maker_id) REFERENCES makes (id) ON DELETE CASCADE )";
+    assert_eq!(sql[1], expected);
+}
+
+#[test]
+fn down_should_recreate_a_dropped_foreign_key() {
+    let m = mock_table(Syntax::Mysql).drop_foreign_key(
+        "fk_old",
+        "id",
+        "s2.makes",
+        "id",
+        OnDelete::SetNull,
+    );
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars DROP FOREIGN KEY fk_old");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mysql).join("; ");
+    let expected = "ALTER TABLE s2.cars ADD CONSTRAINT fk_old FOREIGN KEY (id) REFERENCES s2.makes (id) ON DELETE SET NULL";
+    assert_eq!(sql, expected);
+}
+
+fn mock_table_with_default(syntax: Syntax) -> Table {
+    use crate::detect::table_def::mock::MockColumnDef;
+    Table::mock(
+        MockTableDef::new(syntax, "s2.cars")
+            .with_pk("id", "INT")
+            .with_column_def(MockColumnDef::new("wheels", "INT").default("4")),
+    )
+}
+
+#[test]
+fn should_add_a_not_null_column_with_a_default() {
+    let m = mock_table(Syntax::Postgres)
+        .add_column("wheels", Type::Int)
+        .default(4)
+        .check("ck_cars_wheels", "wheels > 0");
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    let expected = "ALTER TABLE s2.cars ADD COLUMN wheels INT NOT NULL DEFAULT 4 CONSTRAINT ck_cars_wheels CHECK (wheels > 0)";
+    assert_eq!(sql, expected);
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars DROP COLUMN wheels");
+}
+
+#[test]
+fn mssql_should_drop_the_constraints_of_an_added_column_on_down() {
+    let m = mock_table(Syntax::Mssql)
+        .add_column("wheels", Type::Int)
+        .default(4)
+        .check("ck_cars_wheels", "wheels > 0");
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    let expected = "ALTER TABLE s2.cars ADD wheels INT NOT NULL CONSTRAINT df_cars_wheels DEFAULT 4 CONSTRAINT ck_cars_wheels CHECK (wheels > 0)";
+    assert_eq!(sql, expected);
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mssql);
+    assert_eq!(sql.len(), 3);
+    assert!(sql[0].contains("sys.default_constraints"));
+    assert_eq!(sql[1], "ALTER TABLE s2.cars DROP CONSTRAINT ck_cars_wheels");
+    assert_eq!(sql[2], "ALTER TABLE s2.cars DROP COLUMN wheels");
+}
+
+#[test]
+fn down_should_recreate_the_default_of_a_dropped_column() {
+    let m = mock_table_with_default(Syntax::Postgres)
+        .change("wheels")
+        .drop_column();
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(
+        sql,
+        "ALTER TABLE s2.cars ADD COLUMN wheels INT NOT NULL DEFAULT 4"
+    );
+}
+
+#[test]
+fn down_should_recreate_a_table_with_its_defaults() {
+    let m = mock_table_with_default(Syntax::Postgres).drop();
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    let expected = "CREATE TABLE s2.cars ( id SERIAL PRIMARY KEY, wheels INT NOT NULL DEFAULT 4 )";
+    assert_eq!(sql, expected);
+}
+
+#[test]
+fn changing_a_column_should_keep_its_default() {
+    let m = mock_table_with_default(Syntax::Mysql)
+        .change("wheels")
+        .null();
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mysql).join("; ");
+    let expected = "ALTER TABLE s2.cars MODIFY COLUMN wheels INT NULL DEFAULT 4";
+    assert_eq!(sql, expected);
+
+    let m = mock_table_with_default(Syntax::Sqlite)
+        .change("wheels")
+        .null();
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite);
+    let expected = "CREATE TABLE s2.cars_weldstmp ( id INTEGER PRIMARY KEY AUTOINCREMENT, wheels INT NULL DEFAULT 4 )";
+    assert_eq!(sql[0], expected);
+}
+
+#[test]
+fn sqlite_should_keep_the_checks_of_a_rebuilt_table() {
+    let table = Table::mock(
+        MockTableDef::new(Syntax::Sqlite, "cars")
+            .with_pk("id", "INT")
+            .with_column("wheels", "INT")
+            .with_check(Some("wheels"), Some("ck_cars_wheels"), "wheels > 0")
+            .with_check(None, None, "wheels < 20"),
+    );
+    let m = table.change("wheels").null();
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite);
+    let expected = "CREATE TABLE cars_weldstmp ( id INTEGER PRIMARY KEY AUTOINCREMENT, wheels INT NULL CONSTRAINT ck_cars_wheels CHECK (wheels > 0), CHECK (wheels < 20) )";
+    assert_eq!(sql[0], expected);
+}
+
+#[test]
+fn sqlite_should_rename_the_column_in_the_checks_of_a_rebuilt_table() {
+    let table = Table::mock(
+        MockTableDef::new(Syntax::Sqlite, "cars")
+            .with_pk("id", "INT")
+            .with_column("wheels", "INT")
+            .with_check(Some("wheels"), Some("ck_cars_wheels"), "wheels > 0")
+            .with_check(None, None, "wheels < 20"),
+    );
+    let m = table.change("wheels").rename("tires").to_type(Type::IntBig);
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite);
+    assert_eq!(sql[0], "ALTER TABLE cars RENAME wheels TO tires");
+    let expected = "CREATE TABLE cars_weldstmp ( id INTEGER PRIMARY KEY AUTOINCREMENT, tires INTEGER NOT NULL CONSTRAINT ck_cars_wheels CHECK (tires > 0), CHECK (tires < 20) )";
+    assert_eq!(sql[1], expected);
+    let expected =
+        "INSERT INTO cars_weldstmp ( id, tires ) SELECT id, CAST(tires AS INTEGER) FROM cars";
+    assert_eq!(sql[2], expected);
+}
+
+#[test]
+fn sqlite_should_not_add_a_column_with_a_default_that_is_not_constant() {
+    use crate::migrations::types::DefaultValue;
+    let m = mock_table(Syntax::Sqlite)
+        .add_column("created_at", Type::Datetime)
+        .default(DefaultValue::CurrentTimestamp);
+    let err = MigrationWriter::validate(&m, Syntax::Sqlite).unwrap_err();
+    assert!(matches!(err, WeldsError::MigrationError(_)), "{err}");
+    assert!(MigrationWriter::validate(&m, Syntax::Postgres).is_ok());
+
+    let m = mock_table(Syntax::Sqlite)
+        .add_column("uid", Type::Uuid)
+        .default(DefaultValue::Uuid);
+    assert!(MigrationWriter::validate(&m, Syntax::Sqlite).is_err());
+
+    let m = mock_table(Syntax::Sqlite)
+        .add_column("wheels", Type::Int)
+        .default(4);
+    assert!(MigrationWriter::validate(&m, Syntax::Sqlite).is_ok());
+}
+
+#[test]
+fn should_be_able_to_rename_a_table() {
+    let m = mock_table(Syntax::Postgres).rename("autos");
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars RENAME TO autos");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.autos RENAME TO cars");
+
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, "EXEC sp_rename 's2.cars', 'autos'");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, "EXEC sp_rename 's2.autos', 'cars'");
+
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, "RENAME TABLE s2.cars TO s2.autos");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, "RENAME TABLE s2.autos TO s2.cars");
+
+    let sql = MigrationWriter::up_sql(&m, Syntax::Sqlite).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars RENAME TO autos");
+}
+
+#[test]
+fn should_be_able_to_move_a_table_to_another_schema() {
+    let m = mock_table(Syntax::Postgres).set_schema("archive");
+    let sql = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, "ALTER TABLE s2.cars SET SCHEMA archive");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(sql, "ALTER TABLE archive.cars SET SCHEMA s2");
+
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, "ALTER SCHEMA archive TRANSFER s2.cars");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(sql, "ALTER SCHEMA s2 TRANSFER archive.cars");
+
+    let sql = MigrationWriter::up_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, "RENAME TABLE s2.cars TO archive.cars");
+    let sql = MigrationWriter::down_sql(&m, Syntax::Mysql).join("; ");
+    assert_eq!(sql, "RENAME TABLE archive.cars TO s2.cars");
+}
+
+#[test]
+fn sqlite_should_not_move_a_table_to_another_schema() {
+    let m = mock_table(Syntax::Sqlite).set_schema("archive");
+    let err = MigrationWriter::validate(&m, Syntax::Sqlite).unwrap_err();
+    assert!(matches!(err, WeldsError::MigrationError(_)), "{err}");
+}
+
+#[test]
+fn should_not_move_a_table_without_a_schema_back_on_postgres() {
+    let table = Table::mock(MockTableDef::new(Syntax::Postgres, "cars").with_pk("id", "INT"));
+    let m = table.set_schema("archive");
+    assert!(MigrationWriter::validate(&m, Syntax::Postgres).is_err());
+    assert!(MigrationWriter::validate(&m, Syntax::Mysql).is_ok());
+}
+
+#[test]
+fn should_drop_and_recreate_a_view() {
+    let sql = "CREATE VIEW s2.old_cars AS SELECT id FROM s2.cars";
+    let table = Table::mock(MockTableDef::new(Syntax::Mssql, "s2.old_cars").as_view_of(sql, false));
+    let m = table.drop();
+    let up = MigrationWriter::up_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(up, "DROP VIEW s2.old_cars");
+    let down = MigrationWriter::down_sql(&m, Syntax::Mssql).join("; ");
+    assert_eq!(down, sql);
+}
+
+#[test]
+fn should_drop_and_recreate_a_materialized_view() {
+    let sql = "CREATE MATERIALIZED VIEW s2.old_cars AS SELECT id FROM s2.cars";
+    let table =
+        Table::mock(MockTableDef::new(Syntax::Postgres, "s2.old_cars").as_view_of(sql, true));
+    let m = table.drop();
+    let up = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(up, "DROP MATERIALIZED VIEW s2.old_cars");
+    let down = MigrationWriter::down_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(down, sql);
+}
+
+#[test]
+fn should_refresh_a_materialized_view() {
+    let sql = "CREATE MATERIALIZED VIEW s2.old_cars AS SELECT id FROM s2.cars";
+    let table =
+        Table::mock(MockTableDef::new(Syntax::Postgres, "s2.old_cars").as_view_of(sql, true));
+    let m = table.refresh();
+    let up = MigrationWriter::up_sql(&m, Syntax::Postgres).join("; ");
+    assert_eq!(up, "REFRESH MATERIALIZED VIEW s2.old_cars");
+    assert!(MigrationWriter::down_sql(&m, Syntax::Postgres).is_empty());
+    let err = MigrationWriter::validate(&m, Syntax::Mysql).unwrap_err();
+    assert!(matches!(err, WeldsError::MigrationError(_)), "{err}");
+}
+
+#[test]
+fn should_not_drop_a_view_it_can_not_recreate() {
+    let table = Table::mock(MockTableDef::new(Syntax::Postgres, "s2.old_cars").as_view());
+    let m = table.drop();
+    let err = MigrationWriter::validate(&m, Syntax::Postgres).unwrap_err();
+    assert!(matches!(err, WeldsError::MigrationError(_)), "{err}");
+    assert!(MigrationWriter::down_sql(&m, Syntax::Postgres).is_empty());
+}
+
+#[cfg(feature = "sqlite")]
+use crate::migrations::MigrationStep;
+
+#[cfg(feature = "sqlite")]
+fn create_makes(_: &TableState) -> Result<MigrationStep> {
+    let m = crate::migrations::create_table("makes");
+    Ok(MigrationStep::new("create_makes", m))
+}
+
+#[cfg(feature = "sqlite")]
+fn create_cars(_: &TableState) -> Result<MigrationStep> {
+    let m = crate::migrations::create_table("cars").column(|c| c("make_id", Type::Int));
+    Ok(MigrationStep::new("create_cars", m))
+}
+
+#[cfg(feature = "sqlite")]
+fn add_cars_fk(state: &TableState) -> Result<MigrationStep> {
+    let m =
+        change_table(state, "cars")?.add_foreign_key("make_id", "makes", "id", OnDelete::Cascade);
+    Ok(MigrationStep::new("add_cars_fk", m))
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_should_add_a_foreign_key_and_remove_it_on_down() {
+    use crate::migrations::{down_last, TestDatabase};
+    use crate::Client;
+    futures::executor::block_on(async move {
+        let db = TestDatabase::sqlite(&[create_makes, create_cars, add_cars_fk])
+            .await
+            .unwrap();
+        let client = db.client();
+        client
+            .execute("INSERT INTO makes (id) VALUES (1)", &[])
+            .await
+            .unwrap();
+        let good = client.execute("INSERT INTO cars (make_id) VALUES (1)", &[]);
+        good.await.unwrap();
+        let bad = client.execute("INSERT INTO cars (make_id) VALUES (99)", &[]);
+        assert!(bad.await.is_err());
+        // the ON DELETE CASCADE was kept
+        client.execute("DELETE FROM makes", &[]).await.unwrap();
+        let rows = client.fetch_rows("SELECT id FROM cars", &[]).await.unwrap();
+        assert!(rows.is_empty());
+
+        down_last(client).await.unwrap();
+        let orphan = client.execute("INSERT INTO cars (make_id) VALUES (99)", &[]);
+        orphan.await.unwrap();
+        db.teardown().await.unwrap();
+    });
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_should_drop_a_foreign_key_and_add_it_back_on_down() {
+    use crate::migrations::{down_last, TestDatabase};
+    use crate::Client;
+    fn drop_cars_fk(state: &TableState) -> Result<MigrationStep> {
+        let m = change_table(state, "cars")?.drop_foreign_key(
+            "fk_cars_make_id",
+            "make_id",
+            "makes",
+            "id",
+            OnDelete::Cascade,
+        );
+        Ok(MigrationStep::new("drop_cars_fk", m))
+    }
+    futures::executor::block_on(async move {
+        let steps = [create_makes, create_cars, add_cars_fk, drop_cars_fk];
+        let db = TestDatabase::sqlite(&steps).await.unwrap();
+        let client = db.client();
+        let orphan = client.execute("INSERT INTO cars (make_id) VALUES (99)", &[]);
+        orphan.await.unwrap();
+        client.execute("DELETE FROM cars", &[]).await.unwrap();
+
+        down_last(client).await.unwrap();
+        let bad = client.execute("INSERT INTO cars (make_id) VALUES (99)", &[]);
+        assert!(bad.await.is_err());
+        db.teardown().await.unwrap();
+    });
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_checks_should_still_be_enforced_after_a_column_is_changed() {
+    use crate::migrations::{create_table, MigrationStep, TableState, TestDatabase};
+    use crate::Client;
+    fn create_cars(_: &TableState) -> Result<MigrationStep> {
+        let m = create_table("cars")
+            .column(|c| c("wheels", Type::Int).check("ck_cars_wheels", "wheels > 0"));
+        Ok(MigrationStep::new("create_cars", m))
+    }
+    fn wheels_null(state: &TableState) -> Result<MigrationStep> {
+        let m = change_table(state, "cars")?.change("wheels").null();
+        Ok(MigrationStep::new("wheels_null", m))
+    }
+    futures::executor::block_on(async move {
+        let db = TestDatabase::sqlite(&[create_cars, wheels_null])
+            .await
+            .unwrap();
+        let client = db.client();
+        client
+            .execute("INSERT INTO cars (wheels) VALUES (NULL)", &[])
+            .await
+            .unwrap();
+        let bad = client
+            .execute("INSERT INTO cars (wheels) VALUES (0)", &[])
+            .await;
+        assert!(bad.is_err());
+        db.teardown().await.unwrap();
+    });
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_should_rename_and_change_a_column_with_a_check() {
+    use crate::migrations::{create_table, down_last, MigrationStep, TableState, TestDatabase};
+    use crate::Client;
+    fn create_cars(_: &TableState) -> Result<MigrationStep> {
+        let m = create_table("cars")
+            .column(|c| c("wheels", Type::Int).check("ck_cars_wheels", "wheels > 0"));
+        Ok(MigrationStep::new("create_cars", m))
+    }
+    fn rename_wheels(state: &TableState) -> Result<MigrationStep> {
+        let m = change_table(state, "cars")?
+            .change("wheels")
+            .rename("tires")
+            .to_type(Type::IntBig)
+            .null();
+        Ok(MigrationStep::new("rename_wheels", m))
+    }
+    futures::executor::block_on(async move {
+        let db = TestDatabase::sqlite(&[create_cars, rename_wheels])
+            .await
+            .unwrap();
+        let client = db.client();
+        let insert = "INSERT INTO cars (tires) VALUES (NULL)";
+        client.execute(insert, &[]).await.unwrap();
+        let bad = client.execute("INSERT INTO cars (tires) VALUES (0)", &[]);
+        assert!(bad.await.is_err());
+
+        down_last(client).await.unwrap();
+        client.execute("DELETE FROM cars", &[]).await.unwrap();
+        let bad = client.execute("INSERT INTO cars (wheels) VALUES (0)", &[]);
+        assert!(bad.await.is_err());
+        let good = client.execute("INSERT INTO cars (wheels) VALUES (4)", &[]);
+        good.await.unwrap();
+        db.teardown().await.unwrap();
+    });
+}
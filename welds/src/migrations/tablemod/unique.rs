@@ -0,0 +1,77 @@
+use crate::detect::TableDef;
+use crate::migrations::writers::constraints::{add_unique, drop_unique};
+use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+pub struct AddUnique {
+    tabledef: TableDef,
+    name: String,
+    columns: Vec<String>,
+}
+
+impl AddUnique {
+    pub(crate) fn new(tabledef: TableDef, columns: &[&str]) -> Self {
+        let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+        let name = format!("uq_{}_{}", tabledef.ident().name(), columns.join("_"));
+        Self {
+            tabledef,
+            name,
+            columns,
+        }
+    }
+
+    /// Override the name of the constraint. Defaults to uq_{table}_{columns}
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+impl MigrationWriter for AddUnique {
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        let ident = self.tabledef.ident();
+        vec![add_unique(syntax, ident, &self.name, &self.columns)]
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        vec![drop_unique(syntax, self.tabledef.ident(), &self.name)]
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
+
+/// Drops a unique constraint. The columns are needed to re-create it on down
+pub struct DropUnique {
+    tabledef: TableDef,
+    name: String,
+    columns: Vec<String>,
+}
+
+impl DropUnique {
+    pub(crate) fn new(tabledef: TableDef, name: String, columns: &[&str]) -> Self {
+        let columns = columns.iter().map(|c| c.to_string()).collect();
+        Self {
+            tabledef,
+            name,
+            columns,
+        }
+    }
+}
+
+impl MigrationWriter for DropUnique {
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        vec![drop_unique(syntax, self.tabledef.ident(), &self.name)]
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        let ident = self.tabledef.ident();
+        vec![add_unique(syntax, ident, &self.name, &self.columns)]
+    }
+
+    fn table(&self) -> Option<&TableIdent> {
+        Some(self.tabledef.ident())
+    }
+}
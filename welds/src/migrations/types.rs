@@ -2,6 +2,7 @@ use crate::writers::types::get_pairs;
 use crate::writers::types::Pair;
 use crate::Syntax;
 use std::iter::Iterator;
+use welds_connections::enums::{DbEnum, EnumStorage};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// These are types that are defined in migrations.
@@ -24,10 +25,21 @@ pub enum Type {
     Datetime,
     /// Datetime + (offset or timezone)
     DatetimeZone,
+    /// A native Postgres ENUM type with this name. Other databases use a String column
+    PostgresEnum(String),
     Raw(String),
 }
 
 impl Type {
+    /// The type of column used to store an enum that derives `WeldsEnum`
+    pub fn enum_of<E: DbEnum>() -> Type {
+        match E::STORAGE {
+            EnumStorage::Text => Type::String,
+            EnumStorage::Integer => Type::Int,
+            EnumStorage::PostgresEnum(name) => Type::PostgresEnum(name.to_owned()),
+        }
+    }
+
     // Returns the rust type to use for a given migration type
     pub fn rust_type(&self) -> Option<String> {
         Some(match self {
@@ -47,6 +59,7 @@ impl Type {
             Type::Time => "chrono::NaiveTime".to_owned(),
             Type::Datetime => "chrono::NaiveDateTime".to_owned(),
             Type::DatetimeZone => "chrono::DateTime<chrono::Utc>".to_owned(),
+            Type::PostgresEnum(_) => "String".to_owned(),
             Type::Raw(_) => return None,
         })
     }
@@ -55,14 +68,14 @@ impl Type {
     pub fn db_type(&self, syntax: Syntax) -> String {
         let pairs = get_pairs(syntax);
         let mut pairs_iter = pairs.iter().filter(|x| !x.id_only());
-        find_db_type(self, &mut pairs_iter)
+        find_db_type(self, syntax, &mut pairs_iter)
     }
 
     /// Returns the DB type to use in a migration for Id columns
     pub fn db_id_type(&self, syntax: Syntax) -> String {
         let pairs = get_pairs(syntax);
         let mut pairs_iter = pairs.iter();
-        find_db_type(self, &mut pairs_iter)
+        find_db_type(self, syntax, &mut pairs_iter)
     }
 
     /// Reads in a DB type and returns a type version of it.
@@ -81,7 +94,7 @@ impl Type {
 }
 
 /// Returns the DB type to use in a migration
-fn find_db_type<'i, I>(ty: &Type, pairs: &mut I) -> String
+fn find_db_type<'i, I>(ty: &Type, syntax: Syntax, pairs: &mut I) -> String
 where
     I: Iterator<Item = &'i Pair>,
 {
@@ -89,6 +102,12 @@ where
     if let Type::Raw(raw) = ty {
         return raw.to_owned();
     }
+    // Only postgres has named ENUM types
+    if let Type::PostgresEnum(name) = ty {
+        if syntax == Syntax::Postgres {
+            return name.to_owned();
+        }
+    }
     // find the best DB type based on the rust type
     let rust_type = ty.rust_type().unwrap();
     let pair = pairs.find(|&p| p.is_rust_type(&rust_type)).unwrap();
@@ -129,6 +148,84 @@ pub enum OnDelete {
     NoAction,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// The default value of a column. Written by the database when a row is inserted without it
+pub enum DefaultValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+    /// The date and time the row was inserted
+    CurrentTimestamp,
+    /// A newly generated random UUID (v4)
+    Uuid,
+    /// SQL used as is. Write it for the database you are migrating
+    Raw(String),
+}
+
+impl DefaultValue {
+    /// Returns the SQL for this default value
+    pub(crate) fn db_default(&self, syntax: Syntax) -> String {
+        match self {
+            DefaultValue::Int(x) => x.to_string(),
+            DefaultValue::Float(x) => x.to_string(),
+            DefaultValue::Bool(x) => match (syntax, x) {
+                (Syntax::Postgres | Syntax::Mysql, true) => "TRUE".to_owned(),
+                (Syntax::Postgres | Syntax::Mysql, false) => "FALSE".to_owned(),
+                (Syntax::Mssql | Syntax::Sqlite, true) => "1".to_owned(),
+                (Syntax::Mssql | Syntax::Sqlite, false) => "0".to_owned(),
+            },
+            DefaultValue::Text(x) => format!("'{}'", x.replace('\'', "''")),
+            DefaultValue::CurrentTimestamp => "CURRENT_TIMESTAMP".to_owned(),
+            DefaultValue::Uuid => match syntax {
+                Syntax::Postgres => "gen_random_uuid()".to_owned(),
+                // MySql requires expressions to be wrapped in ()
+                Syntax::Mysql => "(UUID())".to_owned(),
+                Syntax::Mssql => "NEWID()".to_owned(),
+                // Sqlite has no uuid function. build a v4 uuid from random bytes
+                Syntax::Sqlite => "(lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-' || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6))))".to_owned(),
+            },
+            DefaultValue::Raw(x) => x.to_owned(),
+        }
+    }
+}
+
+impl From<i32> for DefaultValue {
+    fn from(x: i32) -> Self {
+        DefaultValue::Int(x as i64)
+    }
+}
+
+impl From<i64> for DefaultValue {
+    fn from(x: i64) -> Self {
+        DefaultValue::Int(x)
+    }
+}
+
+impl From<f64> for DefaultValue {
+    fn from(x: f64) -> Self {
+        DefaultValue::Float(x)
+    }
+}
+
+impl From<bool> for DefaultValue {
+    fn from(x: bool) -> Self {
+        DefaultValue::Bool(x)
+    }
+}
+
+impl From<&str> for DefaultValue {
+    fn from(x: &str) -> Self {
+        DefaultValue::Text(x.to_owned())
+    }
+}
+
+impl From<String> for DefaultValue {
+    fn from(x: String) -> Self {
+        DefaultValue::Text(x)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -170,6 +267,15 @@ mod tests {
         assert_eq!(&db_type(Syntax::Mssql), "SMALLINT");
     }
 
+    #[test]
+    fn type_should_be_a_postgres_enum_only_on_postgres() {
+        let db_type = |s| Type::PostgresEnum("mood".to_string()).db_type(s);
+        assert_eq!(&db_type(Syntax::Sqlite), "TEXT");
+        assert_eq!(&db_type(Syntax::Mysql), "VARCHAR(255)");
+        assert_eq!(&db_type(Syntax::Postgres), "mood");
+        assert_eq!(&db_type(Syntax::Mssql), "NVARCHAR(MAX)");
+    }
+
     #[test]
     fn no_db_type_should_panic() {
         let syntaxes = [
@@ -190,6 +296,7 @@ mod tests {
             Type::FloatBig,
             Type::Binary,
             Type::Uuid,
+            Type::PostgresEnum("mood".to_string()),
             Type::Raw("BLA".to_string()),
         ];
         for syntax in &syntaxes {
@@ -0,0 +1,94 @@
+use crate::errors::Result;
+use crate::migrations::writers::view::{create_view_sql, drop_view_sql};
+use crate::migrations::MigrationWriter;
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+pub struct ViewBuilder {
+    ident: TableIdent,
+    select: String,
+    materialized: bool,
+}
+
+/// Builds a new migration used to create a view from a SELECT statement
+/// This migration can be passed to `migrations::up` to update your database
+/// ```
+/// use welds::errors::Result;
+/// use welds::migrations::{create_view, MigrationStep, TableState};
+///
+/// fn create_old_dogs_view(_state: &TableState) -> Result<MigrationStep> {
+///     let m = create_view("old_dogs", "SELECT id, name FROM dogs WHERE age > 10");
+///     Ok(MigrationStep::new("m20250301101500_create_view_old_dogs", m))
+/// }
+/// ```
+pub fn create_view(name: impl Into<String>, select: impl Into<String>) -> ViewBuilder {
+    let name: String = name.into();
+    ViewBuilder {
+        ident: TableIdent::parse(&name),
+        select: select.into(),
+        materialized: false,
+    }
+}
+
+impl ViewBuilder {
+    /// Store the results of the view, refreshed with `change_table(..)?.refresh()`
+    ///
+    /// NOTE: Only Postgres has materialized views, other databases fail with a `MigrationError`
+    pub fn materialized(mut self) -> Self {
+        self.materialized = true;
+        self
+    }
+}
+
+impl MigrationWriter for ViewBuilder {
+    // The errors writing the SQL are returned by validate
+    fn up_sql(&self, syntax: Syntax) -> Vec<String> {
+        let sql = create_view_sql(syntax, &self.ident, &self.select, self.materialized);
+        sql.into_iter().collect()
+    }
+
+    fn down_sql(&self, syntax: Syntax) -> Vec<String> {
+        let sql = drop_view_sql(syntax, &self.ident, self.materialized);
+        sql.into_iter().collect()
+    }
+
+    fn validate(&self, syntax: Syntax) -> Result<()> {
+        create_view_sql(syntax, &self.ident, &self.select, self.materialized)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_create_and_drop_a_view() {
+        let m = create_view("s1.old_dogs", "SELECT id FROM dogs WHERE age > 10");
+        let expected = "CREATE VIEW s1.old_dogs AS SELECT id FROM dogs WHERE age > 10";
+        assert_eq!(m.up_sql(Syntax::Mssql), vec![expected]);
+        assert_eq!(m.up_sql(Syntax::Sqlite), vec![expected]);
+        assert_eq!(m.down_sql(Syntax::Mysql), vec!["DROP VIEW s1.old_dogs"]);
+    }
+
+    #[test]
+    fn should_create_and_drop_a_materialized_view() {
+        let m = create_view("old_dogs", "SELECT id FROM dogs").materialized();
+        assert_eq!(
+            m.up_sql(Syntax::Postgres),
+            vec!["CREATE MATERIALIZED VIEW old_dogs AS SELECT id FROM dogs"]
+        );
+        assert_eq!(
+            m.down_sql(Syntax::Postgres),
+            vec!["DROP MATERIALIZED VIEW old_dogs"]
+        );
+    }
+
+    #[test]
+    fn only_postgres_should_have_materialized_views() {
+        let m = create_view("old_dogs", "SELECT id FROM dogs").materialized();
+        let err = m.validate(Syntax::Mysql).unwrap_err();
+        assert!(matches!(err, crate::WeldsError::MigrationError(_)), "{err}");
+        assert!(m.validate(Syntax::Postgres).is_ok());
+    }
+}
@@ -1,5 +1,6 @@
 use welds_connections::Syntax;
 
+use super::constraints::column_constraints;
 use crate::detect::TableDef;
 
 /// writes the SQL to Renames a column on a table
@@ -9,12 +10,15 @@ pub fn write(
     col: impl Into<String>,
     ty: impl Into<String>,
     nullable: bool,
+    default: Option<&str>,
+    check: Option<(&str, &str)>,
 ) -> String {
     let tablename: String = table.ident().to_string();
     let col: String = sanitize_column(col.into());
     let ty: String = ty.into();
     let null = if nullable { "NULL" } else { "NOT NULL" };
-    let coldef = format!("{ty} {null}");
+    let extras = column_constraints(syntax, table.ident(), &col, default, check);
+    let coldef = format!("{ty} {null}{extras}");
 
     match syntax {
         Syntax::Mssql => format!("ALTER TABLE {tablename} ADD {col} {coldef}"),
@@ -1,7 +1,7 @@
 use crate::detect::{ColumnDef, TableDef};
 use crate::Syntax;
 mod pg_writer;
-mod sqlite_writer;
+pub(crate) mod sqlite_writer;
 
 /// writes the up SQL change the type/null of a column
 pub fn write_up(
@@ -26,7 +26,8 @@ pub fn write_up(
             "ALTER TABLE {tablename} ALTER COLUMN {colname} {ty} {null}"
         )],
         Syntax::Mysql => vec![format!(
-            "ALTER TABLE {tablename} MODIFY COLUMN {colname} {ty} {null}"
+            "ALTER TABLE {tablename} MODIFY COLUMN {colname} {ty} {null}{}",
+            mysql_default(column)
         )],
     }
 }
@@ -54,11 +55,20 @@ pub fn write_down(
             "ALTER TABLE {tablename} ALTER COLUMN {colname} {ty} {null}"
         )],
         Syntax::Mysql => vec![format!(
-            "ALTER TABLE {tablename} MODIFY COLUMN {colname} {ty} {null}"
+            "ALTER TABLE {tablename} MODIFY COLUMN {colname} {ty} {null}{}",
+            mysql_default(column)
         )],
     }
 }
 
+/// MySql drops the default of a column when it is modified, unless it is given again
+fn mysql_default(column: &ColumnDef) -> String {
+    match column.default() {
+        Some(default) => format!(" DEFAULT {default}"),
+        None => String::default(),
+    }
+}
+
 /// Make sure this string is a valid column name
 fn sanitize_column(input: String) -> String {
     input
@@ -1,4 +1,5 @@
-use crate::detect::TableDef;
+use crate::detect::{CheckDef, ForeignKeyDef, TableDef};
+use crate::migrations::writers::create_table::build_pk_constraint;
 
 pub(crate) fn down_sql(
     table: &TableDef,
@@ -11,11 +12,13 @@ pub(crate) fn down_sql(
     let col_match: String = colname_match.into();
     let col_new: String = colname_new.into();
     let ty: String = ty.into();
-    let old_cols = old_columns(table, &col_match, &col_new);
-    let new_cols = new_columns(table, &col_match, &col_new, &ty, nullable);
+    let checks = renamed_checks(table, &col_match, &col_new);
+    let fks = renamed_foreign_keys(table, &col_match, &col_new);
+    let old_cols = old_columns(table, &checks, &col_match, &col_new);
+    let new_cols = new_columns(table, &checks, &col_match, &col_new, &ty, nullable);
     let tablename = table.ident().to_string();
     vec![
-        build_table_create(&temptable, &old_cols),
+        build_table_create(&temptable, &old_cols, &checks, &fks),
         build_copy_data(&tablename, &new_cols, &temptable, &old_cols),
         build_drop(&tablename),
         build_table_rename(&temptable, &tablename),
@@ -35,21 +38,82 @@ pub(crate) fn up_sql(
     let col_new: String = colname_new.into();
     let ty: String = ty.into();
 
-    let old_cols = old_columns(table, &col_match, &col_new);
-    let new_cols = new_columns(table, &col_match, &col_new, &ty, nullable);
+    let checks = renamed_checks(table, &col_match, &col_new);
+    let fks = renamed_foreign_keys(table, &col_match, &col_new);
+    let old_cols = old_columns(table, &checks, &col_match, &col_new);
+    let new_cols = new_columns(table, &checks, &col_match, &col_new, &ty, nullable);
 
     let tablename = table.ident().to_string();
     vec![
-        build_table_create(&temptable, &new_cols),
+        build_table_create(&temptable, &new_cols, &checks, &fks),
         build_copy_data(&tablename, &old_cols, &temptable, &new_cols),
         build_drop(&tablename),
         build_table_rename(&temptable, &tablename),
     ]
 }
 
-// writes the SQL to create a table
-pub(crate) fn build_table_create(tablename: &str, cols: &[Col]) -> String {
-    let cols_sql: Vec<String> = cols.iter().map(write_col).collect();
+/// Rebuilds the table as it is, with a different set of foreign keys.
+/// Sqlite can't add or drop a constraint on an existing table
+pub(crate) fn rebuild_with_foreign_keys(table: &TableDef, fks: &[ForeignKeyDef]) -> Vec<String> {
+    let temptable = format!("{}_weldstmp", table.ident());
+    let checks = table.checks();
+    // no column is being renamed
+    let cols = old_columns(table, checks, "", "");
+    let tablename = table.ident().to_string();
+    vec![
+        build_table_create(&temptable, &cols, checks, fks),
+        build_copy_data(&tablename, &cols, &temptable, &cols),
+        build_drop(&tablename),
+        build_table_rename(&temptable, &tablename),
+    ]
+}
+
+// The CHECK constraints of the table. The column could have been renamed after the table was
+// scanned, the checks are written with its new name like sqlite does when it renames a column
+fn renamed_checks(table: &TableDef, col_match: &str, col_name: &str) -> Vec<CheckDef> {
+    table
+        .checks()
+        .iter()
+        .map(|c| c.rename_column(col_match, col_name))
+        .collect()
+}
+
+// The foreign keys of the table, with the column renamed like the CHECKs are
+fn renamed_foreign_keys(table: &TableDef, col_match: &str, col_name: &str) -> Vec<ForeignKeyDef> {
+    table
+        .foreign_keys()
+        .iter()
+        .map(|fk| fk.rename_column(col_match, col_name))
+        .collect()
+}
+
+// writes the SQL to create a table, keeping the CHECK constraints and foreign keys of the table
+// being rebuilt
+pub(crate) fn build_table_create(
+    tablename: &str,
+    cols: &[Col],
+    checks: &[CheckDef],
+    fks: &[ForeignKeyDef],
+) -> String {
+    let pks: Vec<&str> = cols
+        .iter()
+        .filter(|c| c.primary_key)
+        .map(|c| c.name.as_str())
+        .collect();
+    // more than one primary key column is written as a PRIMARY KEY constraint
+    let cols_sql: Vec<String> = if pks.len() > 1 {
+        let mut cols_sql: Vec<String> = cols.iter().map(write_col_in_composite_pk).collect();
+        cols_sql.push(build_pk_constraint(&pks));
+        cols_sql
+    } else {
+        cols.iter().map(write_col).collect()
+    };
+    let table_checks = checks.iter().filter(|c| c.column().is_none());
+    let cols_sql: Vec<String> = cols_sql
+        .into_iter()
+        .chain(table_checks.map(|c| c.sql()))
+        .chain(fks.iter().map(|fk| fk.sql()))
+        .collect();
 
     // Join the columns parts together
     let cols_sql_joined = cols_sql.join(", ");
@@ -65,7 +129,36 @@ fn write_col(col: &Col) -> String {
     let name = &col.name;
     let ty = &col.ty;
     let nullable = if col.nullable { "NULL" } else { "NOT NULL" };
-    format!("{name} {ty} {nullable}")
+    let default = write_default(col);
+    let checks = write_checks(col);
+    format!("{name} {ty} {nullable}{default}{checks}")
+}
+
+/// write the column part for a create table, where the primary key is a constraint
+fn write_col_in_composite_pk(col: &Col) -> String {
+    let name = &col.name;
+    let ty = &col.ty;
+    let nullable = if col.nullable && !col.primary_key {
+        "NULL"
+    } else {
+        "NOT NULL"
+    };
+    let default = write_default(col);
+    let checks = write_checks(col);
+    format!("{name} {ty} {nullable}{default}{checks}")
+}
+
+/// write the default part of a column, keeping the default when the table is rebuilt
+fn write_default(col: &Col) -> String {
+    match &col.default {
+        Some(default) => format!(" DEFAULT {default}"),
+        None => String::default(),
+    }
+}
+
+/// write the CHECK constraints that were on the column
+fn write_checks(col: &Col) -> String {
+    col.checks.iter().map(|c| format!(" {c}")).collect()
 }
 
 /// write the primary key column part for a create table
@@ -78,7 +171,8 @@ fn write_pk(col: &Col) -> String {
         ty = "INTEGER";
     };
     //else { "" };
-    format!("{name} {ty} PRIMARY KEY{auto}")
+    let checks = write_checks(col);
+    format!("{name} {ty} PRIMARY KEY{auto}{checks}")
 }
 
 #[derive(Debug)]
@@ -87,36 +181,48 @@ pub(crate) struct Col {
     ty: String,
     nullable: bool,
     primary_key: bool,
+    default: Option<String>,
+    /// The SQL of the CHECK constraints written on this column
+    checks: Vec<String>,
 }
 
 // build a list of the new versions of the columns
 fn new_columns(
     table: &TableDef,
+    checks: &[CheckDef],
     col: &str,
     new_name: &str,
     new_ty: &str,
     nullable: bool,
 ) -> Vec<Col> {
-    let mut list = old_columns(table, col, new_name);
+    let mut list = old_columns(table, checks, col, new_name);
 
+    // the old columns already have the new name of the column
     list.drain(..)
         .map(|c| {
-            if c.name != col {
+            if c.name != new_name {
                 return c;
             }
             // build the updated version of the column
             Col {
-                name: col.to_string(),
+                name: new_name.to_string(),
                 ty: new_ty.to_string(),
                 nullable,
                 primary_key: c.primary_key,
+                default: c.default,
+                checks: c.checks,
             }
         })
         .collect()
 }
 
 // build a list of the old versions of the columns
-pub(crate) fn old_columns(tabledef: &TableDef, col_match: &str, col_name: &str) -> Vec<Col> {
+pub(crate) fn old_columns(
+    tabledef: &TableDef,
+    checks: &[CheckDef],
+    col_match: &str,
+    col_name: &str,
+) -> Vec<Col> {
     let mut list = Vec::default();
     for def in tabledef.columns() {
         // The column name could have changed AFTER the table was scanned. This happens then the
@@ -133,6 +239,12 @@ pub(crate) fn old_columns(tabledef: &TableDef, col_match: &str, col_name: &str)
             ty: def.ty.to_string(),
             nullable: def.null,
             primary_key: def.primary_key,
+            default: def.default.clone(),
+            checks: checks
+                .iter()
+                .filter(|c| c.column() == Some(name))
+                .map(|c| c.sql())
+                .collect(),
         });
     }
     list
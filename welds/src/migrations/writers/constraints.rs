@@ -0,0 +1,127 @@
+use crate::migrations::types::OnDelete;
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+/// writes the SQL to create an index on one or more columns of a table.
+/// `filter` makes a partial (filtered) index. MySql doesn't support them.
+pub fn create_index(
+    _syntax: Syntax,
+    table: &TableIdent,
+    name: &str,
+    columns: &[String],
+    unique: bool,
+    filter: Option<&str>,
+) -> String {
+    let tablename = table.to_string();
+    let cols = columns.join(", ");
+    let unique = if unique { "UNIQUE " } else { "" };
+    let sql = format!("CREATE {unique}INDEX {name} ON {tablename} ( {cols} )");
+    match filter {
+        None => sql,
+        Some(filter) => format!("{sql} WHERE {filter}"),
+    }
+}
+
+/// writes the SQL to drop an index from a table
+pub fn drop_index(syntax: Syntax, table: &TableIdent, name: &str) -> String {
+    match syntax {
+        Syntax::Mysql | Syntax::Mssql => format!("DROP INDEX {name} ON {table}"),
+        // the index lives in the same schema as the table
+        Syntax::Postgres | Syntax::Sqlite => match table.schema() {
+            Some(schema) => format!("DROP INDEX {schema}.{name}"),
+            None => format!("DROP INDEX {name}"),
+        },
+    }
+}
+
+/// writes the SQL to add a unique constraint to a table.
+/// Sqlite can't add constraints to a table, a unique index is used instead.
+pub fn add_unique(syntax: Syntax, table: &TableIdent, name: &str, columns: &[String]) -> String {
+    if syntax == Syntax::Sqlite {
+        return create_index(syntax, table, name, columns, true, None);
+    }
+    let cols = columns.join(", ");
+    format!("ALTER TABLE {table} ADD CONSTRAINT {name} UNIQUE ( {cols} )")
+}
+
+/// writes the SQL to drop a unique constraint from a table
+pub fn drop_unique(syntax: Syntax, table: &TableIdent, name: &str) -> String {
+    match syntax {
+        Syntax::Sqlite => drop_index(syntax, table, name),
+        Syntax::Mysql => format!("ALTER TABLE {table} DROP INDEX {name}"),
+        Syntax::Postgres | Syntax::Mssql => format!("ALTER TABLE {table} DROP CONSTRAINT {name}"),
+    }
+}
+
+/// writes the SQL to add a foreign key to a table.
+/// Sqlite can't add constraints to a table, no SQL is written.
+pub fn add_foreign_key(
+    syntax: Syntax,
+    table: &TableIdent,
+    name: &str,
+    column: &str,
+    foreign_table: &str,
+    foreign_column: &str,
+    on_delete: OnDelete,
+) -> Option<String> {
+    if syntax == Syntax::Sqlite {
+        return None;
+    }
+
+    let on_delete_str = on_delete_sql(on_delete);
+    Some(format!("ALTER TABLE {table} ADD CONSTRAINT {name} FOREIGN KEY ({column}) REFERENCES {foreign_table} ({foreign_column}) ON DELETE {on_delete_str}"))
+}
+
+/// The SQL for the action taken when the row a foreign key points to is deleted
+pub(crate) fn on_delete_sql(on_delete: OnDelete) -> &'static str {
+    match on_delete {
+        OnDelete::Cascade => "CASCADE",
+        OnDelete::SetNull => "SET NULL",
+        OnDelete::SetDefault => "SET DEFAULT",
+        OnDelete::Restrict => "RESTRICT",
+        OnDelete::NoAction => "NO ACTION",
+    }
+}
+
+/// writes the SQL to drop a foreign key from a table.
+/// Sqlite can't drop constraints from a table, no SQL is written.
+pub fn drop_foreign_key(syntax: Syntax, table: &TableIdent, name: &str) -> Option<String> {
+    match syntax {
+        Syntax::Sqlite => None,
+        Syntax::Mysql => Some(format!("ALTER TABLE {table} DROP FOREIGN KEY {name}")),
+        Syntax::Postgres | Syntax::Mssql => {
+            Some(format!("ALTER TABLE {table} DROP CONSTRAINT {name}"))
+        }
+    }
+}
+
+/// writes the DEFAULT and CHECK parts of a column in a create table or add column.
+/// The parts start with a space so they can be appended to the column.
+pub fn column_constraints(
+    syntax: Syntax,
+    table: &TableIdent,
+    column: &str,
+    default: Option<&str>,
+    check: Option<(&str, &str)>,
+) -> String {
+    let mut sql = String::default();
+    if let Some(default) = default {
+        // Mssql needs to know the name of a default to drop it or the column later
+        if syntax == Syntax::Mssql {
+            let name = format!("df_{}_{}", table.name(), column);
+            sql.push_str(&format!(" CONSTRAINT {name}"));
+        }
+        sql.push_str(&format!(" DEFAULT {default}"));
+    }
+    if let Some((name, expr)) = check {
+        sql.push_str(&format!(" CONSTRAINT {name} CHECK ({expr})"));
+    }
+    sql
+}
+
+/// writes the SQL to drop the default of a column on Mssql, whatever it was named.
+/// Mssql won't drop a column that still has a default.
+pub fn drop_mssql_default(table: &TableIdent, column: &str) -> String {
+    let tablename = table.to_string();
+    format!("DECLARE @df NVARCHAR(256) = (SELECT name FROM sys.default_constraints WHERE parent_object_id = OBJECT_ID('{tablename}') AND COL_NAME(parent_object_id, parent_column_id) = '{column}'); IF @df IS NOT NULL EXEC('ALTER TABLE {tablename} DROP CONSTRAINT ' + @df)")
+}
@@ -1,6 +1,7 @@
 use crate::migrations::create_table::ColumnBuilder;
 use crate::migrations::types::Index;
 use crate::migrations::types::OnDelete;
+use crate::migrations::writers::constraints;
 use crate::model_traits::TableIdent;
 use crate::Syntax;
 
@@ -35,25 +36,20 @@ fn write_fk(
     foreign_column: &str,
     on_delete: OnDelete,
 ) -> String {
-    if syntax == Syntax::Sqlite {
-        return String::default();
-    }
-
-    let on_delete_str = match on_delete {
-        OnDelete::Cascade => "CASCADE",
-        OnDelete::SetNull => "SET NULL",
-        OnDelete::SetDefault => "SET DEFAULT",
-        OnDelete::Restrict => "RESTRICT",
-        OnDelete::NoAction => "NO ACTION",
-    };
-
     let indexname = match &col.index_name {
         Some(n) => n.to_owned(),
         None => format!("fk_{}_{}", table.name, col.name),
     };
 
-    let tablename = table.to_string();
     let colname = col.name.as_str();
-
-    format!("ALTER TABLE {tablename} ADD CONSTRAINT {indexname} FOREIGN KEY ({colname}) REFERENCES {foreign_table} ({foreign_column}) ON DELETE {on_delete_str}")
+    constraints::add_foreign_key(
+        syntax,
+        table,
+        &indexname,
+        colname,
+        foreign_table,
+        foreign_column,
+        on_delete,
+    )
+    .unwrap_or_default()
 }
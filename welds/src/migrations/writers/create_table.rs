@@ -1,9 +1,12 @@
+use super::constraints::column_constraints;
 use super::create_index;
 use crate::detect::TableDef;
 use crate::migrations::create_table::ColumnBuilder;
 use crate::migrations::create_table::IdBuilder;
+use crate::migrations::create_table::PrimaryKey;
 use crate::migrations::create_table::TableBuilder;
-use crate::migrations::types::Type;
+use crate::migrations::types::{DefaultValue, Type};
+use crate::model_traits::TableIdent;
 use crate::writers::types::pk_override;
 use crate::Syntax;
 
@@ -14,26 +17,56 @@ pub fn from_def(syntax: Syntax, def: &TableDef) -> Vec<String> {
 
     let mut columns: Vec<String> = Vec::default();
 
-    for c in def.columns().iter().filter(|x| x.primary_key) {
-        let pk_type = pk_override(syntax, &c.ty).unwrap_or(&c.ty);
-        let col = IdBuilder {
-            name: c.name.to_string(),
-            ty: Type::parse_db_type(syntax, pk_type),
-        };
-        columns.push(build_id_column(syntax, &col))
+    // A table with more than one primary key column is written with a PRIMARY KEY constraint
+    let pks: Vec<_> = def.columns().iter().filter(|x| x.primary_key).collect();
+    let composite = pks.len() > 1;
+
+    if !composite {
+        for c in &pks {
+            let pk_type = pk_override(syntax, &c.ty).unwrap_or(&c.ty);
+            let col = IdBuilder {
+                name: c.name.to_string(),
+                ty: Type::parse_db_type(syntax, pk_type),
+            };
+            columns.push(build_id_column(syntax, &col))
+        }
     }
 
-    for c in def.columns().iter().filter(|&x| !x.primary_key) {
+    for c in def
+        .columns()
+        .iter()
+        .filter(|&x| composite || !x.primary_key)
+    {
         let col = ColumnBuilder {
             name: c.name.to_string(),
             ty: Type::parse_db_type(syntax, c.ty()),
-            nullable: c.null,
+            nullable: c.null && !c.primary_key,
             index: None,
             index_name: None,
+            default: c.default.clone().map(DefaultValue::Raw),
+            check: None,
         };
-        columns.push(build_column(syntax, &col))
+        let checks: String = def
+            .checks()
+            .iter()
+            .filter(|check| check.column() == Some(c.name()))
+            .map(|check| format!(" {}", check.sql()))
+            .collect();
+        columns.push(format!(
+            "{}{checks}",
+            build_column(syntax, def.ident(), &col)
+        ))
     }
 
+    if composite {
+        let names: Vec<&str> = pks.iter().map(|c| c.name()).collect();
+        columns.push(build_pk_constraint(&names));
+    }
+
+    let table_checks = def.checks().iter().filter(|c| c.column().is_none());
+    columns.extend(table_checks.map(|c| c.sql()));
+    columns.extend(def.foreign_keys().iter().map(|fk| fk.sql()));
+
     let parts = vec![format!(
         "CREATE TABLE {} ( {} )",
         def.ident(),
@@ -43,7 +76,7 @@ pub fn from_def(syntax: Syntax, def: &TableDef) -> Vec<String> {
 }
 
 pub fn from_builder(syntax: Syntax, tb: &TableBuilder) -> Vec<String> {
-    let columns: Vec<String> = build_columns(syntax, &tb.pk, &tb.columns);
+    let columns: Vec<String> = build_columns(syntax, &tb.ident, &tb.pk, &tb.columns);
     let columns: String = columns.join(", ");
     let mut parts = vec![format!("CREATE TABLE {} ( {} )", tb.ident, columns)];
     let index_cols = tb.columns.iter().filter(|c| c.index.is_some());
@@ -53,15 +86,41 @@ pub fn from_builder(syntax: Syntax, tb: &TableBuilder) -> Vec<String> {
     parts
 }
 
-fn build_columns(syntax: Syntax, idcol: &IdBuilder, cols: &[ColumnBuilder]) -> Vec<String> {
+fn build_columns(
+    syntax: Syntax,
+    table: &TableIdent,
+    pk: &PrimaryKey,
+    cols: &[ColumnBuilder],
+) -> Vec<String> {
     let mut parts = Vec::default();
-    parts.push(build_id_column(syntax, idcol));
-    for col in cols {
-        parts.push(build_column(syntax, col))
+    match pk {
+        PrimaryKey::Id(idcol) => {
+            parts.push(build_id_column(syntax, idcol));
+            for col in cols {
+                parts.push(build_column(syntax, table, col))
+            }
+        }
+        PrimaryKey::Columns(names) => {
+            for col in cols {
+                // columns in the primary key can't be null
+                if names.contains(&col.name) {
+                    parts.push(build_not_null_column(syntax, table, col))
+                } else {
+                    parts.push(build_column(syntax, table, col))
+                }
+            }
+            let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+            parts.push(build_pk_constraint(&names));
+        }
     }
     parts
 }
 
+/// The primary key of a table made from more than one column (or a column that isn't an id)
+pub(crate) fn build_pk_constraint(names: &[&str]) -> String {
+    format!("PRIMARY KEY ( {} )", names.join(", "))
+}
+
 fn build_id_column(syntax: Syntax, col: &IdBuilder) -> String {
     let name = &col.name;
     let ty: String = col.ty.db_id_type(syntax);
@@ -77,10 +136,24 @@ fn build_id_column(syntax: Syntax, col: &IdBuilder) -> String {
     format!("{name} {ty} {tail}")
 }
 
-fn build_column(syntax: Syntax, col: &ColumnBuilder) -> String {
+fn build_column(syntax: Syntax, table: &TableIdent, col: &ColumnBuilder) -> String {
     let name = &col.name;
     let ty: String = col.ty.db_type(syntax);
 
     let null = if col.nullable { "NULL" } else { "NOT NULL" };
-    format!("{name} {ty} {null}")
+    let extras = build_column_constraints(syntax, table, col);
+    format!("{name} {ty} {null}{extras}")
+}
+
+fn build_not_null_column(syntax: Syntax, table: &TableIdent, col: &ColumnBuilder) -> String {
+    let name = &col.name;
+    let ty: String = col.ty.db_type(syntax);
+    let extras = build_column_constraints(syntax, table, col);
+    format!("{name} {ty} NOT NULL{extras}")
+}
+
+fn build_column_constraints(syntax: Syntax, table: &TableIdent, col: &ColumnBuilder) -> String {
+    let default = col.default.as_ref().map(|d| d.db_default(syntax));
+    let check = col.check.as_ref().map(|(n, e)| (n.as_str(), e.as_str()));
+    column_constraints(syntax, table, &col.name, default.as_deref(), check)
 }
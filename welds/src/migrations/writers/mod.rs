@@ -1,6 +1,8 @@
 use crate::model_traits::TableIdent;
 
+pub mod constraints;
 pub mod create_table;
+pub mod view;
 
 mod create_indexes;
 pub use create_indexes::write as create_index;
@@ -12,7 +14,12 @@ pub fn drop_table(table: &TableIdent) -> String {
 mod rename_column;
 pub use rename_column::write as rename_column;
 
+mod rename_table;
+pub use rename_table::write as rename_table;
+pub use rename_table::write_set_schema as set_schema;
+
 mod alter_column_type;
+pub(crate) use alter_column_type::sqlite_writer::rebuild_with_foreign_keys as sqlite_rebuild_with_foreign_keys;
 pub use alter_column_type::write_down as alter_column_type_down;
 pub use alter_column_type::write_up as alter_column_type_up;
 
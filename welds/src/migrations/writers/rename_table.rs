@@ -0,0 +1,55 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+/// writes the SQL to rename a table. The table stays in the same schema
+pub fn write(syntax: Syntax, table: &TableIdent, new_name: impl Into<String>) -> String {
+    let newname: String = sanitize(new_name.into());
+    let tablename: String = table.to_string();
+
+    match syntax {
+        Syntax::Mssql => format!("EXEC sp_rename '{tablename}', '{newname}'"),
+        Syntax::Mysql => {
+            let new_table = TableIdent::new(newname, table.schema());
+            format!("RENAME TABLE {tablename} TO {new_table}")
+        }
+        Syntax::Postgres | Syntax::Sqlite => format!("ALTER TABLE {tablename} RENAME TO {newname}"),
+    }
+}
+
+/// writes the SQL to move a table into another schema. The table keeps its name.
+/// Sqlite doesn't have schemas, and Postgres/Mssql need a schema to move the table to
+pub fn write_set_schema(
+    syntax: Syntax,
+    table: &TableIdent,
+    schema: Option<&str>,
+) -> Result<String> {
+    let tablename: String = table.to_string();
+    let schema: Option<String> = schema.map(|s| sanitize(s.to_owned()));
+
+    match (syntax, schema) {
+        (Syntax::Postgres, Some(schema)) => {
+            Ok(format!("ALTER TABLE {tablename} SET SCHEMA {schema}"))
+        }
+        (Syntax::Mssql, Some(schema)) => Ok(format!("ALTER SCHEMA {schema} TRANSFER {tablename}")),
+        // In MySql a schema is a database. no schema means the current database
+        (Syntax::Mysql, schema) => {
+            let new_table = TableIdent::new(table.name(), schema);
+            Ok(format!("RENAME TABLE {tablename} TO {new_table}"))
+        }
+        (Syntax::Sqlite, _) => Err(WeldsError::MigrationError(
+            "Sqlite does not support moving a table to another schema".to_owned(),
+        )),
+        (_, None) => Err(WeldsError::MigrationError(format!(
+            "A schema is required to move the table {tablename}"
+        ))),
+    }
+}
+
+/// Make sure this string is a valid table/schema name
+fn sanitize(input: String) -> String {
+    input
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_')
+        .collect::<String>()
+}
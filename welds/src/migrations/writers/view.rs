@@ -0,0 +1,42 @@
+use crate::errors::{Result, WeldsError};
+use crate::model_traits::TableIdent;
+use crate::Syntax;
+
+/// writes the SQL to create a view
+pub fn create_view_sql(
+    syntax: Syntax,
+    ident: &TableIdent,
+    select: &str,
+    materialized: bool,
+) -> Result<String> {
+    let kind = view_kind(syntax, materialized)?;
+    Ok(format!("CREATE {kind} {ident} AS {select}"))
+}
+
+/// writes the SQL to drop a view
+pub fn drop_view_sql(syntax: Syntax, ident: &TableIdent, materialized: bool) -> Result<String> {
+    let kind = view_kind(syntax, materialized)?;
+    Ok(format!("DROP {kind} {ident}"))
+}
+
+/// writes the SQL to refresh the stored results of a materialized view
+pub fn refresh_view_sql(syntax: Syntax, ident: &TableIdent) -> Result<String> {
+    if syntax != Syntax::Postgres {
+        return Err(WeldsError::MigrationError(
+            "Only Postgres has materialized views to refresh".to_owned(),
+        ));
+    }
+    Ok(format!("REFRESH MATERIALIZED VIEW {ident}"))
+}
+
+fn view_kind(syntax: Syntax, materialized: bool) -> Result<&'static str> {
+    if !materialized {
+        return Ok("VIEW");
+    }
+    if syntax != Syntax::Postgres {
+        return Err(WeldsError::MigrationError(
+            "Only Postgres supports materialized views".to_owned(),
+        ));
+    }
+    Ok("MATERIALIZED VIEW")
+}
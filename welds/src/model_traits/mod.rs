@@ -8,6 +8,76 @@ pub mod hooks;
 pub trait TableInfo {
     /// the unique name (schema + tablename) that identities this database object
     fn identifier() -> &'static [&'static str];
+
+    /// `identifier` as a const, so an enum of models can check its variants share a table
+    /// when it is compiled. Left empty, the check is skipped.
+    const IDENTIFIER: &'static [&'static str] = &[];
+
+    /// The column and value that identify rows of this model in a table shared by an enum.
+    /// Set with `#[welds(discriminator(column = "type", value = "car"))]`
+    const DISCRIMINATOR: Option<Discriminator> = None;
+
+    /// Returns `DISCRIMINATOR`
+    fn discriminator() -> Option<Discriminator> {
+        Self::DISCRIMINATOR
+    }
+}
+
+/// Used by enums deriving WeldsModel to check at compile time that a variant is in the same table
+/// as the first variant. An empty identifier isn't checked
+#[doc(hidden)]
+pub const fn same_table(first: &[&str], other: &[&str]) -> bool {
+    if first.is_empty() || other.is_empty() {
+        return true;
+    }
+    if first.len() != other.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < first.len() {
+        if !const_str_eq(first[i], other[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Used by enums deriving WeldsModel to check at compile time that a variant has a
+/// discriminator on the same column as the first variant
+#[doc(hidden)]
+pub const fn same_discriminator_column(
+    first: Option<Discriminator>,
+    other: Option<Discriminator>,
+) -> bool {
+    match (first, other) {
+        (Some(first), Some(other)) => const_str_eq(first.column, other.column),
+        _ => false,
+    }
+}
+
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Identifies the rows of one variant in single-table inheritance.
+/// Queries for the model are filtered to rows where `column = value`, and saving writes `value`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Discriminator {
+    pub column: &'static str,
+    pub value: &'static str,
 }
 
 /// The db column name to use for a field
@@ -67,6 +137,12 @@ pub trait WriteToArgs {
     fn bind<'s, 'c, 'a, 'p>(&'s self, column: &'c str, args: &'a mut ParamArgs<'p>) -> Result<()>
     where
         's: 'p;
+
+    /// False if this value doesn't have the column. (the column belongs to another variant of an enum)
+    /// These columns are left out of inserts and updates
+    fn writes_column(&self, _column: &str) -> bool {
+        true
+    }
 }
 
 pub trait ColumnDefaultCheck {
@@ -77,10 +153,54 @@ pub trait UpdateFromRow {
     fn update_from_row(&mut self, row: &mut crate::Row) -> crate::errors::Result<()>;
 }
 
+/// A group of columns that is stored on a model with `[welds(flatten)]`
+/// This is impl by `#[derive(WeldsEmbedded)]`
+///
+/// The columns are named `prefix` + the name of the field (or its rename).
+/// `column` passed to `bind` and `col_is_default` has the prefix removed.
+pub trait EmbeddedColumns: Sized {
+    /// The clauses for each column, used by `where_col(|m| m.address.city ...)`
+    type Schema;
+
+    fn columns(prefix: &str) -> Vec<Column>;
+
+    fn schema(prefix: &str) -> Self::Schema;
+
+    fn bind<'s, 'c, 'a, 'p>(&'s self, column: &'c str, args: &'a mut ParamArgs<'p>) -> Result<()>
+    where
+        's: 'p;
+
+    fn col_is_default(&self, column: &str) -> Result<bool>;
+
+    fn from_row(row: &crate::Row, prefix: &str) -> Result<Self>;
+}
+
+/// The columns of a table shared by the variants of an enum. Used by `#[derive(WeldsModel)]` on enums.
+///
+/// Columns are in the order they are first found.
+/// Columns that are not on every variant are nullable.
+#[doc(hidden)]
+pub fn union_columns(variants: &[Vec<Column>]) -> Vec<Column> {
+    let mut columns: Vec<Column> = Vec::default();
+    for col in variants.iter().flatten() {
+        if columns.iter().any(|c| c.name() == col.name()) {
+            continue;
+        }
+        let on_all = variants
+            .iter()
+            .all(|cols| cols.iter().any(|c| c.name() == col.name()));
+        let mut col = col.clone();
+        col.nullable = col.nullable || !on_all;
+        columns.push(col);
+    }
+    columns
+}
+
 /// Used to link a models schema to the model
 pub trait HasSchema: Sync + Send {
     type Schema: Default + TableInfo;
 }
 
 mod tableident;
+#[cfg(feature = "migrations")]
 pub use tableident::TableIdent;
@@ -1,6 +1,7 @@
 use super::HasSchema;
 use super::TableColumns;
 use super::TableInfo;
+use crate::errors::{Result, WeldsError};
 use crate::Syntax;
 
 /// a unique identifier for a table.
@@ -65,6 +66,24 @@ impl TableIdent {
         Self { schema, name }
     }
 
+    /// Returns an error if the table/schema name has anything that isn't valid in an identifier.
+    /// Used on names given at runtime so they can't be used to inject SQL
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Some(schema) = &self.schema {
+            Self::validate_name(schema)?;
+        }
+        Self::validate_name(&self.name)
+    }
+
+    /// Returns an error if this table or schema name has anything that isn't valid in an identifier.
+    pub(crate) fn validate_name(name: &str) -> Result<()> {
+        let valid = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !valid {
+            return Err(WeldsError::InvalidTableName(name.to_owned()));
+        }
+        Ok(())
+    }
+
     /// returns True if a schema_name/table_name match this TableIdent
     pub fn equals(&self, schema: &Option<String>, name: &str) -> bool {
         &self.schema == schema && self.name == name
@@ -1,5 +1,11 @@
 pub use crate::exts::{VecRowExt, VecStateExt};
+#[cfg(feature = "factory")]
+pub use crate::factory::WeldsFactory;
 pub use crate::state::DbState;
 pub use crate::Client;
 pub use crate::TransactStart;
+pub use crate::WeldsEmbedded;
+pub use crate::WeldsEnum;
+#[cfg(feature = "factory")]
+pub use crate::WeldsFactory;
 pub use crate::WeldsModel;
@@ -1,13 +1,15 @@
 use super::clause::{self, AsOptField};
 use super::select_cols::SelectBuilder;
 pub use super::update::bulk::UpdateBuilder;
-use crate::model_traits::{HasSchema, TableColumns, TableInfo, UniqueIdentifier};
+use crate::errors::Result;
+use crate::model_traits::{HasSchema, TableColumns, TableIdent, TableInfo, UniqueIdentifier};
 use crate::query::clause::exists::ExistIn;
 use crate::query::clause::{AsFieldName, AssignmentAdder, ClauseAdder, OrderBy};
 use crate::relations::{HasRelations, Relationship};
 use crate::writers::alias::TableAlias;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use welds_connections::convert::StaticText;
 use welds_connections::Param;
 
 pub use super::clause::manualparam::ManualParam;
@@ -31,6 +33,7 @@ pub struct QueryBuilder<T> {
     pub(crate) orderby: Vec<OrderBy>,
     pub(crate) alias: String,
     pub(crate) alias_asigner: Arc<TableAlias>,
+    pub(crate) table: Option<TableIdent>,
 }
 
 impl<T> Default for QueryBuilder<T>
@@ -49,18 +52,90 @@ where
     pub fn new() -> Self {
         let ta = TableAlias::new();
         let alias = ta.next();
+        // A variant of single-table inheritance only sees its own rows
+        let mut wheres: Vec<Box<dyn ClauseAdder>> = Vec::default();
+        if let Some(d) = <<T as HasSchema>::Schema as TableInfo>::discriminator() {
+            wheres.push(Box::new(clause::ClauseColValEqual {
+                null_clause: false,
+                not_clause: false,
+                col: d.column.to_owned(),
+                operator: "=",
+                val: Some(StaticText(d.value)),
+            }));
+        }
         Self {
             _t: Default::default(),
-            wheres: Vec::default(),
+            wheres,
             limit: None,
             offset: None,
             orderby: Vec::default(),
             exist_ins: Default::default(),
             alias,
             alias_asigner: Arc::new(ta),
+            table: None,
         }
     }
 
+    /// The table this query runs against.
+    /// This is the table of the model unless it was changed with `in_table` or `in_schema`
+    pub fn table_ident(&self) -> TableIdent
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.table
+            .clone()
+            .unwrap_or_else(|| TableIdent::from_model::<T>())
+    }
+
+    /// Run this query against a different table with the same columns as the model.
+    /// Useful for partitioned tables (events_2026_10).
+    ///
+    /// Rows loaded by this query will also be saved/deleted in this table.
+    /// Returns an error if the name has characters that aren't valid in a table name.
+    /// ```
+    /// use welds::prelude::*;
+    ///
+    /// #[derive(Debug, Default, WeldsModel)]
+    /// #[welds(table = "events")]
+    /// struct Event {
+    ///     #[welds(primary_key)]
+    ///     pub id: i32,
+    /// }
+    ///
+    /// async fn example(db: &dyn Client) -> welds::errors::Result<()> {
+    ///     let rows = Event::all().in_table("events_2026_10")?.run(db).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn in_table(mut self, table_name: impl Into<String>) -> Result<Self>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let table_name = table_name.into();
+        TableIdent::validate_name(&table_name)?;
+        let mut table = self.table_ident();
+        table.name = table_name;
+        self.table = Some(table);
+        Ok(self)
+    }
+
+    /// Run this query against the model's table in a different schema.
+    /// Useful for per-tenant schemas.
+    ///
+    /// Rows loaded by this query will also be saved/deleted in this schema.
+    /// Returns an error if the name has characters that aren't valid in a schema name.
+    pub fn in_schema(mut self, schema_name: impl Into<String>) -> Result<Self>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        let schema_name = schema_name.into();
+        TableIdent::validate_name(&schema_name)?;
+        let mut table = self.table_ident();
+        table.schema = Some(schema_name);
+        self.table = Some(table);
+        Ok(self)
+    }
+
     /// Filter the results returned by this query.
     /// Used when you want to filter on the columns of this table.
     /// This is the default way to write `WHERE` clauses
@@ -199,8 +274,7 @@ where
     {
         let ship = relationship(Default::default());
         let out_col = ship.my_key::<R::Schema, T::Schema>();
-        let inner_tn = <R as HasSchema>::Schema::identifier();
-        let inner_tn = inner_tn.join(".");
+        let inner_tn = filter.table_ident().to_string();
         let inner_col = ship.their_key::<R::Schema, T::Schema>();
         let mut exist_in = ExistIn::new(filter, out_col, inner_tn, inner_col);
         exist_in.set_aliases(&self.alias_asigner);
@@ -227,7 +301,7 @@ where
         qb.set_aliases(&self.alias_asigner);
 
         let out_col = ship.their_key::<R::Schema, T::Schema>();
-        let inner_tn = <T as HasSchema>::Schema::identifier().join(".");
+        let inner_tn = self.table_ident().to_string();
         let inner_col = ship.my_key::<R::Schema, T::Schema>();
         let exist_in = ExistIn::new(self, out_col, inner_tn, inner_col);
 
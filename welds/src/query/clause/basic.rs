@@ -1,4 +1,4 @@
-use super::{AsFieldName, ClauseColVal, ClauseColValEqual};
+use super::{AsFieldName, ClauseColVal, ClauseColValEqual, ClauseColValIn};
 use std::marker::PhantomData;
 use welds_connections::Param;
 
@@ -56,4 +56,32 @@ where
         };
         Box::new(cv)
     }
+
+    /// Will write SQL checking the value is one of the values in the list (IN)
+    pub fn in_list<P>(self, slice: &[P]) -> Box<ClauseColValIn<T>>
+    where
+        P: Into<T> + Clone,
+        T: Param,
+    {
+        let cv = ClauseColValIn::<T> {
+            not_clause: false,
+            col: self.col,
+            list: slice.iter().map(|p| p.clone().into()).collect(),
+        };
+        Box::new(cv)
+    }
+
+    /// Will write SQL checking the value is NOT one of the values in the list (NOT IN)
+    pub fn not_in_list<P>(self, slice: &[P]) -> Box<ClauseColValIn<T>>
+    where
+        P: Into<T> + Clone,
+        T: Param,
+    {
+        let cv = ClauseColValIn::<T> {
+            not_clause: true,
+            col: self.col,
+            list: slice.iter().map(|p| p.clone().into()).collect(),
+        };
+        Box::new(cv)
+    }
 }
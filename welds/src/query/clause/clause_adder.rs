@@ -1,4 +1,4 @@
-use super::{ClauseColManual, ClauseColVal, ClauseColValEqual, ClauseColValList};
+use super::{ClauseColManual, ClauseColVal, ClauseColValEqual, ClauseColValIn, ClauseColValList};
 use super::{Param, ParamArgs};
 use crate::writers::NextParam;
 use crate::Syntax;
@@ -122,6 +122,38 @@ where
     }
 }
 
+impl<T> ClauseAdder for ClauseColValIn<T>
+where
+    T: Clone + Send + Sync + Param,
+{
+    fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
+    where
+        'lam: 'p,
+    {
+        for val in &self.list {
+            args.push(val);
+        }
+    }
+
+    fn clause(&self, _syntax: Syntax, alias: &str, next_params: &NextParam) -> Option<String> {
+        // an empty IN () isn't valid SQL. Nothing is in an empty list
+        if self.list.is_empty() {
+            let clause = if self.not_clause { "1=1" } else { "1=0" };
+            return Some(clause.to_string());
+        }
+
+        let params: Vec<String> = self.list.iter().map(|_| next_params.next()).collect();
+        let operator = if self.not_clause { "NOT IN" } else { "IN" };
+        Some(format!(
+            "{}.{} {} ({})",
+            alias,
+            self.col,
+            operator,
+            params.join(", ")
+        ))
+    }
+}
+
 impl ClauseAdder for ClauseColManual {
     fn bind<'lam, 'args, 'p>(&'lam self, args: &'args mut ParamArgs<'p>)
     where
@@ -54,6 +54,12 @@ pub struct ClauseColValList<T> {
     pub list: Vec<T>,
 }
 
+pub struct ClauseColValIn<T> {
+    pub not_clause: bool,
+    pub col: String,
+    pub list: Vec<T>,
+}
+
 pub struct ClauseColManual {
     pub(crate) col: Option<String>,
     pub(crate) sql: String,
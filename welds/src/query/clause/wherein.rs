@@ -1,6 +1,7 @@
 use super::ClauseAdder;
 use crate::model_traits::HasSchema;
 use crate::model_traits::TableColumns;
+use crate::model_traits::TableIdent;
 use crate::model_traits::TableInfo;
 use crate::model_traits::UniqueIdentifier;
 use crate::query::builder::QueryBuilder;
@@ -55,7 +56,11 @@ where
         let inner_alias = &self.qb.alias;
         let mut args = None;
         let inner_sql = join_sql_parts(&[
-            build_head_select::<<T as HasSchema>::Schema>(syntax, inner_alias),
+            build_head_select::<<T as HasSchema>::Schema>(
+                syntax,
+                &self.qb.table_ident(),
+                inner_alias,
+            ),
             build_where(
                 syntax,
                 next_params,
@@ -71,11 +76,11 @@ where
     }
 }
 
-fn build_head_select<S>(syntax: Syntax, tablealias: &str) -> Option<String>
+fn build_head_select<S>(syntax: Syntax, table: &TableIdent, tablealias: &str) -> Option<String>
 where
-    S: TableInfo + UniqueIdentifier,
+    S: UniqueIdentifier,
 {
-    let mut tablename = S::identifier().join(".");
+    let mut tablename = table.to_string();
     if tablename != tablealias {
         tablename = format!("{} {}", tablename, tablealias);
     }
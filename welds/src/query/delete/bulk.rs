@@ -4,6 +4,7 @@ use super::super::{
     helpers::{build_where, join_sql_parts},
 };
 use crate::errors::Result;
+use crate::instrument::operation;
 use crate::model_traits::UniqueIdentifier;
 use crate::model_traits::{HasSchema, TableColumns, TableInfo};
 use crate::query::clause::ParamArgs;
@@ -47,10 +48,10 @@ where
         let next_params = NextParam::new(syntax);
 
         // Note: for deletes we can't alias the FROM tablename
-        let alias = <T as HasSchema>::Schema::identifier().join(".");
+        let alias = self.table_ident().to_string();
 
         join_sql_parts(&[
-            build_head_delete::<<T as HasSchema>::Schema>(syntax),
+            build_head_delete(syntax, &alias),
             build_where_delete(syntax, &next_params, &alias, args, self, w_in),
         ])
     }
@@ -71,16 +72,12 @@ where
         let mut args: Option<ParamArgs> = Some(Vec::default());
         let sql = self.delete_sql_internal(syntax, &mut w_in, &mut args);
         let args: ParamArgs = args.unwrap();
-        client.execute(&sql, &args).await?;
+        operation::<T, _>("bulk_delete", client.execute(&sql, &args)).await?;
         Ok(())
     }
 }
 
-fn build_head_delete<S>(_syntax: Syntax) -> Option<String>
-where
-    S: TableInfo + TableColumns,
-{
-    let identifier = S::identifier().join(".");
+fn build_head_delete(_syntax: Syntax, identifier: &str) -> Option<String> {
     Some(format!("DELETE FROM {}", identifier))
 }
 
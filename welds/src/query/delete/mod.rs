@@ -1,7 +1,8 @@
 use crate::errors::Result;
 use crate::errors::WeldsError;
+use crate::instrument::operation;
 use crate::model_traits::hooks::{AfterDelete, BeforeDelete};
-use crate::model_traits::{HasSchema, TableColumns, TableInfo, WriteToArgs};
+use crate::model_traits::{HasSchema, TableColumns, TableIdent, TableInfo, WriteToArgs};
 use crate::query::clause::ParamArgs;
 use crate::writers::ColumnWriter;
 use crate::writers::NextParam;
@@ -9,7 +10,7 @@ use welds_connections::Client;
 
 pub mod bulk;
 
-pub async fn delete_one<T>(obj: &T, client: &dyn Client) -> Result<()>
+pub async fn delete_one<T>(obj: &T, table: &TableIdent, client: &dyn Client) -> Result<()>
 where
     T: HasSchema + WriteToArgs,
     <T as HasSchema>::Schema: TableInfo + TableColumns,
@@ -19,7 +20,7 @@ where
     let syntax = client.syntax();
     let col_writer = ColumnWriter::new(syntax);
     let next_params = NextParam::new(syntax);
-    let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+    let identifier = table.to_string();
 
     let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
     if pks.is_empty() {
@@ -39,7 +40,7 @@ where
 
     let sql = format!("DELETE FROM {} where {}", identifier, wheres);
 
-    client.execute(&sql, &args).await?;
+    operation::<T, _>("delete", client.execute(&sql, &args)).await?;
     AfterDelete::after(obj);
 
     Ok(())
@@ -1,3 +1,4 @@
+use crate::errors::WeldsError;
 use crate::state::DbState;
 use crate::Syntax;
 use welds_connections::Row;
@@ -61,3 +62,43 @@ fn should_be_able_to_write_delete_query_with_limit() {
         assert_eq!(args_count, 1);
     });
 }
+
+#[test]
+fn should_be_able_to_delete_from_another_schema() {
+    futures::executor::block_on(async move {
+        use crate::query::builder::QueryBuilder;
+        let q = QueryBuilder::<Product>::new()
+            .in_schema("tenant_a")
+            .unwrap()
+            .where_col(|c| c.a.gt(1))
+            .limit(10);
+        let client = welds_connections::noop::build(Syntax::Mysql);
+        let _ = q.delete(&client).await;
+        let ran_sql = client.last_sql().unwrap();
+        let expected = "DELETE FROM tenant_a.nums WHERE (  tenant_a.nums.id IN (SELECT t1.id FROM tenant_a.nums t1 WHERE ( t1.a > ? ) ORDER BY 1 LIMIT 0, 10)  )";
+        assert_eq!(expected, &ran_sql);
+    });
+}
+
+#[test]
+fn should_delete_a_loaded_object_from_the_table_it_was_loaded_from() {
+    futures::executor::block_on(async move {
+        use crate::model_traits::TableIdent;
+        let mut obj = DbState::db_loaded(Product::default());
+        obj.set_db_table(TableIdent::parse("nums_2026_10")).unwrap();
+        let client = welds_connections::noop::build(Syntax::Mysql);
+        let _ = obj.delete(&client).await;
+        let ran_sql = client.last_sql().unwrap();
+        let expected = "DELETE FROM nums_2026_10 where id=?";
+        assert_eq!(expected, &ran_sql);
+    });
+}
+
+#[test]
+fn should_not_set_a_table_that_is_not_an_identifier_on_a_loaded_object() {
+    use crate::model_traits::TableIdent;
+    let mut obj = DbState::db_loaded(Product::default());
+    let result = obj.set_db_table(TableIdent::new("x; DROP TABLE y", None::<String>));
+    assert!(matches!(result, Err(WeldsError::InvalidTableName(_))));
+    assert!(obj.db_table().is_none());
+}
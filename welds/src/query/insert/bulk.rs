@@ -1,5 +1,6 @@
 use crate::errors::Result;
-use crate::model_traits::{HasSchema, TableColumns, TableInfo, WriteToArgs};
+use crate::instrument::operation;
+use crate::model_traits::{HasSchema, TableColumns, TableIdent, TableInfo, WriteToArgs};
 use crate::query::clause::ParamArgs;
 use crate::writers::ColumnWriter;
 use crate::writers::NextParam;
@@ -8,6 +9,16 @@ use crate::Client;
 
 /// Executes the query in the database Bulk Inserting values
 pub async fn run<T>(conn: &dyn Client, data: &[T]) -> Result<()>
+where
+    T: WriteToArgs + HasSchema,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+{
+    run_into(conn, &TableIdent::from_model::<T>(), data).await
+}
+
+/// Executes the query in the database Bulk Inserting values into a table other than the model's own.
+/// The table must have the same columns
+pub async fn run_into<T>(conn: &dyn Client, table: &TableIdent, data: &[T]) -> Result<()>
 where
     T: WriteToArgs + HasSchema,
     <T as HasSchema>::Schema: TableInfo + TableColumns,
@@ -27,7 +38,7 @@ where
     let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
     let columns: Vec<_> = all_columns.iter().filter(|c| !pks.contains(c)).collect();
 
-    let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+    let identifier = table.to_string();
 
     let colnames: Vec<String> = columns
         .iter()
@@ -47,6 +58,11 @@ where
         for d in chunk {
             let mut row: Vec<String> = Vec::default();
             for col in &columns {
+                // every row has the same columns, so columns of another variant are NULL
+                if !d.writes_column(col.name()) {
+                    row.push("NULL".to_owned());
+                    continue;
+                }
                 d.bind(col.name(), &mut args)?;
                 row.push(next_params.next());
             }
@@ -54,7 +70,7 @@ where
         }
         let rows = rows.join(",");
         let sql = format!("INSERT INTO {} ({}) VALUES {}", identifier, colnames, rows);
-        conn.execute(&sql, &args).await?;
+        operation::<T, _>("bulk_insert", conn.execute(&sql, &args)).await?;
     }
 
     Ok(())
@@ -1,7 +1,8 @@
 mod bulk;
 mod single;
 
-pub use single::insert_one;
+pub use single::{insert_one, insert_one_into};
 
 pub use bulk::run as bulk_insert;
+pub use bulk::run_into as bulk_insert_into;
 //pub use bulk::run_fast as bulk_insert_fast;
@@ -1,8 +1,9 @@
 use crate::errors::Result;
 use crate::errors::WeldsError::InsertFailed;
+use crate::instrument::operation;
 use crate::model_traits::hooks::{AfterCreate, BeforeCreate};
 use crate::model_traits::{ColumnDefaultCheck, UpdateFromRow};
-use crate::model_traits::{HasSchema, TableColumns, TableInfo, WriteToArgs};
+use crate::model_traits::{HasSchema, TableColumns, TableIdent, TableInfo, WriteToArgs};
 use crate::query::clause::ParamArgs;
 use crate::writers::column::ColumnWriter;
 use crate::writers::insert::{ColArg, InsertWriter};
@@ -12,6 +13,17 @@ use welds_connections::Client;
 use welds_connections::Fetch;
 
 pub async fn insert_one<T>(obj: &mut T, client: &dyn Client) -> Result<()>
+where
+    T: WriteToArgs + HasSchema + ColumnDefaultCheck,
+    <T as HasSchema>::Schema: TableInfo + TableColumns,
+    T: UpdateFromRow,
+    T: BeforeCreate + AfterCreate,
+{
+    insert_one_into(obj, &TableIdent::from_model::<T>(), client).await
+}
+
+/// Inserts the model into a table other than its own. The table must have the same columns
+pub async fn insert_one_into<T>(obj: &mut T, table: &TableIdent, client: &dyn Client) -> Result<()>
 where
     T: WriteToArgs + HasSchema + ColumnDefaultCheck,
     <T as HasSchema>::Schema: TableInfo + TableColumns,
@@ -28,7 +40,7 @@ where
     let next_params = NextParam::new(syntax);
     let writer = InsertWriter::new(syntax);
 
-    let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+    let identifier = table.to_string();
     let columns = <<T as HasSchema>::Schema as TableColumns>::columns();
     let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
 
@@ -36,6 +48,9 @@ where
     let mut id_return_required = true;
 
     for col in &columns {
+        if !obj.writes_column(col.name()) {
+            continue;
+        }
         let pk = pks.iter().find(|p| p == &col);
 
         match pk {
@@ -83,7 +98,7 @@ where
     // WARNING: these statements MUST be ran on the same DB connection in the pool
     // If this isn't done, you will not get back the last_id.
     // That is why we are using fetch_many
-    let mut datasets = client.fetch_many(&statements).await?;
+    let mut datasets = operation::<T, _>("insert", client.fetch_many(&statements)).await?;
     let mut rows: Vec<Row> = datasets.drain(..).flatten().collect();
 
     // If we are providing the DB with the ID, (string/uuid) it doesn't need to return the id, and will not
@@ -31,3 +31,25 @@ fn should_be_able_to_insert_simple_object() {
         assert_eq!(expected, &ran_sql);
     });
 }
+
+#[test]
+fn should_be_able_to_insert_into_another_table() {
+    futures::executor::block_on(async move {
+        use crate::model_traits::TableIdent;
+        use crate::query::insert::{bulk_insert_into, insert_one_into};
+        let table = TableIdent::parse("tenant_a.nums_2026_10");
+        let client = welds_connections::noop::build(Syntax::Postgres);
+
+        let mut obj = Product::default();
+        let _ = insert_one_into(&mut obj, &table, &client).await;
+        let ran_sql = client.last_sql().unwrap();
+        let expected =
+            "INSERT INTO tenant_a.nums_2026_10 (\"a\", \"b\") VALUES ($1, $2) RETURNING *";
+        assert_eq!(expected, &ran_sql);
+
+        let _ = bulk_insert_into(&client, &table, &[Product::default()]).await;
+        let ran_sql = client.last_sql().unwrap();
+        let expected = "INSERT INTO tenant_a.nums_2026_10 (\"a\", \"b\") VALUES ($1,$2)";
+        assert_eq!(expected, &ran_sql);
+    });
+}
@@ -7,4 +7,5 @@ pub mod optional;
 pub mod select;
 pub mod select_cols;
 pub(crate) mod tail;
+pub mod truncate;
 pub(crate) mod update;
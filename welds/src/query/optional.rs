@@ -1,3 +1,5 @@
+use welds_connections::convert::Converted;
+
 // This is part of the fluent builder for queries.
 // We are using it instead of Option so we can have slightly different behavior
 //
@@ -85,6 +87,13 @@ impl From<&Option<String>> for Optional<String> {
     }
 }
 
+// Fields with a converter can be searched for by their own type
+impl<C, T> From<T> for Optional<Converted<C, T>> {
+    fn from(inner: T) -> Optional<Converted<C, T>> {
+        Optional::Some(Converted::new(inner))
+    }
+}
+
 impl<T> Clone for Optional<T>
 where
     T: Clone,
@@ -1,7 +1,8 @@
 use super::builder::QueryBuilder;
 use super::clause::ParamArgs;
 use crate::errors::Result;
-use crate::model_traits::{HasSchema, TableColumns, TableIdent, TableInfo};
+use crate::instrument::operation;
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
 use crate::state::DbState;
 use crate::{Syntax, WeldsError};
 use welds_connections::Client;
@@ -24,7 +25,7 @@ where
         T: HasSchema,
         <T as HasSchema>::Schema: TableInfo + TableColumns,
     {
-        let table = TableIdent::from_model::<T>();
+        let table = self.table_ident();
         let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias);
         writer.sql_count(
             &self.wheres,
@@ -47,7 +48,7 @@ where
         let syntax = client.syntax();
         let mut args: Option<ParamArgs> = Some(Vec::default());
 
-        let table = TableIdent::from_model::<T>();
+        let table = self.table_ident();
         let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias);
         let sql = writer.sql_count(
             &self.wheres,
@@ -59,7 +60,7 @@ where
         );
 
         let args = args.unwrap();
-        let rows = client.fetch_rows(&sql, &args).await?;
+        let rows = operation::<T, _>("count", client.fetch_rows(&sql, &args)).await?;
         let row = rows.first().ok_or(WeldsError::RowNowFound)?;
         let count: i64 = row.get_by_position(0)?;
         Ok(count as u64)
@@ -70,7 +71,7 @@ where
     where
         <T as HasSchema>::Schema: TableInfo + TableColumns,
     {
-        let table = TableIdent::from_model::<T>();
+        let table = self.table_ident();
         let columns = <T as HasSchema>::Schema::columns();
         let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias);
         writer.sql(
@@ -95,7 +96,7 @@ where
         let syntax = client.syntax();
         let mut args: Option<ParamArgs> = Some(Vec::default());
 
-        let table = TableIdent::from_model::<T>();
+        let table = self.table_ident();
         let columns = <T as HasSchema>::Schema::columns();
         let writer = SelectWriter::new_with_alias(syntax, &table, &self.alias);
         let sql = writer.sql(
@@ -109,12 +110,15 @@ where
         );
 
         let args = args.unwrap();
-        let rows = client.fetch_rows(&sql, &args).await?;
+        let rows = operation::<T, _>("select", client.fetch_rows(&sql, &args)).await?;
 
         let mut objs = Vec::default();
         for row in rows {
             let obj: T = T::try_from(row)?;
-            objs.push(DbState::db_loaded(obj));
+            let mut state = DbState::db_loaded(obj);
+            // the table's name was already checked when it was given to the query
+            state.table = self.table.clone();
+            objs.push(state);
         }
         Ok(objs)
     }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::errors::WeldsError;
 
 // Test Object that can be used to write SQL
 use crate::WeldsModel;
@@ -101,3 +102,67 @@ fn should_exec_basic_with_where() {
         &ran_sql
     );
 }
+
+#[test]
+fn should_read_rows_from_a_mock_client() {
+    use welds_connections::noop::{MockClient, MockRow};
+    let q = QueryBuilder::<Product>::new();
+    let client = MockClient::new(Syntax::Postgres);
+    let row = MockRow::new().column("dbname", "Bobby".to_string());
+    client.expect_rows("FROM da_schemaname.da_tablename", vec![row]);
+    let products = futures::executor::block_on(async { q.run(&client).await.unwrap() });
+    client.assert_all_consumed();
+    assert_eq!(products.len(), 1);
+    assert_eq!(products[0].name, "Bobby");
+}
+
+#[test]
+fn should_read_count_from_a_mock_client() {
+    use welds_connections::noop::{MockClient, MockRow};
+    let q = QueryBuilder::<Product>::new();
+    let client = MockClient::new(Syntax::Postgres);
+    let row = MockRow::new().column("count", 42_i64);
+    client.expect_rows("COUNT", vec![row]);
+    let count = futures::executor::block_on(async { q.count(&client).await.unwrap() });
+    assert_eq!(count, 42);
+}
+
+#[test]
+fn should_be_able_to_select_from_another_table_and_schema() {
+    let q = QueryBuilder::<Product>::new()
+        .in_table("da_tablename_2026_10")
+        .unwrap();
+    assert_eq!(
+        "SELECT t1.\"dbname\" FROM da_schemaname.da_tablename_2026_10 t1",
+        q.to_sql(Syntax::Postgres)
+    );
+    let q = q.in_schema("tenant_a").unwrap();
+    assert_eq!(
+        "SELECT t1.\"dbname\" FROM tenant_a.da_tablename_2026_10 t1",
+        q.to_sql(Syntax::Postgres)
+    );
+}
+
+#[test]
+fn should_reject_runtime_table_and_schema_names_that_are_not_identifiers() {
+    let q = QueryBuilder::<Product>::new().in_table("x; DROP TABLE y");
+    assert!(matches!(q, Err(WeldsError::InvalidTableName(n)) if n == "x; DROP TABLE y"));
+    let q = QueryBuilder::<Product>::new().in_schema("a;--");
+    assert!(matches!(q, Err(WeldsError::InvalidTableName(n)) if n == "a;--"));
+    let q = QueryBuilder::<Product>::new().in_table("events-2026");
+    assert!(matches!(q, Err(WeldsError::InvalidTableName(n)) if n == "events-2026"));
+}
+
+#[test]
+fn should_remember_the_table_rows_were_loaded_from() {
+    use welds_connections::noop::{MockClient, MockRow};
+    let q = QueryBuilder::<Product>::new()
+        .in_schema("tenant_a")
+        .unwrap();
+    let client = MockClient::new(Syntax::Postgres);
+    let row = MockRow::new().column("dbname", "Bobby".to_string());
+    client.expect_rows("FROM tenant_a.da_tablename", vec![row]);
+    let products = futures::executor::block_on(async { q.run(&client).await.unwrap() });
+    let table = products[0].db_table().unwrap();
+    assert_eq!(table.to_string(), "tenant_a.da_tablename");
+}
@@ -0,0 +1,121 @@
+use super::*;
+use crate::WeldsModel;
+use welds_connections::convert::Converted;
+use welds_connections::convert::{ConvertError, DbConverter};
+use welds_connections::noop::{MockClient, MockRow, ParamValue};
+
+// A type that doesn't implement any of the database traits
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Money(i64);
+
+struct MoneyCents;
+
+impl DbConverter<Money> for MoneyCents {
+    type Db = i64;
+    fn to_db(value: &Money) -> i64 {
+        value.0
+    }
+    fn from_db(value: i64) -> std::result::Result<Money, ConvertError> {
+        if value < 0 {
+            return Err("money can't be negative".into());
+        }
+        Ok(Money(value))
+    }
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "orders")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Order {
+    #[welds(primary_key)]
+    pub id: i32,
+    #[welds(with = "MoneyCents")]
+    pub total: Money,
+    #[welds(with = "MoneyCents")]
+    pub discount: Option<Money>,
+}
+
+#[test]
+fn should_bind_converted_fields_in_where_clauses() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.expect_rows("FROM orders", vec![]);
+    futures::executor::block_on(async {
+        Order::where_col(|c| c.total.equal(Money(500)))
+            .where_col(|c| c.discount.equal(Money(25)))
+            .where_col(|c| c.total.in_list(&[Money(1), Money(2)]))
+            .run(&client)
+            .await
+            .unwrap();
+    });
+
+    let ran = &client.history()[0];
+    assert_eq!(
+        ran.params,
+        vec![
+            ParamValue::Int(500),
+            ParamValue::Int(25),
+            ParamValue::Int(1),
+            ParamValue::Int(2),
+        ]
+    );
+}
+
+fn order_row(id: i32, total: Money, discount: Option<Money>) -> MockRow {
+    MockRow::new()
+        .column("id", id)
+        .column("total", Converted::<MoneyCents, _>::new(total))
+        .column("discount", Converted::<MoneyCents, _>::new(discount))
+}
+
+#[test]
+fn should_read_converted_fields_from_rows() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let rows = vec![
+        order_row(1, Money(500), Some(Money(25))),
+        order_row(2, Money(300), None),
+    ];
+    client.expect_rows("FROM orders", rows);
+    let orders = futures::executor::block_on(async { Order::all().run(&client).await.unwrap() });
+
+    assert_eq!(orders[0].total, Money(500));
+    assert_eq!(orders[0].discount, Some(Money(25)));
+    assert_eq!(orders[1].total, Money(300));
+    assert_eq!(orders[1].discount, None);
+}
+
+#[test]
+fn should_return_the_error_when_a_converted_column_can_not_be_read() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let row = MockRow::new()
+        .column("id", 1_i32)
+        .column("total", 500_i64)
+        .column("discount", None::<i64>);
+    client.expect_rows("FROM orders", vec![row]);
+    let result = futures::executor::block_on(async { Order::all().run(&client).await });
+    assert!(result.is_err());
+}
+
+#[test]
+fn should_return_the_error_from_the_converter() {
+    futures::executor::block_on(async {
+        let client = welds_connections::sqlite::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let create =
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, total INTEGER, discount INTEGER)";
+        client.execute(create, &[]).await.unwrap();
+        let insert = "INSERT INTO orders VALUES (1, 500, NULL), (2, 300, -5)";
+        client.execute(insert, &[]).await.unwrap();
+
+        let first = Order::where_col(|c| c.id.equal(1)).run(&client).await;
+        let first = first.unwrap();
+        assert_eq!(first[0].total, Money(500));
+        assert_eq!(first[0].discount, None);
+
+        let err = Order::where_col(|c| c.id.equal(2))
+            .run(&client)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("money can't be negative"), "{err}");
+    });
+}
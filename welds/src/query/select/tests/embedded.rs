@@ -0,0 +1,136 @@
+use super::*;
+use crate::model_traits::TableColumns;
+use crate::state::DbState;
+use crate::{WeldsEmbedded, WeldsModel};
+use welds_connections::noop::{MockClient, MockRow, ParamValue};
+use welds_connections::Row;
+
+#[derive(Debug, Default, Clone, PartialEq, WeldsEmbedded)]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Address {
+    pub street: String,
+    pub city: String,
+    #[welds(rename = "zip_code")]
+    pub zip: Option<String>,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "customers")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Customer {
+    #[welds(primary_key)]
+    pub id: i32,
+    #[welds(flatten)]
+    pub address: Address,
+    #[welds(flatten, prefix = "billing_")]
+    pub billing: Address,
+}
+
+#[test]
+fn should_list_the_embedded_columns_with_their_prefix() {
+    let columns: Vec<_> = <Customer as HasSchema>::Schema::columns()
+        .iter()
+        .map(|c| c.name().to_owned())
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            "id",
+            "address_street",
+            "address_city",
+            "address_zip_code",
+            "billing_street",
+            "billing_city",
+            "billing_zip_code",
+        ]
+    );
+}
+
+#[test]
+fn should_be_able_to_filter_on_embedded_columns() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.expect_rows("FROM customers", vec![]);
+    futures::executor::block_on(async {
+        Customer::where_col(|c| c.address.city.equal("Paris"))
+            .where_col(|c| c.billing.zip.equal(None))
+            .run(&client)
+            .await
+            .unwrap();
+    });
+
+    let ran = &client.history()[0];
+    assert!(ran.sql.contains("t1.address_city = ?"), "{}", ran.sql);
+    assert!(
+        ran.sql.contains("t1.billing_zip_code IS NULL"),
+        "{}",
+        ran.sql
+    );
+}
+
+fn customer_row() -> MockRow {
+    MockRow::new()
+        .column("id", 1_i32)
+        .column("address_street", "1 Rue".to_string())
+        .column("address_city", "Paris".to_string())
+        .column("address_zip_code", Some("75001".to_string()))
+        .column("billing_street", "2 Main".to_string())
+        .column("billing_city", "Boston".to_string())
+        .column("billing_zip_code", None::<String>)
+}
+
+#[test]
+fn should_read_embedded_columns_from_a_row() {
+    let customer = Customer::try_from(Row::from(customer_row())).unwrap();
+    assert_eq!(customer.id, 1);
+    let address = Address {
+        street: "1 Rue".to_string(),
+        city: "Paris".to_string(),
+        zip: Some("75001".to_string()),
+    };
+    assert_eq!(customer.address, address);
+    assert_eq!(customer.billing.city, "Boston");
+    assert_eq!(customer.billing.zip, None);
+}
+
+#[test]
+fn should_error_when_an_embedded_column_is_missing() {
+    let row = MockRow::new()
+        .column("id", 1_i32)
+        .column("street", "1 Rue".to_string());
+    assert!(Customer::try_from(Row::from(row)).is_err());
+}
+
+#[test]
+fn should_bind_embedded_columns_when_inserting() {
+    let client = MockClient::new(Syntax::Postgres);
+    client.expect_rows("INSERT INTO customers", vec![customer_row()]);
+    let customer = Customer {
+        id: 0,
+        address: Address {
+            street: "1 Rue".to_string(),
+            city: "Paris".to_string(),
+            zip: Some("75001".to_string()),
+        },
+        billing: Address {
+            street: "2 Main".to_string(),
+            city: "Boston".to_string(),
+            zip: None,
+        },
+    };
+    let mut customer = DbState::new_uncreated(customer);
+    futures::executor::block_on(async { customer.save(&client).await.unwrap() });
+    assert_eq!(customer.id, 1);
+
+    let ran = &client.history()[0];
+    let expected = "INSERT INTO customers (\"address_street\", \"address_city\", \"address_zip_code\", \"billing_street\", \"billing_city\", \"billing_zip_code\") VALUES ($1, $2, $3, $4, $5, $6) RETURNING *";
+    assert_eq!(ran.sql, expected);
+    let params = vec![
+        ParamValue::from("1 Rue"),
+        ParamValue::from("Paris"),
+        ParamValue::from("75001"),
+        ParamValue::from("2 Main"),
+        ParamValue::from("Boston"),
+        ParamValue::Null,
+    ];
+    assert_eq!(ran.params, params);
+}
@@ -0,0 +1,126 @@
+use super::*;
+use crate::state::DbState;
+use crate::{WeldsEnum, WeldsModel};
+use welds_connections::noop::{MockClient, MockRow, ParamValue};
+
+#[derive(Debug, Clone, Default, PartialEq, WeldsEnum)]
+#[welds_path(crate)] // needed only within the welds crate.
+enum Mood {
+    #[default]
+    #[welds(rename = "happy")]
+    Happy,
+    #[welds(rename = "sad")]
+    Sad,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, WeldsEnum)]
+#[welds(integer)]
+#[welds_path(crate)] // needed only within the welds crate.
+enum Priority {
+    #[default]
+    Low = 1,
+    High = 10,
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "people")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Person {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub mood: Mood,
+    pub priority: Priority,
+}
+
+#[test]
+fn should_bind_enums_in_equal_and_in_clauses() {
+    let client = MockClient::new(Syntax::Sqlite);
+    client.expect_rows("FROM people", vec![]);
+    futures::executor::block_on(async {
+        Person::where_col(|c| c.mood.in_list(&[Mood::Happy, Mood::Sad]))
+            .where_col(|c| c.priority.equal(Priority::High))
+            .run(&client)
+            .await
+            .unwrap();
+    });
+
+    let ran = &client.history()[0];
+    assert_eq!(
+        ran.sql,
+        "SELECT t1.\"id\", t1.\"mood\", t1.\"priority\" FROM people t1 WHERE ( t1.mood IN (?, ?) AND t1.priority = ? )"
+    );
+    assert_eq!(
+        ran.params,
+        vec![
+            ParamValue::Text("happy".to_owned()),
+            ParamValue::Text("sad".to_owned()),
+            ParamValue::Int(10),
+        ]
+    );
+}
+
+#[test]
+fn should_write_nothing_is_in_an_empty_list() {
+    let empty: &[Mood] = &[];
+    let q = Person::where_col(|c| c.mood.in_list(empty));
+    assert_eq!(
+        "SELECT t1.\"id\", t1.\"mood\", t1.\"priority\" FROM people t1 WHERE ( 1=0 )",
+        q.to_sql(Syntax::Sqlite)
+    );
+}
+
+#[derive(Debug, WeldsModel)]
+#[welds(table = "pets")]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Pet {
+    #[welds(primary_key)]
+    pub id: i32,
+    #[welds(db_enum)]
+    pub mood: Option<Mood>,
+    #[welds(db_enum)]
+    pub priority: Option<Priority>,
+}
+
+#[test]
+fn should_bind_optional_enums() {
+    let client = MockClient::new(Syntax::Mssql);
+    client.expect_rows("FROM pets", vec![]);
+    let row = MockRow::new()
+        .column("id", 1)
+        .column("mood", None::<Mood>)
+        .column("priority", Some(Priority::High));
+    client.expect_rows("INSERT INTO pets", vec![row]);
+    futures::executor::block_on(async {
+        Pet::where_col(|c| c.mood.equal(Some(Mood::Sad)))
+            .run(&client)
+            .await
+            .unwrap();
+        let mut pet = DbState::new_uncreated(Pet {
+            id: 0,
+            mood: None,
+            priority: Some(Priority::High),
+        });
+        pet.save(&client).await.unwrap();
+    });
+
+    let history = client.history();
+    assert_eq!(history[0].params, vec![ParamValue::Text("sad".to_owned())]);
+    assert_eq!(
+        history[1].params,
+        vec![ParamValue::Null, ParamValue::Int(10)]
+    );
+}
+
+#[test]
+fn should_bind_a_none_enum_as_null_on_mssql() {
+    use welds_connections::__private::tiberius::{ColumnData, ToSql};
+    use welds_connections::enums::EnumNull;
+    use welds_connections::mssql::MssqlParam;
+    let none = &EnumNull::<Mood>::NULL;
+    assert_eq!(none.param_value(), ParamValue::Null);
+    assert_eq!(none.add_param(Vec::default()).len(), 1);
+    // the NULL has the type the enum is stored as
+    assert!(matches!(none.to_sql(), ColumnData::String(None)));
+    let none = &EnumNull::<Priority>::NULL;
+    assert!(matches!(none.to_sql(), ColumnData::I32(None)));
+}
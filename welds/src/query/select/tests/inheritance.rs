@@ -0,0 +1,130 @@
+use super::*;
+use crate::model_traits::TableColumns;
+use crate::WeldsModel;
+use welds_connections::noop::{MockClient, MockRow, ParamValue};
+
+#[derive(Debug, Default, Clone, PartialEq, WeldsModel)]
+#[welds(table = "vehicles", discriminator(column = "kind", value = "car"))]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Car {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub doors: i32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, WeldsModel)]
+#[welds(table = "vehicles", discriminator(column = "kind", value = "truck"))]
+#[welds_path(crate)] // needed only within the welds crate.
+struct Truck {
+    #[welds(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub payload: i32,
+}
+
+#[derive(Debug, PartialEq, WeldsModel)]
+#[welds_path(crate)] // needed only within the welds crate.
+enum Vehicle {
+    Car(Car),
+    Truck(Truck),
+}
+
+#[test]
+fn should_only_select_rows_of_the_variant() {
+    let q = Car::where_col(|c| c.doors.gt(2));
+    assert_eq!(
+        "SELECT t1.\"id\", t1.\"name\", t1.\"doors\", t1.\"kind\" FROM vehicles t1 WHERE ( t1.kind = ? AND t1.doors > ? )",
+        q.to_sql(Syntax::Sqlite)
+    );
+}
+
+#[test]
+fn should_select_the_columns_of_every_variant() {
+    let columns: Vec<_> = <Vehicle as HasSchema>::Schema::columns()
+        .iter()
+        .map(|c| (c.name().to_owned(), c.nullable()))
+        .collect();
+    let expected: Vec<_> = [
+        ("id", false),
+        ("name", false),
+        ("doors", true),
+        ("kind", false),
+        ("payload", true),
+    ]
+    .iter()
+    .map(|(n, null)| (n.to_string(), *null))
+    .collect();
+    assert_eq!(columns, expected);
+    assert_eq!(
+        "SELECT t1.\"id\", t1.\"name\", t1.\"doors\", t1.\"kind\", t1.\"payload\" FROM vehicles t1",
+        Vehicle::all().to_sql(Syntax::Sqlite)
+    );
+}
+
+#[test]
+fn should_read_each_row_as_its_variant() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let car = MockRow::new()
+        .column("id", 1)
+        .column("name", "Mini".to_string())
+        .column("doors", 3)
+        .column("kind", "car".to_string());
+    let truck = MockRow::new()
+        .column("id", 2)
+        .column("name", "Hauler".to_string())
+        .column("payload", 9000)
+        .column("kind", "truck".to_string());
+    client.expect_rows("FROM vehicles", vec![car, truck]);
+
+    let vehicles =
+        futures::executor::block_on(async { Vehicle::all().run(&client).await.unwrap() });
+    let vehicles: Vec<_> = vehicles.into_iter().map(|v| v.into_inner()).collect();
+    assert_eq!(
+        vehicles,
+        vec![
+            Vehicle::Car(Car {
+                id: 1,
+                name: "Mini".to_owned(),
+                doors: 3
+            }),
+            Vehicle::Truck(Truck {
+                id: 2,
+                name: "Hauler".to_owned(),
+                payload: 9000
+            }),
+        ]
+    );
+}
+
+#[test]
+fn should_fail_to_read_a_row_without_a_known_discriminator() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let row = MockRow::new()
+        .column("id", 1)
+        .column("kind", "bike".to_string());
+    client.expect_rows("FROM vehicles", vec![row]);
+    let err = futures::executor::block_on(async { Vehicle::all().run(&client).await });
+    assert!(matches!(err, Err(WeldsError::UnknownDiscriminator(_))));
+}
+
+#[test]
+fn should_write_the_discriminator_and_only_the_columns_of_the_variant() {
+    let client = MockClient::new(Syntax::Sqlite);
+    let truck = Truck {
+        id: 0,
+        name: "Hauler".to_owned(),
+        payload: 9000,
+    };
+    let mut vehicle = DbState::new_uncreated(Vehicle::Truck(truck));
+    client.expect_rows("SELECT", vec![]);
+    // no row comes back to update from, the insert is what we are checking
+    let _ = futures::executor::block_on(async { vehicle.save(&client).await });
+
+    let ran = &client.history()[0];
+    assert_eq!(
+        ran.sql,
+        "INSERT INTO vehicles (\"name\", \"kind\", \"payload\") VALUES (?, ?, ?)"
+    );
+    assert_eq!(ran.params[1], ParamValue::Text("truck".to_owned()));
+}
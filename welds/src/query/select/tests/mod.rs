@@ -1,5 +1,9 @@
 use super::*;
 mod basic;
 mod basicopt;
+mod converters;
+mod embedded;
+mod enums;
+mod inheritance;
 mod relationships;
 mod sql_tails;
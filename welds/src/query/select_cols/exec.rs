@@ -1,4 +1,5 @@
 use crate::errors::Result;
+use crate::instrument::operation;
 use crate::model_traits::{HasSchema, TableColumns, TableInfo};
 use crate::query::clause::ParamArgs;
 use crate::query::helpers::{build_tail, build_where_clauses, join_sql_parts};
@@ -68,9 +69,22 @@ where
         let mut args: Option<ParamArgs> = Some(Vec::default());
         let sql = self.sql_internal(syntax, &mut args);
         let args = args.unwrap();
-        let rows = client.fetch_rows(&sql, &args).await?;
+        let rows = operation::<T, _>("select", client.fetch_rows(&sql, &args)).await?;
         Ok(rows)
     }
+
+    /// Executes the query in the database, deserializing each row into `R`.
+    /// Fields of `R` are matched to the selected columns by name (use `select_as` to rename them).
+    #[cfg(feature = "serde")]
+    pub async fn run_as<R>(&self, client: &dyn Client) -> Result<Vec<R>>
+    where
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+        R: serde::de::DeserializeOwned,
+    {
+        let rows = self.run(client).await?;
+        let items: std::result::Result<Vec<R>, _> = rows.iter().map(|r| r.deserialize()).collect();
+        Ok(items?)
+    }
 }
 
 fn build_head_select<T>(syntax: Syntax, sb: &SelectBuilder<T>) -> Option<String>
@@ -107,7 +121,7 @@ where
     head.push(&cols_text);
 
     head.push("FROM");
-    let tn = <T as HasSchema>::Schema::identifier().join(".");
+    let tn = sb.qb.table_ident();
     let identifier = format!("{} {}", tn, alias);
     head.push(&identifier);
     Some(head.join(" "))
@@ -1,6 +1,6 @@
 use super::SelectBuilder;
 use super::SelectColumn;
-use crate::model_traits::{HasSchema, TableInfo};
+use crate::model_traits::{HasSchema, TableColumns, TableInfo};
 use crate::query::clause::ClauseAdder;
 use crate::query::clause::ParamArgs;
 use crate::writers::alias::TableAlias;
@@ -113,9 +113,9 @@ impl JoinBuilder {
     pub(super) fn new<T>(sb: SelectBuilder<T>, outer_key: String, inner_key: String) -> JoinBuilder
     where
         T: Send + HasSchema,
-        <T as HasSchema>::Schema: TableInfo,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
     {
-        let tn = <T as HasSchema>::Schema::identifier().join(".");
+        let tn = sb.qb.table_ident().to_string();
         JoinBuilder {
             alias_asigner: sb.qb.alias_asigner.clone(),
             inner_alias: sb.qb.alias.clone(),
@@ -41,3 +41,27 @@ fn should_be_able_to_select_both_sets_of_ids() {
         assert_eq!(sql, "SELECT t1.\"id\" AS \"pid\", t2.\"id\" AS \"oid\" FROM products t1 JOIN orders t2 ON t1.\"id\" = t2.\"product_id\"");
     });
 }
+
+#[test]
+fn should_deserialize_selected_columns() {
+    use welds_connections::noop::{MockClient, MockRow};
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Total {
+        name: String,
+        total: i64,
+    }
+
+    let client = MockClient::new(Syntax::Postgres);
+    let row = MockRow::new()
+        .column("name", "Bobby".to_string())
+        .column("total", 3_i32);
+    client.expect_rows("SELECT", vec![row]);
+    let q = Product::all()
+        .select(|x| x.name)
+        .select_as(|x| x.id, "total");
+    let totals: Vec<Total> =
+        futures::executor::block_on(async { q.run_as(&client).await.unwrap() });
+    assert_eq!(totals[0].name, "Bobby");
+    assert_eq!(totals[0].total, 3);
+}
@@ -0,0 +1,28 @@
+use crate::errors::Result;
+use crate::instrument::operation;
+use crate::model_traits::{HasSchema, TableInfo};
+use crate::Syntax;
+use welds_connections::Client;
+
+/// Removes every row from the table of a model.
+///
+/// Postgres uses `TRUNCATE ... CASCADE`, tables with foreign keys pointing at this table are also emptied.
+/// The other databases don't allow truncating a table that is referenced by a foreign key,
+/// so the rows are removed with a `DELETE`
+pub async fn truncate<T>(client: &dyn Client) -> Result<()>
+where
+    T: HasSchema,
+    <T as HasSchema>::Schema: TableInfo,
+{
+    let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+    let sql = write(client.syntax(), &identifier);
+    operation::<T, _>("truncate", client.execute(&sql, &[])).await?;
+    Ok(())
+}
+
+pub(crate) fn write(syntax: Syntax, identifier: &str) -> String {
+    match syntax {
+        Syntax::Postgres => format!("TRUNCATE TABLE {} CASCADE", identifier),
+        Syntax::Mysql | Syntax::Mssql | Syntax::Sqlite => format!("DELETE FROM {}", identifier),
+    }
+}
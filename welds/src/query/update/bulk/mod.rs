@@ -1,4 +1,5 @@
 use crate::errors::Result;
+use crate::instrument::operation;
 use crate::model_traits::UniqueIdentifier;
 use crate::model_traits::{HasSchema, TableColumns, TableInfo};
 use crate::query::builder::QueryBuilder;
@@ -188,10 +189,10 @@ where
     {
         let next_params = NextParam::new(syntax);
         let sets = self.sets.as_slice();
-        let alias = <T as HasSchema>::Schema::identifier().join(".");
+        let alias = self.query_builder.table_ident().to_string();
 
         join_sql_parts(&[
-            build_head::<<T as HasSchema>::Schema>(syntax, &next_params, &alias, args, sets),
+            build_head(syntax, &next_params, &alias, args, sets),
             build_where_update(
                 syntax,
                 w_in,
@@ -213,24 +214,24 @@ where
         let mut w_in = WhereIn::new(&self.query_builder);
         let sql = self.sql_internal(syntax, &mut w_in, &mut args);
         let args = args.unwrap();
-        let _results = client.execute(&sql, &args).await?;
+        let _results = operation::<T, _>("bulk_update", client.execute(&sql, &args)).await?;
 
         Ok(())
     }
 }
 
-fn build_head<'s, 'args, 'p, S>(
+fn build_head<'s, 'args, 'p>(
     syntax: Syntax,
     next_params: &NextParam,
-    alias: &str,
+    tn: &str,
     args: &'args mut Option<ParamArgs<'p>>,
     sets: &'s [Box<dyn AssignmentAdder>],
 ) -> Option<String>
 where
     's: 'p,
-    S: TableInfo + TableColumns,
 {
-    let tn = S::identifier().join(".");
+    // use fulltable name for alias when updating
+    let alias = tn;
 
     let mut set_parts: Vec<String> = Vec::default();
 
@@ -275,8 +276,7 @@ where
     }
 
     // use fulltable name for alias when updating
-    let tableparts = T::Schema::identifier();
-    let outer_tablealias = tableparts.join(".");
+    let outer_tablealias = qb.table_ident().to_string();
 
     if let Some(p) = w_in.clause(syntax, &outer_tablealias, next_params) {
         where_sql.push(p);
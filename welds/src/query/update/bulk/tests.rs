@@ -104,3 +104,24 @@ fn should_be_able_to_write_a_custom_set_statement() {
         assert_eq!(expected, &ran_sql);
     });
 }
+
+#[test]
+fn should_be_able_to_update_another_table() {
+    futures::executor::block_on(async move {
+        let q = QueryBuilder::<Product>::new()
+            .in_table("nums_2026_10")
+            .unwrap()
+            .in_schema("tenant_a")
+            .unwrap()
+            .where_col(|p| p.b.equal(2));
+        let bulk = q.set(|p| p.a, 1);
+
+        let client = welds_connections::noop::build(Syntax::Postgres);
+        let _ = bulk.run(&client).await;
+        let ran_sql = client.last_sql().unwrap();
+
+        let expected =
+            "UPDATE tenant_a.nums_2026_10 SET \"a\"=$1 WHERE ( tenant_a.nums_2026_10.b = $2 )";
+        assert_eq!(expected, &ran_sql);
+    });
+}
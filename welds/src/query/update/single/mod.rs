@@ -1,12 +1,15 @@
 use crate::errors::{Result, WeldsError};
+use crate::instrument::operation;
 use crate::model_traits::hooks::{AfterUpdate, BeforeUpdate};
-use crate::model_traits::{HasSchema, TableColumns, TableInfo, UpdateFromRow, WriteToArgs};
+use crate::model_traits::{
+    HasSchema, TableColumns, TableIdent, TableInfo, UpdateFromRow, WriteToArgs,
+};
 use crate::query::clause::ParamArgs;
 use crate::writers::ColumnWriter;
 use crate::writers::NextParam;
 use welds_connections::Client;
 
-pub async fn update_one<T>(obj: &mut T, client: &dyn Client) -> Result<()>
+pub async fn update_one<T>(obj: &mut T, table: &TableIdent, client: &dyn Client) -> Result<()>
 where
     T: WriteToArgs + HasSchema,
     <T as HasSchema>::Schema: TableInfo + TableColumns,
@@ -20,7 +23,7 @@ where
     let col_writer = ColumnWriter::new(syntax);
     let next_params = NextParam::new(syntax);
 
-    let identifier = <<T as HasSchema>::Schema>::identifier().join(".");
+    let identifier = table.to_string();
     let columns = <<T as HasSchema>::Schema as TableColumns>::columns();
     let pks = <<T as HasSchema>::Schema as TableColumns>::primary_keys();
     if pks.is_empty() {
@@ -29,7 +32,7 @@ where
     let mut sets = Vec::default();
 
     for col in columns {
-        if !pks.contains(&col) {
+        if !pks.contains(&col) && obj.writes_column(col.name()) {
             obj.bind(col.name(), &mut args)?;
             let p = next_params.next();
             let colname = col_writer.excape(col.name());
@@ -53,7 +56,7 @@ where
 
     let sql = format!("UPDATE {} SET {} where {}", identifier, sets, wheres);
 
-    client.execute(&sql, &args).await?;
+    operation::<T, _>("update", client.execute(&sql, &args)).await?;
 
     AfterUpdate::after(obj);
     Ok(())
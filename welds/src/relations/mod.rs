@@ -1,4 +1,4 @@
-use crate::model_traits::UniqueIdentifier;
+use crate::model_traits::{Column, UniqueIdentifier};
 use std::marker::PhantomData;
 
 pub struct BelongsTo<T> {
@@ -75,6 +75,24 @@ pub trait Relationship<R> {
         R2: UniqueIdentifier;
 }
 
+/// Runtime info about a BelongsTo relationship on a model
+#[derive(Debug, Clone)]
+pub struct BelongsToInfo {
+    /// The name of the relationship field
+    pub field: &'static str,
+    /// The column on this model that points at the other model
+    pub foreign_key: &'static str,
+    /// The table of the other model
+    pub table: &'static [&'static str],
+    /// The primary keys of the other model
+    pub primary_keys: Vec<Column>,
+}
+
+/// Lists the relationships on a model at runtime
+pub trait ListRelations {
+    fn belongs_to() -> Vec<BelongsToInfo>;
+}
+
 pub trait HasRelations {
     type Relation: Default;
 }
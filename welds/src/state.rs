@@ -3,7 +3,7 @@ use crate::model_traits::hooks::{
     AfterCreate, AfterDelete, AfterUpdate, BeforeCreate, BeforeDelete, BeforeUpdate,
 };
 use crate::model_traits::{
-    ColumnDefaultCheck, HasSchema, TableColumns, TableInfo, UpdateFromRow, WriteToArgs,
+    ColumnDefaultCheck, HasSchema, TableColumns, TableIdent, TableInfo, UpdateFromRow, WriteToArgs,
 };
 use crate::query::delete;
 use crate::query::insert;
@@ -31,6 +31,7 @@ pub struct DbState<T> {
     _t: PhantomData<T>,
     inner: T,
     status: DbStatus,
+    pub(crate) table: Option<TableIdent>,
 }
 
 impl<T> std::fmt::Debug for DbState<T>
@@ -74,6 +75,7 @@ impl<T> DbState<T> {
             _t: PhantomData,
             inner,
             status: DbStatus::NotInDatabase,
+            table: None,
         }
     }
 
@@ -83,9 +85,29 @@ impl<T> DbState<T> {
             _t: PhantomData,
             inner,
             status: DbStatus::NotModified,
+            table: None,
         }
     }
 
+    /// Returns the table the entity is saved to, if it isn't the model's own table
+    pub fn db_table(&self) -> Option<&TableIdent> {
+        self.table.as_ref()
+    }
+
+    /// Save/delete the entity in a different table (or schema) with the same columns as the model.
+    /// `None` uses the model's own table.
+    ///
+    /// Entities loaded by a query using `in_table`/`in_schema` are already set to that table.
+    /// Returns an error if the name has characters that aren't valid in a table/schema name.
+    pub fn set_db_table(&mut self, table: impl Into<Option<TableIdent>>) -> Result<()> {
+        let table = table.into();
+        if let Some(table) = &table {
+            table.validate()?;
+        }
+        self.table = table;
+        Ok(())
+    }
+
     /// Saves the inner T to the database. Results in an insert or update if needed. If no change
     /// has been detected on the inner T, No operation will occur
     ///
@@ -97,13 +119,14 @@ impl<T> DbState<T> {
         T: BeforeCreate + AfterCreate,
         T: BeforeUpdate + AfterUpdate,
     {
+        let table = self.table_or_default();
         match self.status {
             DbStatus::NotModified => {}
             DbStatus::Edited => {
-                update::update_one(&mut self.inner, client).await?;
+                update::update_one(&mut self.inner, &table, client).await?;
             }
             DbStatus::NotInDatabase => {
-                insert::insert_one(&mut self.inner, client).await?;
+                insert::insert_one_into(&mut self.inner, &table, client).await?;
             }
         }
         self.status = DbStatus::NotModified;
@@ -117,12 +140,13 @@ impl<T> DbState<T> {
         <T as HasSchema>::Schema: TableInfo + TableColumns,
         T: BeforeDelete + AfterDelete,
     {
+        let table = self.table_or_default();
         match self.status {
             DbStatus::NotModified => {
-                delete::delete_one(&self.inner, client).await?;
+                delete::delete_one(&self.inner, &table, client).await?;
             }
             DbStatus::Edited => {
-                delete::delete_one(&self.inner, client).await?;
+                delete::delete_one(&self.inner, &table, client).await?;
             }
             DbStatus::NotInDatabase => {}
         }
@@ -130,6 +154,16 @@ impl<T> DbState<T> {
         Ok(())
     }
 
+    fn table_or_default(&self) -> TableIdent
+    where
+        T: HasSchema,
+        <T as HasSchema>::Schema: TableInfo + TableColumns,
+    {
+        self.table
+            .clone()
+            .unwrap_or_else(|| TableIdent::from_model::<T>())
+    }
+
     /// Consumes the DbState, returning the wrapped value. The inner value is nolonger connected to
     /// welds and can nolonger be saved/created/deleted
     pub fn into_inner(self) -> T {